@@ -3,7 +3,7 @@ use sp_io::TestExternalities;
 
 use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     Storage,
 };
 
@@ -12,7 +12,7 @@ use frame_support::{
     parameter_types,
     traits::Everything,
 };
-use frame_support::traits::ConstU32;
+use frame_support::traits::{ConstU32, ConstU64};
 use frame_system as system;
 
 use pallet_permissions::{
@@ -110,6 +110,7 @@ use crate::utils::moderation_utils::MockModeration;
 
 impl pallet_permissions::Config for TestRuntime {
     type DefaultSpacePermissions = DefaultSpacePermissions;
+    type NamespacedPermissions = (Roles,);
 }
 
 parameter_types! {
@@ -118,8 +119,11 @@ parameter_types! {
 
 impl pallet_posts::Config for TestRuntime {
     type RuntimeEvent = RuntimeEvent;
+    type SpaceStats = Spaces;
     type MaxCommentDepth = MaxCommentDepth;
     type IsPostBlocked = MockModeration;
+    type BasePostDeposit = ConstU64<0>;
+    type BaseCommentDeposit = ConstU64<0>;
     type WeightInfo = ();
 }
 
@@ -127,11 +131,13 @@ impl pallet_profiles::Config for TestRuntime {
     type RuntimeEvent = RuntimeEvent;
     type SpacePermissionsProvider = Spaces;
     type SpacesInterface = Spaces;
+    type MaxLinkedAddresses = ConstU32<10>;
     type WeightInfo = ();
 }
 
 impl pallet_reactions::Config for TestRuntime {
     type RuntimeEvent = RuntimeEvent;
+    type BaseReactionDeposit = ConstU64<0>;
     type WeightInfo = ();
 }
 
@@ -144,19 +150,30 @@ impl pallet_roles::Config for TestRuntime {
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
     type SpacePermissionsProvider = Spaces;
     type SpaceFollows = SpaceFollows;
+    type Currency = Balances;
+    type JoinRequestDeposit = ConstU64<0>;
+    type DomainsProvider = ();
     type IsAccountBlocked = MockModeration;
     type IsContentBlocked = MockModeration;
+    type SpaceSettings = Spaces;
+    type OnRoleRemoved = ();
+    type RoleProposalLifetime = ConstU64<100>;
+    type RoleProposalDeposit = ConstU64<0>;
+    type InviteSigner = UintAuthorityId;
+    type InviteSignature = TestSignature;
     type WeightInfo = ();
 }
 
 impl pallet_space_follows::Config for TestRuntime {
     type RuntimeEvent = RuntimeEvent;
+    type SpaceStats = Spaces;
     type WeightInfo = pallet_space_follows::weights::SubstrateWeight<TestRuntime>;
 }
 
 impl pallet_space_ownership::Config for TestRuntime {
     type RuntimeEvent = RuntimeEvent;
-    type ProfileManager = Profiles;
+    type MaxExpiredTransfersPerBlock = ConstU32<10>;
+    type MaxOwnershipHistoryLength = ConstU32<10>;
     type WeightInfo = ();
 }
 
@@ -166,7 +183,19 @@ impl pallet_spaces::Config for TestRuntime {
     type SpaceFollows = SpaceFollows;
     type IsAccountBlocked = MockModeration;
     type IsContentBlocked = MockModeration;
+    type SpacePosts = Posts;
+    type ProfileManager = Profiles;
+    type OnSpaceDeleted = (SpaceFollows, Roles, SpaceOwnership);
+    type OnSpaceOwnerChanged = SpaceOwnership;
+    type SpaceSettingsHandler = (Posts, Roles);
+    type SpaceOwnerOrigin = frame_system::EnsureSigned<AccountId>;
+    type SpaceAccounts = ();
+    type Currency = Balances;
+    type BaseSpaceDeposit = ConstU64<0>;
+    type StorageByteDeposit = ConstU64<0>;
     type MaxSpacesPerAccount = ConstU32<100>;
+    type MinHandleLen = ConstU32<5>;
+    type MaxHandleLen = ConstU32<50>;
     type WeightInfo = ();
 }
 
@@ -40,6 +40,7 @@ pub fn _create_role(
         time_to_live.unwrap_or_default(), // Should return 'None'
         content.unwrap_or_else(default_role_content_ipfs),
         permissions.unwrap_or_else(permission_set_default),
+        0,
     )
 }
 
@@ -56,6 +57,7 @@ pub fn _grant_role(
         origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
         role_id.unwrap_or(ROLE1),
         users.unwrap_or_else(|| vec![User::Account(ACCOUNT2)]),
+        None,
     )
 }
 
@@ -18,6 +18,7 @@ pub(crate) fn _transfer_space_ownership(
         origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
         space_id.unwrap_or(SPACE1),
         transfer_to.unwrap_or(ACCOUNT2),
+        None,
     )
 }
 
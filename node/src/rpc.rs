@@ -42,7 +42,7 @@ where
 		+ Sync
 		+ 'static,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
-	C::Api: pallet_domains_rpc::DomainsRuntimeApi<Block, Balance>,
+	C::Api: pallet_domains_rpc::DomainsRuntimeApi<Block, AccountId, Balance>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
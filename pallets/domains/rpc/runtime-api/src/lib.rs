@@ -7,9 +7,14 @@ use sp_runtime::traits::MaybeDisplay;
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
-	pub trait DomainsApi<Balance> where
+	#[api_version(2)]
+	pub trait DomainsApi<AccountId, Balance> where
+		AccountId: Codec,
 		Balance: Codec + MaybeDisplay,
 	{
 		fn calculate_price(subdomain: Vec<u8>) -> Option<Balance>;
+
+		/// A domain that an account has chosen as its primary one, if it is still valid.
+		fn primary_domain(account: AccountId) -> Option<Vec<u8>>;
 	}
 }
@@ -4,7 +4,7 @@ use std::{convert::TryInto, sync::Arc};
 
 use codec::Codec;
 use jsonrpsee::{
-    core::RpcResult,
+    core::{DeserializeOwned, RpcResult},
     proc_macros::rpc,
     types::error::{CallError, ErrorObject},
 };
@@ -19,12 +19,15 @@ use sp_runtime::{
 pub use pallet_domains_rpc_runtime_api::DomainsApi as DomainsRuntimeApi;
 
 #[rpc(client, server)]
-pub trait DomainsApi<BlockHash, ResponseType> {
+pub trait DomainsApi<BlockHash, AccountId, ResponseType> {
     #[method(name = "domains_calculatePrice")]
     fn calculate_price(&self, subdomain: Vec<u8>, at: Option<BlockHash>) -> RpcResult<Option<ResponseType>>;
+
+    #[method(name = "domains_primaryDomain")]
+    fn primary_domain(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Option<Vec<u8>>>;
 }
 
-/// Provides RPC method to query a domain price.
+/// Provides RPC methods to query a domain price and a primary domain of an account.
 pub struct Domains<C, P> {
     /// Shared reference to the client.
     client: Arc<C>,
@@ -52,15 +55,25 @@ impl From<Error> for i32 {
     }
 }
 
-impl<C, Block, Balance>
+fn map_err(error: impl ToString, desc: &'static str) -> CallError {
+    CallError::Custom(ErrorObject::owned(
+        Error::RuntimeError.into(),
+        desc,
+        Some(error.to_string()),
+    ))
+}
+
+impl<C, Block, AccountId, Balance>
 DomainsApiServer<
     <Block as BlockT>::Hash,
+    AccountId,
     Balance,
 > for Domains<C, Block>
     where
         Block: BlockT,
         C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
-        C::Api: DomainsRuntimeApi<Block, Balance>,
+        C::Api: DomainsRuntimeApi<Block, AccountId, Balance>,
+        AccountId: Codec + DeserializeOwned + Send + Sync + 'static,
         Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + Send + Sync + 'static,
 {
     fn calculate_price(
@@ -71,18 +84,25 @@ DomainsApiServer<
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
 
-        fn map_err(error: impl ToString, desc: &'static str) -> CallError {
-            CallError::Custom(ErrorObject::owned(
-                Error::RuntimeError.into(),
-                desc,
-                Some(error.to_string()),
-            ))
-        }
-
         let res = api
             .calculate_price(&at, subdomain)
             .map_err(|e| map_err(e, "Unable to calculate price for domain."))?;
 
         Ok(res)
     }
+
+    fn primary_domain(
+        &self,
+        account: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<Vec<u8>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        let res = api
+            .primary_domain(&at, account)
+            .map_err(|e| map_err(e, "Unable to get primary domain of account."))?;
+
+        Ok(res)
+    }
 }
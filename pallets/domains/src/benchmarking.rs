@@ -187,6 +187,17 @@ benchmarks! {
 		assert_last_event::<T>(Event::DomainMetaUpdated { who, domain }.into());
 	}
 
+	set_primary_domain {
+		let who = account_with_balance::<T>();
+		let domain = add_domain::<T>(&who)?;
+		let domain_lc = Pallet::<T>::lower_domain_then_bound(&domain);
+	}: _(RawOrigin::Signed(who.clone()), Some(domain))
+	verify {
+		assert_last_event::<T>(
+			Event::PrimaryDomainUpdated { who, domain: Some(domain_lc) }.into()
+		);
+	}
+
 	reserve_words {
 		let s in 1 .. T::DomainsInsertLimit::get() => ();
 		let words = mock_bounded_string_array::<T>(s as usize);
@@ -38,7 +38,11 @@ pub mod pallet {
     use sp_runtime::traits::{Saturating, StaticLookup, Zero};
     use sp_std::{cmp::Ordering, convert::TryInto, vec::Vec};
 
-    use subsocial_support::ensure_content_is_valid;
+    use subsocial_support::{
+        cleanup::{process_block_queue, WeightMeter},
+        ensure_content_is_valid,
+        traits::DomainsProvider,
+    };
 
     #[pallet::config]
     pub trait Config: frame_system::Config + pallet_timestamp::Config {
@@ -138,6 +142,30 @@ pub mod pallet {
             DomainName<T>,
         >;
 
+    /// A domain chosen by an account to be displayed as its name.
+    ///
+    /// It is resolved only while the account still owns the domain and the domain is not expired,
+    /// see [`Pallet::primary_domain`]. It is cleared in `on_idle` once the domain expires.
+    ///
+    /// TWOX-NOTE: Safe as `AccountId`s are crypto hashes anyway.
+    #[pallet::storage]
+    pub(super) type PrimaryDomainByAccount<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, DomainName<T>>;
+
+    /// Accounts indexed by the block at which their primary domains expire,
+    /// so that primary domains are cleared in `on_idle` once they expire.
+    ///
+    /// TWOX-NOTE: Safe as `AccountId`s are crypto hashes anyway.
+    #[pallet::storage]
+    pub(super) type PrimaryDomainsExpiringAt<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, T::AccountId, ()>;
+
+    /// The first block, whose expired primary domains may not be cleared yet.
+    #[pallet::storage]
+    #[pallet::getter(fn next_primary_domain_expiration_block)]
+    pub(super) type NextPrimaryDomainExpirationBlock<T: Config> =
+        StorageValue<_, T::BlockNumber, ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn is_tld_supported)]
     pub(super) type SupportedTlds<T: Config> =
@@ -178,6 +206,8 @@ pub mod pallet {
         NewWordsReserved { count: u32 },
         /// Added support for new TLDs (top-level domains).
         NewTldsSupported { count: u32 },
+        /// The primary domain of an account was set or cleared.
+        PrimaryDomainUpdated { who: T::AccountId, domain: Option<DomainName<T>> },
     }
 
     #[pallet::error]
@@ -221,6 +251,17 @@ pub mod pallet {
         InsufficientBalanceToReserveDeposit,
         /// There are insufficient funds to pay for the domain and reserve the deposit on it.
         InsufficientBalanceToRegisterDomain,
+        /// A new primary domain is the same as the old one.
+        PrimaryDomainNotChanged,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            let mut meter = WeightMeter::new(remaining_weight);
+            Self::clean_expired_primary_domains(now, &mut meter);
+            meter.consumed()
+        }
     }
 
     #[pallet::call]
@@ -421,6 +462,44 @@ pub mod pallet {
 
             Ok(Pays::No.into())
         }
+
+        /// Sets one of the caller's domains as its primary domain, or clears it if `None`.
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_primary_domain())]
+        pub fn set_primary_domain(
+            origin: OriginFor<T>,
+            domain_opt: Option<DomainName<T>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let domain_lc_opt = domain_opt.map(|domain| Self::lower_domain_then_bound(&domain));
+
+            let mut expires_at_opt = None;
+            if let Some(domain_lc) = &domain_lc_opt {
+                let meta = Self::require_domain(domain_lc.clone())?;
+
+                Self::ensure_allowed_to_update_domain(&meta, &sender)?;
+                ensure!(!Self::is_domain_expired(&meta), Error::<T>::DomainHasExpired);
+                expires_at_opt = Some(meta.expires_at);
+            }
+
+            ensure!(
+                PrimaryDomainByAccount::<T>::get(&sender) != domain_lc_opt,
+                Error::<T>::PrimaryDomainNotChanged,
+            );
+
+            match &domain_lc_opt {
+                Some(domain_lc) => PrimaryDomainByAccount::<T>::insert(&sender, domain_lc),
+                None => PrimaryDomainByAccount::<T>::remove(&sender),
+            }
+            // An entry of a previous primary domain is skipped once its' block is cleaned up.
+            if let Some(expires_at) = expires_at_opt {
+                PrimaryDomainsExpiringAt::<T>::insert(expires_at, &sender, ());
+            }
+
+            Self::deposit_event(Event::PrimaryDomainUpdated { who: sender, domain: domain_lc_opt });
+            Ok(())
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -650,6 +729,66 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Get the primary domain of an account.
+        ///
+        /// A primary domain is ignored once the domain expires or the account no longer owns it.
+        pub fn primary_domain(account: &T::AccountId) -> Option<DomainName<T>> {
+            let domain = PrimaryDomainByAccount::<T>::get(account)?;
+            let meta = Self::registered_domain(&domain)?;
+
+            let is_valid = &meta.owner == account && !Self::is_domain_expired(&meta);
+            is_valid.then_some(domain)
+        }
+
+        /// Clear the primary domain of an account, if it is a given domain.
+        ///
+        /// Should be called whenever an account loses a domain: once the domain expires,
+        /// or when its' ownership changes.
+        pub(crate) fn clear_primary_domain(account: &T::AccountId, domain_lc: &DomainName<T>) {
+            if PrimaryDomainByAccount::<T>::get(account).as_ref() != Some(domain_lc) {
+                return
+            }
+
+            PrimaryDomainByAccount::<T>::remove(account);
+            Self::deposit_event(Event::PrimaryDomainUpdated { who: account.clone(), domain: None });
+        }
+
+        /// Clear primary domains, that expired at or before a given block,
+        /// or are no longer owned by their accounts, while there is enough weight left.
+        pub(crate) fn clean_expired_primary_domains(now: T::BlockNumber, meter: &mut WeightMeter) {
+            let db_weight = T::DbWeight::get();
+            if !meter.try_consume(db_weight.reads_writes(1, 1)) {
+                return
+            }
+
+            let next_block = process_block_queue(
+                Self::next_primary_domain_expiration_block(),
+                now,
+                meter,
+                db_weight.reads(1),
+                |block| PrimaryDomainsExpiringAt::<T>::iter_key_prefix(block).next(),
+                |_| <T as Config>::WeightInfo::clear_expired_primary_domain(),
+                |block, account| {
+                    PrimaryDomainsExpiringAt::<T>::remove(block, &account);
+
+                    if let Some(domain_lc) = PrimaryDomainByAccount::<T>::get(&account) {
+                        let is_lost = Self::registered_domain(&domain_lc).map_or(true, |meta| {
+                            meta.owner != account || Self::is_domain_expired(&meta)
+                        });
+                        if is_lost {
+                            Self::clear_primary_domain(&account, &domain_lc);
+                        }
+                    }
+                },
+            );
+
+            NextPrimaryDomainExpirationBlock::<T>::put(next_block);
+        }
+
+        fn is_domain_expired(domain_meta: &DomainMeta<T>) -> bool {
+            System::<T>::block_number() >= domain_meta.expires_at
+        }
+
         pub fn try_reserve_deposit(
             depositor: &T::AccountId,
             old_deposit: BalanceOf<T>,
@@ -713,4 +852,13 @@ pub mod pallet {
             )
         }
     }
+
+    impl<T: Config> DomainsProvider<T::AccountId> for Pallet<T> {
+        fn owns_any_domain(account: &T::AccountId) -> bool {
+            Self::domains_by_owner(account).into_iter().any(|domain| {
+                Self::registered_domain(domain)
+                    .map_or(false, |meta| !Self::is_domain_expired(&meta))
+            })
+        }
+    }
 }
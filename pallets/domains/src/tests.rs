@@ -1,12 +1,13 @@
-use frame_support::{assert_noop, assert_ok, traits::LockableCurrency};
+use frame_support::{assert_noop, assert_ok, traits::{Hooks, LockableCurrency}, weights::Weight};
 use frame_support::traits::WithdrawReasons;
 use sp_runtime::{DispatchError::BadOrigin, traits::Zero};
 use sp_std::convert::TryInto;
 
 use subsocial_support::mock_functions::{another_valid_content_ipfs, invalid_content_ipfs, valid_content_ipfs};
 use subsocial_support::new_who_and_when;
+use subsocial_support::traits::DomainsProvider;
 
-use crate::{DomainByInnerValue, Event, mock::*};
+use crate::{DomainByInnerValue, Event, PrimaryDomainByAccount, RegisteredDomains, mock::*};
 use crate::Error;
 use crate::types::*;
 
@@ -620,6 +621,179 @@ fn set_prices_config_should_work() {
     });
 }
 
+// `DomainsProvider` tests
+
+#[test]
+fn owns_any_domain_should_ignore_expired_domains() {
+    ExtBuilder::default().build_with_default_domain_registered().execute_with(|| {
+        assert!(<Domains as DomainsProvider<AccountId>>::owns_any_domain(&DOMAIN_OWNER));
+        assert!(!<Domains as DomainsProvider<AccountId>>::owns_any_domain(&DUMMY_ACCOUNT));
+
+        System::set_block_number(ExtBuilder::default().reservation_period_limit + 1);
+
+        assert!(!<Domains as DomainsProvider<AccountId>>::owns_any_domain(&DOMAIN_OWNER));
+    });
+}
+
+// `set_primary_domain` tests
+
+#[test]
+fn set_primary_domain_should_work() {
+    ExtBuilder::default().build_with_default_domain_registered().execute_with(|| {
+        assert_eq!(Domains::primary_domain(&DOMAIN_OWNER), None);
+
+        assert_ok!(Domains::set_primary_domain(
+            RuntimeOrigin::signed(DOMAIN_OWNER),
+            Some(default_domain()),
+        ));
+
+        assert_eq!(Domains::primary_domain(&DOMAIN_OWNER), Some(default_domain_lc()));
+
+        System::assert_last_event(Event::<Test>::PrimaryDomainUpdated {
+            who: DOMAIN_OWNER,
+            domain: Some(default_domain_lc()),
+        }.into());
+
+        assert_ok!(Domains::set_primary_domain(RuntimeOrigin::signed(DOMAIN_OWNER), None));
+        assert_eq!(Domains::primary_domain(&DOMAIN_OWNER), None);
+    });
+}
+
+#[test]
+fn set_primary_domain_should_fail_when_domain_not_found() {
+    ExtBuilder::default().build_with_default_domain_registered().execute_with(|| {
+        assert_noop!(
+            Domains::set_primary_domain(
+                RuntimeOrigin::signed(DOMAIN_OWNER),
+                Some(bound_domain_with_default_tld(b"unknown".to_vec())),
+            ),
+            Error::<Test>::DomainNotFound,
+        );
+    });
+}
+
+#[test]
+fn set_primary_domain_should_fail_when_not_domain_owner() {
+    ExtBuilder::default().build_with_default_domain_registered().execute_with(|| {
+        assert_noop!(
+            Domains::set_primary_domain(RuntimeOrigin::signed(DUMMY_ACCOUNT), Some(default_domain())),
+            Error::<Test>::NotDomainOwner,
+        );
+    });
+}
+
+#[test]
+fn set_primary_domain_should_fail_when_domain_has_expired() {
+    ExtBuilder::default().build_with_default_domain_registered().execute_with(|| {
+        System::set_block_number(ExtBuilder::default().reservation_period_limit + 1);
+
+        assert_noop!(
+            Domains::set_primary_domain(RuntimeOrigin::signed(DOMAIN_OWNER), Some(default_domain())),
+            Error::<Test>::DomainHasExpired,
+        );
+    });
+}
+
+#[test]
+fn set_primary_domain_should_fail_when_primary_domain_not_changed() {
+    ExtBuilder::default().build_with_default_domain_registered().execute_with(|| {
+        assert_noop!(
+            Domains::set_primary_domain(RuntimeOrigin::signed(DOMAIN_OWNER), None),
+            Error::<Test>::PrimaryDomainNotChanged,
+        );
+
+        assert_ok!(Domains::set_primary_domain(
+            RuntimeOrigin::signed(DOMAIN_OWNER),
+            Some(default_domain()),
+        ));
+
+        assert_noop!(
+            Domains::set_primary_domain(RuntimeOrigin::signed(DOMAIN_OWNER), Some(default_domain())),
+            Error::<Test>::PrimaryDomainNotChanged,
+        );
+    });
+}
+
+#[test]
+fn primary_domain_should_be_cleared_when_domain_has_expired() {
+    ExtBuilder::default().build_with_default_domain_registered().execute_with(|| {
+        assert_ok!(Domains::set_primary_domain(
+            RuntimeOrigin::signed(DOMAIN_OWNER),
+            Some(default_domain()),
+        ));
+
+        System::set_block_number(ExtBuilder::default().reservation_period_limit + 1);
+        assert_eq!(Domains::primary_domain(&DOMAIN_OWNER), None);
+
+        // The outdated primary domain can still be cleared by its account.
+        assert_ok!(Domains::set_primary_domain(RuntimeOrigin::signed(DOMAIN_OWNER), None));
+    });
+}
+
+#[test]
+fn on_idle_should_clear_expired_primary_domain() {
+    ExtBuilder::default().build_with_default_domain_registered().execute_with(|| {
+        assert_ok!(Domains::set_primary_domain(
+            RuntimeOrigin::signed(DOMAIN_OWNER),
+            Some(default_domain()),
+        ));
+        let expires_at = Domains::registered_domain(default_domain_lc()).unwrap().expires_at;
+
+        Domains::on_idle(expires_at - 1, Weight::MAX);
+        assert_eq!(PrimaryDomainByAccount::<Test>::get(DOMAIN_OWNER), Some(default_domain_lc()));
+
+        System::set_block_number(expires_at);
+        Domains::on_idle(expires_at, Weight::MAX);
+
+        assert_eq!(PrimaryDomainByAccount::<Test>::get(DOMAIN_OWNER), None);
+        System::assert_last_event(Event::<Test>::PrimaryDomainUpdated {
+            who: DOMAIN_OWNER,
+            domain: None,
+        }.into());
+    });
+}
+
+#[test]
+fn on_idle_should_skip_primary_domain_cleared_before_expiration() {
+    ExtBuilder::default().build_with_default_domain_registered().execute_with(|| {
+        assert_ok!(Domains::set_primary_domain(
+            RuntimeOrigin::signed(DOMAIN_OWNER),
+            Some(default_domain()),
+        ));
+        let expires_at = Domains::registered_domain(default_domain_lc()).unwrap().expires_at;
+
+        // The account clears its' primary domain, and then sets it again before it expires.
+        assert_ok!(Domains::set_primary_domain(RuntimeOrigin::signed(DOMAIN_OWNER), None));
+        Domains::on_idle(expires_at - 1, Weight::MAX);
+        System::reset_events();
+
+        System::set_block_number(expires_at);
+        Domains::on_idle(expires_at, Weight::MAX);
+
+        assert_eq!(PrimaryDomainByAccount::<Test>::get(DOMAIN_OWNER), None);
+        assert!(System::events().is_empty());
+    });
+}
+
+#[test]
+fn primary_domain_should_be_cleared_when_domain_owner_changed() {
+    ExtBuilder::default().build_with_default_domain_registered().execute_with(|| {
+        assert_ok!(Domains::set_primary_domain(
+            RuntimeOrigin::signed(DOMAIN_OWNER),
+            Some(default_domain()),
+        ));
+
+        RegisteredDomains::<Test>::mutate(default_domain_lc(), |meta_opt| {
+            if let Some(meta) = meta_opt {
+                meta.owner = DUMMY_ACCOUNT;
+            }
+        });
+
+        assert_eq!(Domains::primary_domain(&DOMAIN_OWNER), None);
+        assert_eq!(Domains::primary_domain(&DUMMY_ACCOUNT), None);
+    });
+}
+
 // `reserve_domains` tests
 
 #[test]
@@ -5,6 +5,9 @@
 //! DATE: 2023-08-07, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! HOSTNAME: `benchmarks-ci`, CPU: `Intel(R) Xeon(R) Platinum 8358 CPU @ 2.60GHz`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! Entries marked as "Not benchmarked yet" were added by hand after this run,
+//! and must be regenerated with the command below.
 
 // Executed Command:
     // ./scripts/../target/release/subsocial-collator
@@ -48,6 +51,8 @@ pub trait WeightInfo {
     fn set_domain_content() -> Weight;
     fn reserve_words(s: u32, ) -> Weight;
     fn support_tlds(s: u32, ) -> Weight;
+    fn set_primary_domain() -> Weight;
+    fn clear_expired_primary_domain() -> Weight;
 }
 
 /// Weights for pallet_domains using the Substrate node and recommended hardware.
@@ -117,6 +122,24 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
             .saturating_add(T::DbWeight::get().writes(313))
             .saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
         }
+            // Storage: Domains RegisteredDomains (r:1 w:0)
+            // Storage: Domains PrimaryDomainByAccount (r:1 w:1)
+            // Storage: Domains PrimaryDomainsExpiringAt (r:0 w:1)
+        fn set_primary_domain() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(26_184_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+        }
+            // Storage: Domains PrimaryDomainsExpiringAt (r:1 w:1)
+            // Storage: Domains PrimaryDomainByAccount (r:1 w:1)
+            // Storage: Domains RegisteredDomains (r:1 w:0)
+        fn clear_expired_primary_domain() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(18_420_000)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+        }
     }
 
     // For backwards compatibility and tests
@@ -185,4 +208,22 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
             .saturating_add(RocksDbWeight::get().writes(313))
             .saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
         }
+            // Storage: Domains RegisteredDomains (r:1 w:0)
+            // Storage: Domains PrimaryDomainByAccount (r:1 w:1)
+            // Storage: Domains PrimaryDomainsExpiringAt (r:0 w:1)
+        fn set_primary_domain() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(26_184_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+        }
+            // Storage: Domains PrimaryDomainsExpiringAt (r:1 w:1)
+            // Storage: Domains PrimaryDomainByAccount (r:1 w:1)
+            // Storage: Domains RegisteredDomains (r:1 w:0)
+        fn clear_expired_primary_domain() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(18_420_000)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+        }
     }
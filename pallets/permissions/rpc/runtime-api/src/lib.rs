@@ -0,0 +1,24 @@
+//! Runtime API definition for permissions pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+pub use pallet_permissions::{PermissionTrace, SpacePermission};
+pub use subsocial_support::SpaceId;
+
+sp_api::decl_runtime_apis! {
+	pub trait PermissionsApi<AccountId, RoleId> where
+		AccountId: Codec,
+		RoleId: Codec,
+	{
+		/// Check whether an account has a permission in a space without dispatching anything,
+		/// and explain which sources of permissions were checked and what each of them decided.
+		/// Returns `None` if a space does not exist.
+		fn explain_space_permission(
+			account: AccountId,
+			space_id: SpaceId,
+			permission: SpacePermission,
+		) -> Option<PermissionTrace<RoleId>>;
+	}
+}
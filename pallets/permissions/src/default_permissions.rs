@@ -56,6 +56,10 @@ parameter_types! {
       SP::UpdateEntityStatus,
 
       SP::UpdateSpaceSettings,
+
+      SP::ManageContentKeys,
+
+      SP::SpendTreasury,
     ].into_iter().collect()),
   };
 }
@@ -4,12 +4,15 @@ pub use pallet::*;
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-
-use sp_std::collections::btree_set::BTreeSet;
+use sp_std::vec::Vec;
 
 pub mod default_permissions;
+pub mod migration;
 mod types;
 
+#[cfg(test)]
+mod tests;
+
 pub use types::*;
 
 #[frame_support::pallet]
@@ -25,6 +28,16 @@ pub mod pallet {
     pub trait Config: frame_system::Config {
         #[pallet::constant]
         type DefaultSpacePermissions: Get<SpacePermissions>;
+
+        /// Pallets that declare namespaced permissions, which can be used in space permissions
+        /// and role permissions along with the core ones.
+        type NamespacedPermissions: NamespacedPermissionsRegistry;
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// A namespaced permission is not declared by any pallet.
+        UnknownNamespacedPermission,
     }
 
     impl<T: Config> Pallet<T> {
@@ -78,11 +91,79 @@ pub mod pallet {
             None
         }
 
+        /// Resolve a permission the same way as `has_user_a_space_permission` does,
+        /// but also return every checked source of permissions along with its outcome.
+        pub fn trace_space_permission<RoleId>(
+            ctx: SpacePermissionsContext,
+            permission: SpacePermission,
+        ) -> (Option<bool>, Vec<PermissionCheckStep<RoleId>>) {
+            let perms_by_role = Self::resolve_space_perms(ctx.space_perms);
+            let mut steps = Vec::new();
+
+            if permission.is_present_in_role(perms_by_role.none) {
+                steps.push(PermissionCheckStep {
+                    source: PermissionSource::Nobody,
+                    outcome: PermissionCheckOutcome::Forbidden,
+                });
+                return (Some(false), steps)
+            }
+            steps.push(PermissionCheckStep {
+                source: PermissionSource::Nobody,
+                outcome: PermissionCheckOutcome::NotIncluded,
+            });
+
+            let is_space_owner = ctx.is_space_owner;
+            let is_follower = is_space_owner || ctx.is_space_follower;
+
+            let sources = [
+                (PermissionSource::Everyone, perms_by_role.everyone, true),
+                (PermissionSource::Follower, perms_by_role.follower, is_follower),
+                (PermissionSource::SpaceOwner, perms_by_role.space_owner, is_space_owner),
+            ];
+            for (source, perms, applies) in sources {
+                let outcome = match (permission.is_present_in_role(perms), applies) {
+                    (true, true) => PermissionCheckOutcome::Granted,
+                    (true, false) => PermissionCheckOutcome::NotApplicable,
+                    (false, _) => PermissionCheckOutcome::NotIncluded,
+                };
+                steps.push(PermissionCheckStep { source, outcome });
+
+                if outcome == PermissionCheckOutcome::Granted {
+                    return (Some(true), steps)
+                }
+            }
+
+            (None, steps)
+        }
+
+        pub fn ensure_permissions_are_declared(permissions: &SpacePermissionSet) -> DispatchResult {
+            for permission in permissions.namespaced() {
+                ensure!(
+                    T::NamespacedPermissions::is_permission_declared(permission),
+                    Error::<T>::UnknownNamespacedPermission
+                );
+            }
+            Ok(())
+        }
+
+        pub fn ensure_space_permissions_are_declared(
+            permissions: &SpacePermissions,
+        ) -> DispatchResult {
+            [
+                &permissions.none,
+                &permissions.everyone,
+                &permissions.follower,
+                &permissions.space_owner,
+            ]
+            .into_iter()
+            .flatten()
+            .try_for_each(Self::ensure_permissions_are_declared)
+        }
+
         pub fn override_permissions(mut overrides: SpacePermissions) -> SpacePermissions {
-            overrides.none = overrides.none.map(|mut none_permissions_set| {
-                none_permissions_set
-                    .extend(T::DefaultSpacePermissions::get().none.unwrap_or_default());
+            overrides.none = overrides.none.map(|none_permissions_set| {
                 none_permissions_set
+                    .union(&T::DefaultSpacePermissions::get().none.unwrap_or_default())
             });
 
             overrides
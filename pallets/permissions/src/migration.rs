@@ -0,0 +1,71 @@
+/// Types used before `SpacePermissionSet` became a bitset.
+///
+/// Pallets that store space permissions can use them to decode the old values in their
+/// storage migrations.
+pub mod v0 {
+    use codec::{Decode, Encode};
+    use sp_std::collections::btree_set::BTreeSet;
+
+    use crate::SpacePermission;
+
+    pub type SpacePermissionSet = BTreeSet<SpacePermission>;
+
+    #[derive(Encode, Decode, Default, Clone, Eq, PartialEq)]
+    pub struct SpacePermissions {
+        pub none: Option<SpacePermissionSet>,
+        pub everyone: Option<SpacePermissionSet>,
+        pub follower: Option<SpacePermissionSet>,
+        pub space_owner: Option<SpacePermissionSet>,
+    }
+
+    /// Converts an old permission set into a bitset.
+    pub fn migrate_permission_set(old: SpacePermissionSet) -> crate::SpacePermissionSet {
+        old.into_iter().collect()
+    }
+
+    impl From<SpacePermissions> for crate::SpacePermissions {
+        fn from(old: SpacePermissions) -> Self {
+            Self {
+                none: old.none.map(migrate_permission_set),
+                everyone: old.everyone.map(migrate_permission_set),
+                follower: old.follower.map(migrate_permission_set),
+                space_owner: old.space_owner.map(migrate_permission_set),
+            }
+        }
+    }
+}
+
+/// Types used before `SpacePermissionSet` could contain namespaced permissions,
+/// when it was a bitset of core permissions only.
+///
+/// Pallets that store space permissions can use them to decode the old values in their
+/// storage migrations.
+pub mod v1 {
+    use codec::{Decode, Encode};
+
+    pub type SpacePermissionSet = u64;
+
+    #[derive(Encode, Decode, Default, Clone, Eq, PartialEq)]
+    pub struct SpacePermissions {
+        pub none: Option<SpacePermissionSet>,
+        pub everyone: Option<SpacePermissionSet>,
+        pub follower: Option<SpacePermissionSet>,
+        pub space_owner: Option<SpacePermissionSet>,
+    }
+
+    /// Converts an old bitset into a permission set without namespaced permissions.
+    pub fn migrate_permission_set(old: SpacePermissionSet) -> crate::SpacePermissionSet {
+        crate::SpacePermissionSet::from_core_bits(old)
+    }
+
+    impl From<SpacePermissions> for crate::SpacePermissions {
+        fn from(old: SpacePermissions) -> Self {
+            Self {
+                none: old.none.map(migrate_permission_set),
+                everyone: old.everyone.map(migrate_permission_set),
+                follower: old.follower.map(migrate_permission_set),
+                space_owner: old.space_owner.map(migrate_permission_set),
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use codec::{Decode, Encode};
+use sp_std::collections::btree_set::BTreeSet;
+
+use crate::{NamespacedPermission, SpacePermission as SP, SpacePermissionSet};
+
+const TREASURY_SPEND: NamespacedPermission = NamespacedPermission::new(*b"treasury", 1);
+
+fn all_core_permissions() -> Vec<SP> {
+    SpacePermissionSet::decode(
+        &mut &(u64::MAX, BTreeSet::<NamespacedPermission>::new()).encode()[..],
+    )
+    .unwrap()
+    .iter()
+    .collect()
+}
+
+#[test]
+fn space_permission_set_should_be_encoded_as_core_bits_and_namespaced_permissions() {
+    let set: SpacePermissionSet =
+        [SP::ManageRoles, SP::UpdateSpace, SP::Namespaced(TREASURY_SPEND)]
+            .into_iter()
+            .collect();
+
+    let core_bits: u64 = (1 << SP::ManageRoles.encode()[0]) | (1 << SP::UpdateSpace.encode()[0]);
+    let expected_encoding = (core_bits, BTreeSet::from([TREASURY_SPEND])).encode();
+
+    assert_eq!(set.encode(), expected_encoding);
+    assert_eq!(SpacePermissionSet::decode(&mut &expected_encoding[..]).unwrap(), set);
+}
+
+#[test]
+fn every_core_permission_should_use_the_bit_at_its_scale_index() {
+    let permissions = all_core_permissions();
+
+    // Core permissions occupy the lowest bits without gaps, in the order of their variants.
+    assert_eq!(permissions.first(), Some(&SP::ManageRoles));
+    assert_eq!(permissions.last(), Some(&SP::SpendTreasury));
+
+    for (index, permission) in permissions.into_iter().enumerate() {
+        assert_eq!(permission.encode(), vec![index as u8]);
+
+        let set = SpacePermissionSet::from_iter([permission]);
+        assert_eq!(set.encode(), (1u64 << index, BTreeSet::<NamespacedPermission>::new()).encode());
+    }
+}
+
+#[test]
+fn namespaced_permission_should_not_take_a_core_bit() {
+    let set = SpacePermissionSet::from_iter([SP::Namespaced(TREASURY_SPEND)]);
+
+    assert!(!set.is_empty());
+    assert!(all_core_permissions().iter().all(|permission| !set.contains(permission)));
+    assert_eq!(set.encode(), (0u64, BTreeSet::from([TREASURY_SPEND])).encode());
+}
+
+#[test]
+fn space_permission_set_should_be_serialized_as_permission_names() {
+    let set: SpacePermissionSet =
+        [SP::UpdateSpace, SP::ManageRoles, SP::Namespaced(TREASURY_SPEND)]
+            .into_iter()
+            .collect();
+
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(
+        json,
+        r#"["ManageRoles","UpdateSpace",{"Namespaced":{"namespace":[116,114,101,97,115,117,114,121],"index":1}}]"#
+    );
+    assert_eq!(serde_json::from_str::<SpacePermissionSet>(&json).unwrap(), set);
+}
+
+#[test]
+fn every_core_permission_name_should_survive_serde_round_trip() {
+    let permissions = all_core_permissions();
+    let set: SpacePermissionSet = permissions.iter().copied().collect();
+
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(serde_json::from_str::<Vec<SP>>(&json).unwrap(), permissions);
+    assert_eq!(serde_json::from_str::<SpacePermissionSet>(&json).unwrap(), set);
+}
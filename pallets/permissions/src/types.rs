@@ -1,7 +1,8 @@
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::dispatch::{DispatchError, DispatchResult};
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
+use sp_std::{collections::btree_set::BTreeSet, fmt, iter::FromIterator};
 
 use subsocial_support::{SpaceId, SpacePermissionsInfo, User};
 
@@ -10,7 +11,7 @@ use super::*;
 pub type SpacePermissionsInfoOf<T> =
     SpacePermissionsInfo<<T as frame_system::Config>::AccountId, SpacePermissions>;
 
-#[derive(Encode, Decode, Ord, PartialOrd, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Ord, PartialOrd, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum SpacePermission {
     /// Create, update, delete, grant and revoke roles in this space.
@@ -75,9 +76,206 @@ pub enum SpacePermission {
     // Related to space settings:
     /// Allows to update space settings across different pallets.
     UpdateSpaceSettings,
+
+    // Related to members-only spaces:
+    /// Publish encrypted content keys for members of this space.
+    ManageContentKeys,
+
+    // Related to space treasury:
+    /// Spend funds from the treasury account of this space.
+    SpendTreasury,
+
+    /// A permission declared by another pallet. See `NamespacedPermissionsRegistry`.
+    Namespaced(NamespacedPermission),
+}
+
+/// A namespace, under which a pallet declares its' own permissions, e.g. `*b"treasury"`.
+pub type PermissionNamespace = [u8; 8];
+
+/// A permission that is declared by a pallet outside of the core `SpacePermission` variants.
+/// Pallets identify such permissions by their namespace and a local index within it.
+#[derive(
+    Encode,
+    Decode,
+    Ord,
+    PartialOrd,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct NamespacedPermission {
+    pub namespace: PermissionNamespace,
+    pub index: u8,
+}
+
+impl NamespacedPermission {
+    pub const fn new(namespace: PermissionNamespace, index: u8) -> Self {
+        Self { namespace, index }
+    }
+}
+
+impl From<NamespacedPermission> for SpacePermission {
+    fn from(permission: NamespacedPermission) -> Self {
+        SpacePermission::Namespaced(permission)
+    }
+}
+
+/// A registry of namespaced permissions. Each pallet that declares its' own permissions
+/// should implement this trait and be added to a runtime's registry tuple.
+pub trait NamespacedPermissionsRegistry {
+    /// Check whether a given permission is declared by this registry.
+    fn is_permission_declared(permission: &NamespacedPermission) -> bool;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+impl NamespacedPermissionsRegistry for Tuple {
+    fn is_permission_declared(permission: &NamespacedPermission) -> bool {
+        for_tuples!( #(
+            if Tuple::is_permission_declared(permission) {
+                return true
+            }
+        )* );
+        false
+    }
 }
 
-pub type SpacePermissionSet = BTreeSet<SpacePermission>;
+// Every core permission must fit into the bitset of `SpacePermissionSet`.
+const _: () = assert!((SpacePermission::CORE_PERMISSIONS_COUNT as u32) < u64::BITS);
+
+/// A set of space permissions. Core permissions are stored as a fixed-width bitset,
+/// where every permission is represented by the bit at the index of its variant
+/// in `SpacePermission`. Namespaced permissions are stored as an ordered set.
+///
+/// Serde still (de)serializes it as a list of permission names, so RPC clients are not affected.
+#[derive(Encode, Decode, Default, Clone, Eq, PartialEq, TypeInfo)]
+pub struct SpacePermissionSet {
+    core: u64,
+    namespaced: BTreeSet<NamespacedPermission>,
+}
+
+impl SpacePermissionSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a set of core permissions from their bitset, e.g. one that was stored
+    /// before namespaced permissions were introduced.
+    pub(crate) fn from_core_bits(core: u64) -> Self {
+        Self { core, namespaced: BTreeSet::new() }
+    }
+
+    pub fn contains(&self, permission: &SpacePermission) -> bool {
+        match permission {
+            SpacePermission::Namespaced(permission) => self.namespaced.contains(permission),
+            core => self.core & core.bit() != 0,
+        }
+    }
+
+    /// Adds a permission to the set. Returns whether the permission was newly inserted.
+    pub fn insert(&mut self, permission: SpacePermission) -> bool {
+        match permission {
+            SpacePermission::Namespaced(permission) => self.namespaced.insert(permission),
+            core => {
+                let is_new = !self.contains(&core);
+                self.core |= core.bit();
+                is_new
+            },
+        }
+    }
+
+    /// Removes a permission from the set. Returns whether the permission was present.
+    pub fn remove(&mut self, permission: &SpacePermission) -> bool {
+        match permission {
+            SpacePermission::Namespaced(permission) => self.namespaced.remove(permission),
+            core => {
+                let was_present = self.contains(core);
+                self.core &= !core.bit();
+                was_present
+            },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.core == 0 && self.namespaced.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.core.count_ones() as usize + self.namespaced.len()
+    }
+
+    /// Permissions that are present either in `self` or in `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            core: self.core | other.core,
+            namespaced: self.namespaced.union(&other.namespaced).copied().collect(),
+        }
+    }
+
+    /// Permissions that are present in `self`, but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        Self {
+            core: self.core & !other.core,
+            namespaced: self.namespaced.difference(&other.namespaced).copied().collect(),
+        }
+    }
+
+    /// Iterates over the namespaced permissions only.
+    pub fn namespaced(&self) -> impl Iterator<Item = &NamespacedPermission> {
+        self.namespaced.iter()
+    }
+
+    /// Iterates over the core permissions in the order of `SpacePermission` variants,
+    /// followed by the namespaced permissions.
+    pub fn iter(&self) -> impl Iterator<Item = SpacePermission> + '_ {
+        let bits = self.core;
+        (0..SpacePermission::CORE_PERMISSIONS_COUNT)
+            .filter(move |index| bits & (1 << *index) != 0)
+            .filter_map(SpacePermission::from_index)
+            .chain(self.namespaced.iter().copied().map(SpacePermission::Namespaced))
+    }
+}
+
+impl FromIterator<SpacePermission> for SpacePermissionSet {
+    fn from_iter<I: IntoIterator<Item = SpacePermission>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<SpacePermission> for SpacePermissionSet {
+    fn extend<I: IntoIterator<Item = SpacePermission>>(&mut self, iter: I) {
+        for permission in iter {
+            self.insert(permission);
+        }
+    }
+}
+
+impl fmt::Debug for SpacePermissionSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Serialize for SpacePermissionSet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for SpacePermissionSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<SpacePermission>::deserialize(deserializer)
+            .map(|permissions| permissions.into_iter().collect())
+    }
+}
 
 /// These are a set of built-in roles which can be given different permissions within a given space.
 /// For example: everyone can comment (`CreateComments`), but only followers can post
@@ -111,14 +309,123 @@ pub struct SpacePermissionsContext {
     pub space_perms: Option<SpacePermissions>,
 }
 
+/// A source of permissions, that is checked while resolving a permission of a user in a space.
+/// `RoleId` is a type of role ids used by a pallet, that manages roles within spaces.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PermissionSource<RoleId> {
+    /// Permissions that nobody can have in a space. See `SpacePermissions::none`.
+    Nobody,
+    /// See `SpacePermissions::everyone`.
+    Everyone,
+    /// See `SpacePermissions::follower`.
+    Follower,
+    /// See `SpacePermissions::space_owner`.
+    SpaceOwner,
+    /// A role, by its id, that is granted to a user in a space.
+    Role(RoleId),
+    /// A privacy of a space: only the owner and members can interact with a members-only space.
+    SpacePrivacy,
+    /// A moderation of a space, that may block a user within it.
+    Moderation,
+}
+
+/// What a single source of permissions decided about a permission.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PermissionCheckOutcome {
+    /// The source includes the permission and applies to a user.
+    Granted,
+    /// The source forbids the permission to anyone, so no other sources are checked.
+    Forbidden,
+    /// The source does not include the permission.
+    NotIncluded,
+    /// The source includes the permission, but a user is not a follower or an owner of a space.
+    NotApplicable,
+    /// A role includes the permission, but it is disabled.
+    RoleDisabled,
+    /// A role includes the permission, but it has expired.
+    RoleExpired,
+    /// A role includes the permission, but its grant to a user has expired.
+    RoleGrantExpired,
+    /// A user passes a check, that is made before any source of permissions is checked.
+    Passed,
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PermissionCheckStep<RoleId> {
+    pub source: PermissionSource<RoleId>,
+    pub outcome: PermissionCheckOutcome,
+}
+
+/// An explanation of whether a user has a permission in a space: every source of permissions
+/// that was checked, in the order of checking, and what each of them decided.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PermissionTrace<RoleId> {
+    pub permission: SpacePermission,
+    pub granted: bool,
+    pub steps: Vec<PermissionCheckStep<RoleId>>,
+}
+
 impl SpacePermission {
-    pub(super) fn is_present_in_role(&self, perms_opt: Option<SpacePermissionSet>) -> bool {
-        if let Some(perms) = perms_opt {
-            if perms.contains(self) {
-                return true
-            }
+    /// The number of core permissions, i.e. the index of the `Namespaced` variant.
+    const CORE_PERMISSIONS_COUNT: u8 =
+        Self::Namespaced(NamespacedPermission::new([0; 8], 0)).index();
+
+    /// The index of a permission's variant, that is also the first byte of its' SCALE encoding.
+    const fn index(&self) -> u8 {
+        match self {
+            Self::ManageRoles => 0,
+            Self::RepresentSpaceInternally => 1,
+            Self::RepresentSpaceExternally => 2,
+            Self::UpdateSpace => 3,
+            Self::CreateSubspaces => 4,
+            Self::UpdateOwnSubspaces => 5,
+            Self::DeleteOwnSubspaces => 6,
+            Self::HideOwnSubspaces => 7,
+            Self::UpdateAnySubspace => 8,
+            Self::DeleteAnySubspace => 9,
+            Self::HideAnySubspace => 10,
+            Self::CreatePosts => 11,
+            Self::UpdateOwnPosts => 12,
+            Self::DeleteOwnPosts => 13,
+            Self::HideOwnPosts => 14,
+            Self::UpdateAnyPost => 15,
+            Self::DeleteAnyPost => 16,
+            Self::HideAnyPost => 17,
+            Self::CreateComments => 18,
+            Self::UpdateOwnComments => 19,
+            Self::DeleteOwnComments => 20,
+            Self::HideOwnComments => 21,
+            Self::HideAnyComment => 22,
+            Self::Upvote => 23,
+            Self::Downvote => 24,
+            Self::Share => 25,
+            Self::OverrideSubspacePermissions => 26,
+            Self::OverridePostPermissions => 27,
+            Self::SuggestEntityStatus => 28,
+            Self::UpdateEntityStatus => 29,
+            Self::UpdateSpaceSettings => 30,
+            Self::ManageContentKeys => 31,
+            Self::SpendTreasury => 32,
+            Self::Namespaced(_) => 33,
         }
-        false
+    }
+
+    /// A single bit that represents a core permission in a `SpacePermissionSet`.
+    fn bit(&self) -> u64 {
+        1 << self.index()
+    }
+
+    /// Finds a permission by the index of its variant.
+    fn from_index(index: u8) -> Option<Self> {
+        Self::decode(&mut &[index][..]).ok()
+    }
+
+    pub(super) fn is_present_in_role(&self, perms_opt: Option<SpacePermissionSet>) -> bool {
+        perms_opt.map_or(false, |perms| perms.contains(self))
     }
 }
 
@@ -3,18 +3,29 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use frame_benchmarking::{account, benchmarks};
-use frame_support::{dispatch::DispatchError, ensure};
+use frame_support::{
+    dispatch::DispatchError, ensure, sp_runtime::traits::Bounded, traits::Currency,
+};
 use frame_system::RawOrigin;
 
 use pallet_posts::{types::Post, PostExtension, PostById, NextPostId};
-use pallet_spaces::types::Space;
+use pallet_spaces::{types::Space, BalanceOf};
 use subsocial_support::{Content, SpaceId};
 
 use super::*;
 
+fn fund_account<T: Config>(origin: &RawOrigin<T::AccountId>) {
+    if let RawOrigin::Signed(account) = origin {
+        let balance = BalanceOf::<T>::max_value() / 2u32.into();
+        <T as pallet_spaces::Config>::Currency::make_free_balance_be(account, balance);
+    }
+}
+
 fn create_dummy_space<T: Config>(
     origin: RawOrigin<T::AccountId>,
 ) -> Result<Space<T>, DispatchError> {
+    fund_account::<T>(&origin);
+
     let space_id = pallet_spaces::NextSpaceId::<T>::get();
 
     pallet_spaces::Pallet::<T>::create_space(origin.into(), Content::None, None)?;
@@ -1,7 +1,11 @@
 use frame_support::dispatch::DispatchResult;
-use sp_runtime::traits::Saturating;
+use sp_runtime::traits::{Saturating, Zero};
 
-use subsocial_support::{remove_from_vec, SpaceId};
+use subsocial_support::{
+    remove_from_vec,
+    traits::{SpacePostsProvider, SpaceSettingsHandler, SpaceSettingsProvider},
+    SpaceId,
+};
 
 use super::*;
 
@@ -227,6 +231,7 @@ impl<T: Config> Pallet<T> {
         new_post_id: PostId,
         comment_ext: Comment,
         root_post_id: PostId,
+        space_id: SpaceId,
     ) -> DispatchResult {
         let mut commented_post_id = root_post_id;
 
@@ -236,9 +241,12 @@ impl<T: Config> Pallet<T> {
 
             ensure!(parent_comment.is_comment(), Error::<T>::NotACommentByParentId);
 
+            let max_comment_depth =
+                Spaces::<T>::space_setting::<MaxCommentDepthSetting<T>>(space_id);
+
             let ancestors = Self::get_post_ancestors(parent_id);
             ensure!(
-                ancestors.len() < T::MaxCommentDepth::get() as usize,
+                ancestors.len() < max_comment_depth as usize,
                 Error::<T>::MaxCommentDepthReached
             );
 
@@ -279,6 +287,7 @@ impl<T: Config> Pallet<T> {
     ) -> DispatchResult {
         let old_space_id_opt = post.try_get_space_id();
         let new_space = Spaces::<T>::require_space(new_space_id)?;
+        Spaces::<T>::ensure_space_not_archived(new_space_id)?;
 
         ensure!(
             T::IsAccountBlocked::is_allowed_account(editor.clone(), new_space_id),
@@ -305,9 +314,11 @@ impl<T: Config> Pallet<T> {
                     PostIdsBySpaceId::<T>::mutate(old_space_id, |post_ids| {
                         remove_from_vec(post_ids, post.id)
                     });
+                    <T as Config>::SpaceStats::on_post_removed(old_space_id, post.hidden);
                 }
 
                 PostIdsBySpaceId::<T>::mutate(new_space_id, |post_ids| post_ids.push(post.id));
+                <T as Config>::SpaceStats::on_post_added(new_space_id, post.hidden);
 
                 post.space_id = Some(new_space_id);
                 PostById::<T>::insert(post.id, post);
@@ -318,6 +329,32 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Recalculate the storage deposit of a given post according to its' encoded size,
+    /// and reserve or unreserve the difference from the post owner.
+    pub(crate) fn update_post_deposit(post: &Post<T>) -> DispatchResult {
+        if post.id < Self::first_post_id_with_deposit() {
+            return Ok(())
+        }
+
+        let base_deposit = if post.is_comment() {
+            T::BaseCommentDeposit::get()
+        } else {
+            T::BasePostDeposit::get()
+        };
+
+        let old_deposit = Self::post_deposit(post.id);
+        let new_deposit = Spaces::<T>::storage_deposit(base_deposit, post.encoded_size());
+
+        Spaces::<T>::update_reserved_deposit(&post.owner, old_deposit, new_deposit)?;
+
+        if new_deposit.is_zero() {
+            PostDeposits::<T>::remove(post.id);
+        } else {
+            PostDeposits::<T>::insert(post.id, new_deposit);
+        }
+        Ok(())
+    }
+
     pub fn delete_post_from_space(post_id: PostId) -> DispatchResult {
         let mut post = Self::require_post(post_id)?;
 
@@ -328,6 +365,7 @@ impl<T: Config> Pallet<T> {
 
             post.space_id = None;
             PostIdsBySpaceId::<T>::mutate(space_id, |post_ids| remove_from_vec(post_ids, post_id));
+            <T as Config>::SpaceStats::on_post_removed(space_id, post.hidden);
         }
 
         PostById::insert(post.id, post);
@@ -335,3 +373,27 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 }
+
+impl<T: Config> SpacePostsProvider for Pallet<T> {
+    fn space_posts_count(space_id: SpaceId) -> u32 {
+        Self::post_ids_by_space_id(space_id).len() as u32
+    }
+
+    fn space_hidden_posts_count(space_id: SpaceId) -> u32 {
+        Self::post_ids_by_space_id(space_id)
+            .into_iter()
+            .filter_map(Self::post_by_id)
+            .filter(|post| post.hidden)
+            .count() as u32
+    }
+}
+
+impl<T: Config> SpaceSettingsHandler for Pallet<T> {
+    fn validate_space_setting(key: &SpaceSettingKey, raw_value: &[u8]) -> Option<DispatchResult> {
+        if *key == MaxCommentDepthSetting::<T>::KEY {
+            return Some(MaxCommentDepthSetting::<T>::decode_and_validate(raw_value))
+        }
+
+        None
+    }
+}
@@ -17,25 +17,26 @@ use codec::{Decode, Encode};
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
     ensure, fail,
-    traits::Get,
+    traits::{Get, ReservableCurrency},
 };
 use frame_system::ensure_signed;
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::RuntimeDebug;
-use sp_std::prelude::*;
+use sp_std::{marker::PhantomData, prelude::*};
 
 use pallet_permissions::SpacePermission;
-use pallet_spaces::{types::Space, Pallet as Spaces};
+use pallet_spaces::{types::Space, BalanceOf, Pallet as Spaces};
 use subsocial_support::{
-    ensure_content_is_valid, new_who_and_when, remove_from_vec,
-    traits::{IsAccountBlocked, IsContentBlocked, IsPostBlocked},
-    Content, ModerationError, PostId, SpaceId, WhoAndWhen, WhoAndWhenOf,
+    ensure_content_is_valid, new_who_and_when, remove_from_vec, space_owner_walk_weight,
+    traits::{IsAccountBlocked, IsContentBlocked, IsPostBlocked, SpaceSetting, SpaceStatsManager},
+    Content, ModerationError, PostId, SpaceId, SpaceSettingKey, WhoAndWhen, WhoAndWhenOf,
 };
 
 pub use pallet::*;
 pub mod functions;
+pub mod migration;
 
 pub mod types;
 pub use types::*;
@@ -46,6 +47,9 @@ pub mod weights;
 
 // pub mod rpc;
 
+/// The log target of this pallet.
+pub const LOG_TARGET: &str = "runtime::posts";
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -70,6 +74,18 @@ pub mod pallet {
 
         type IsPostBlocked: IsPostBlocked<PostId>;
 
+        /// A handler that keeps the posts counters of a space up to date.
+        type SpaceStats: SpaceStatsManager;
+
+        /// The amount held on deposit for storing a regular or shared post.
+        /// Post and comment deposits are effectively permanent, see `PostDeposits`.
+        #[pallet::constant]
+        type BasePostDeposit: Get<BalanceOf<Self>>;
+
+        /// The amount held on deposit for storing a comment.
+        #[pallet::constant]
+        type BaseCommentDeposit: Get<BalanceOf<Self>>;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -111,6 +127,22 @@ pub mod pallet {
     pub type SharedPostIdsByOriginalPostId<T: Config> =
         StorageMap<_, Twox64Concat, PostId, Vec<PostId>, ValueQuery>;
 
+    /// Get the storage deposit reserved from the owner of a post by the post id.
+    ///
+    /// Posts and comments cannot be deleted by their owners, so this deposit stays reserved
+    /// for as long as a post exists. It is only returned, when a post is removed by root
+    /// via `force_remove_post`.
+    #[pallet::storage]
+    #[pallet::getter(fn post_deposit)]
+    pub type PostDeposits<T: Config> =
+        StorageMap<_, Twox64Concat, PostId, BalanceOf<T>, ValueQuery>;
+
+    /// The first post id that storage deposits apply to. Posts with lower ids were created
+    /// before storage deposits were introduced, so they are exempt from them.
+    #[pallet::storage]
+    #[pallet::getter(fn first_post_id_with_deposit)]
+    pub type FirstPostIdWithDeposit<T: Config> = StorageValue<_, PostId, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -165,6 +197,8 @@ pub mod pallet {
         CannotUpdateSpaceIdOnComment,
         /// Max comment depth reached.
         MaxCommentDepthReached,
+        /// Max comment depth of a space cannot be greater than `MaxCommentDepth` of the runtime.
+        MaxCommentDepthSettingIsTooBig,
         /// Only comment owner can update this comment.
         NotACommentAuthor,
         /// This post's extension is not a `Comment`.
@@ -198,6 +232,7 @@ pub mod pallet {
                 PostExtension::Comment(..) => <T as Config>::WeightInfo::create_post__comment(),
                 PostExtension::SharedPost(..) => <T as Config>::WeightInfo::create_post__shared(),
             }
+            .saturating_add(space_owner_walk_weight::<T>())
         )]
         pub fn create_post(
             origin: OriginFor<T>,
@@ -216,6 +251,7 @@ pub mod pallet {
             // Get space from either space_id_opt or Comment if a comment provided
             let space = &new_post.get_space()?;
             ensure!(!space.hidden, Error::<T>::CannotCreateInHiddenScope);
+            Spaces::<T>::ensure_space_not_archived(space.id)?;
 
             ensure!(
                 T::IsAccountBlocked::is_allowed_account(creator.clone(), space.id),
@@ -249,12 +285,15 @@ pub mod pallet {
                 PostExtension::SharedPost(original_post_id) =>
                     Self::create_shared_post(&creator, new_post_id, original_post_id)?,
                 PostExtension::Comment(comment_ext) =>
-                    Self::create_comment(new_post_id, comment_ext, root_post.id)?,
+                    Self::create_comment(new_post_id, comment_ext, root_post.id, space.id)?,
                 _ => (),
             }
 
+            Self::update_post_deposit(&new_post)?;
+
             if new_post.is_root_post() {
                 PostIdsBySpaceId::<T>::mutate(space.id, |ids| ids.push(new_post_id));
+                <T as Config>::SpaceStats::on_post_added(space.id, new_post.hidden);
             }
 
             PostById::insert(new_post_id, new_post);
@@ -267,7 +306,10 @@ pub mod pallet {
         }
 
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config>::WeightInfo::update_post())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::update_post()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn update_post(
             origin: OriginFor<T>,
             post_id: PostId,
@@ -313,11 +355,22 @@ pub mod pallet {
                 if hidden != post.hidden {
                     post.hidden = hidden;
                     is_update_applied = true;
+
+                    if post.is_root_post() {
+                        if let Some(space_id) = post.space_id {
+                            <T as Config>::SpaceStats::on_post_visibility_changed(space_id, hidden);
+                        }
+                    }
                 }
             }
 
             // Update this post only if at least one field should be updated:
             if is_update_applied {
+                // Editors with `UpdateAnyPost` permission must not change the balance of the owner,
+                // so the deposit is only recalculated, when the owner edits a post.
+                if editor == post.owner {
+                    Self::update_post_deposit(&post)?;
+                }
                 <PostById<T>>::insert(post.id, post);
                 Self::deposit_event(Event::PostUpdated { account: editor, post_id });
             }
@@ -325,7 +378,10 @@ pub mod pallet {
         }
 
         #[pallet::call_index(2)]
-        #[pallet::weight(<T as Config>::WeightInfo::move_post())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::move_post()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn move_post(
             origin: OriginFor<T>,
             post_id: PostId,
@@ -402,6 +458,7 @@ pub mod pallet {
             if new_post.is_root_post() {
                 if let Some(space_id) = new_post.space_id {
                     PostIdsBySpaceId::<T>::mutate(space_id, |ids| ids.push(post_id));
+                    <T as Config>::SpaceStats::on_post_added(space_id, new_post.hidden);
                 }
             }
 
@@ -444,6 +501,7 @@ pub mod pallet {
                         PostIdsBySpaceId::<T>::mutate(space_id, |ids| {
                             remove_from_vec(ids, post_id)
                         });
+                        <T as Config>::SpaceStats::on_post_removed(space_id, old_post.hidden);
                     }
                 }
 
@@ -462,6 +520,10 @@ pub mod pallet {
                     _ => (),
                 }
                 PostById::<T>::remove(post_id);
+                <T as pallet_spaces::Config>::Currency::unreserve(
+                    &old_post.owner,
+                    PostDeposits::<T>::take(post_id),
+                );
             }
 
             Ok(Pays::No.into())
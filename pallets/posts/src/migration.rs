@@ -0,0 +1,49 @@
+use frame_support::{log, traits::OnRuntimeUpgrade};
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+use super::*;
+
+pub mod deposits {
+    use frame_support::{pallet_prelude::*, weights::Weight};
+
+    use super::*;
+
+    /// Exempts posts and comments, that were created before storage deposits were introduced,
+    /// from these deposits, so that their owners are not charged the full deposit
+    /// on the first update after the upgrade.
+    ///
+    /// It does not depend on the storage version, and does nothing if it was applied before.
+    pub struct ExemptLegacyPosts<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for ExemptLegacyPosts<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if FirstPostIdWithDeposit::<T>::exists() {
+                log::info!(
+                    target: LOG_TARGET,
+                    "Migration did not execute. This probably should be removed"
+                );
+                return T::DbWeight::get().reads(1)
+            }
+
+            let first_post_id = Pallet::<T>::next_post_id();
+            FirstPostIdWithDeposit::<T>::put(first_post_id);
+
+            log::info!(
+                target: LOG_TARGET,
+                "Exempted posts with ids below {} from storage deposits",
+                first_post_id
+            );
+            T::DbWeight::get().reads_writes(2, 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            ensure!(
+                FirstPostIdWithDeposit::<T>::exists(),
+                "the first post id with deposit should be set"
+            );
+            Ok(())
+        }
+    }
+}
@@ -69,3 +69,22 @@ impl Default for PostExtension {
         PostExtension::RegularPost
     }
 }
+
+/// A space setting that limits the depth of comments in a given space.
+/// It cannot be greater than the `MaxCommentDepth` of the runtime, which is its' default value.
+pub struct MaxCommentDepthSetting<T>(PhantomData<T>);
+
+impl<T: Config> SpaceSetting for MaxCommentDepthSetting<T> {
+    const KEY: SpaceSettingKey = *b"posts/maxCmtDpth";
+
+    type Value = u32;
+
+    fn default_value() -> Self::Value {
+        T::MaxCommentDepth::get()
+    }
+
+    fn validate(value: &Self::Value) -> DispatchResult {
+        ensure!(*value <= T::MaxCommentDepth::get(), Error::<T>::MaxCommentDepthSettingIsTooBig);
+        Ok(())
+    }
+}
@@ -4,6 +4,9 @@
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2023-02-14, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! Entries marked as "Not benchmarked yet" were adjusted by hand after this run,
+//! and must be regenerated with the command below.
 
 // Executed Command:
     // ./scripts/../target/release/subsocial-collator
@@ -52,28 +55,28 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
         impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             // Storage: Posts NextPostId (r:1 w:1)
             // Storage: Timestamp Now (r:1 w:0)
-            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:1)
             // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
             // Storage: Posts PostIdsBySpaceId (r:1 w:1)
             // Storage: Posts PostById (r:0 w:1)
         fn create_post__regular() -> Weight {
-        // Minimum execution time: 30_000 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(47_502_000)
             .saturating_add(T::DbWeight::get().reads(5))
-            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().writes(4))
         }
             // Storage: Posts NextPostId (r:1 w:1)
             // Storage: Timestamp Now (r:1 w:0)
-            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:1)
             // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
             // Storage: Posts PostById (r:1 w:1)
             // Storage: Posts SharedPostIdsByOriginalPostId (r:1 w:1)
             // Storage: Posts PostIdsBySpaceId (r:1 w:1)
         fn create_post__shared() -> Weight {
-        // Minimum execution time: 39_000 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(62_353_000)
             .saturating_add(T::DbWeight::get().reads(7))
-            .saturating_add(T::DbWeight::get().writes(4))
+            .saturating_add(T::DbWeight::get().writes(5))
         }
             // Storage: Posts NextPostId (r:1 w:1)
             // Storage: Timestamp Now (r:1 w:0)
@@ -88,23 +91,23 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
             .saturating_add(T::DbWeight::get().writes(3))
         }
             // Storage: Posts PostById (r:2 w:1)
-            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:1)
             // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
         fn update_post() -> Weight {
-        // Minimum execution time: 30_000 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(48_472_000)
             .saturating_add(T::DbWeight::get().reads(4))
-            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes(2))
         }
             // Storage: Posts PostById (r:1 w:1)
-            // Storage: Spaces SpaceById (r:2 w:0)
+            // Storage: Spaces SpaceById (r:2 w:2)
             // Storage: SpaceFollows SpaceFollowedByAccount (r:2 w:0)
             // Storage: Posts PostIdsBySpaceId (r:2 w:2)
         fn move_post() -> Weight {
-        // Minimum execution time: 39_000 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(60_733_000)
             .saturating_add(T::DbWeight::get().reads(7))
-            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().writes(5))
         }
     }
 
@@ -112,28 +115,28 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
     impl WeightInfo for () {
             // Storage: Posts NextPostId (r:1 w:1)
             // Storage: Timestamp Now (r:1 w:0)
-            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:1)
             // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
             // Storage: Posts PostIdsBySpaceId (r:1 w:1)
             // Storage: Posts PostById (r:0 w:1)
         fn create_post__regular() -> Weight {
-        // Minimum execution time: 30_000 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(47_502_000)
             .saturating_add(RocksDbWeight::get().reads(5))
-            .saturating_add(RocksDbWeight::get().writes(3))
+            .saturating_add(RocksDbWeight::get().writes(4))
         }
             // Storage: Posts NextPostId (r:1 w:1)
             // Storage: Timestamp Now (r:1 w:0)
-            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:1)
             // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
             // Storage: Posts PostById (r:1 w:1)
             // Storage: Posts SharedPostIdsByOriginalPostId (r:1 w:1)
             // Storage: Posts PostIdsBySpaceId (r:1 w:1)
         fn create_post__shared() -> Weight {
-        // Minimum execution time: 39_000 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(62_353_000)
             .saturating_add(RocksDbWeight::get().reads(7))
-            .saturating_add(RocksDbWeight::get().writes(4))
+            .saturating_add(RocksDbWeight::get().writes(5))
         }
             // Storage: Posts NextPostId (r:1 w:1)
             // Storage: Timestamp Now (r:1 w:0)
@@ -148,22 +151,22 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
             .saturating_add(RocksDbWeight::get().writes(3))
         }
             // Storage: Posts PostById (r:2 w:1)
-            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:1)
             // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
         fn update_post() -> Weight {
-        // Minimum execution time: 30_000 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(48_472_000)
             .saturating_add(RocksDbWeight::get().reads(4))
-            .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().writes(2))
         }
             // Storage: Posts PostById (r:1 w:1)
-            // Storage: Spaces SpaceById (r:2 w:0)
+            // Storage: Spaces SpaceById (r:2 w:2)
             // Storage: SpaceFollows SpaceFollowedByAccount (r:2 w:0)
             // Storage: Posts PostIdsBySpaceId (r:2 w:2)
         fn move_post() -> Weight {
-        // Minimum execution time: 39_000 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(60_733_000)
             .saturating_add(RocksDbWeight::get().reads(7))
-            .saturating_add(RocksDbWeight::get().writes(3))
+            .saturating_add(RocksDbWeight::get().writes(5))
         }
     }
@@ -122,6 +122,31 @@ fn create_comment_should_fail_when_max_comment_depth_reached() {
     });
 }
 
+#[test]
+fn create_comment_should_fail_when_max_comment_depth_of_space_reached() {
+    ExtBuilder::build_with_post().execute_with(|| {
+        assert_ok!(_set_max_comment_depth(2));
+
+        assert_ok!(_create_comment(None, None, Some(None), None)); // PostId 2
+        assert_ok!(_create_comment(None, None, Some(Some(2)), None)); // PostId 3
+
+        assert_noop!(
+            _create_comment(None, None, Some(Some(3)), None),
+            PostsError::<Test>::MaxCommentDepthReached
+        );
+    });
+}
+
+#[test]
+fn set_max_comment_depth_should_fail_when_greater_than_runtime_max() {
+    ExtBuilder::build_with_post().execute_with(|| {
+        assert_noop!(
+            _set_max_comment_depth(MaxCommentDepth::get() + 1),
+            PostsError::<Test>::MaxCommentDepthSettingIsTooBig
+        );
+    });
+}
+
 #[test]
 fn update_comment_should_work() {
     ExtBuilder::build_with_comment().execute_with(|| {
@@ -207,3 +232,16 @@ fn update_comment_should_fail_when_ipfs_cid_is_invalid() {
         );
     });
 }
+
+#[test]
+fn create_comment_should_reserve_comment_deposit() {
+    ExtBuilder::build_with_post().execute_with(|| {
+        BasePostDeposit::set(10);
+        BaseCommentDeposit::set(5);
+
+        assert_ok!(_create_default_comment()); // PostId 2 by ACCOUNT1
+
+        assert_eq!(Posts::post_deposit(POST2), 5);
+        assert_eq!(Balances::reserved_balance(ACCOUNT1), 5);
+    });
+}
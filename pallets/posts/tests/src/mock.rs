@@ -1,7 +1,7 @@
-use frame_support::{pallet_prelude::ConstU32, parameter_types, traits::Everything};
+use frame_support::{pallet_prelude::{ConstU32, ConstU64}, parameter_types, traits::Everything};
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
 };
 use sp_std::convert::{TryFrom, TryInto};
@@ -97,15 +97,24 @@ parameter_types! {
     pub const MaxCommentDepth: u32 = 10;
 }
 
+parameter_types! {
+    pub static BasePostDeposit: Balance = 0;
+    pub static BaseCommentDeposit: Balance = 0;
+}
+
 impl pallet_posts::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type SpaceStats = Spaces;
     type MaxCommentDepth = MaxCommentDepth;
     type IsPostBlocked = MockModeration;
+    type BasePostDeposit = BasePostDeposit;
+    type BaseCommentDeposit = BaseCommentDeposit;
     type WeightInfo = ();
 }
 
 impl pallet_permissions::Config for Test {
     type DefaultSpacePermissions = pallet_permissions::default_permissions::DefaultSpacePermissions;
+    type NamespacedPermissions = ();
 }
 
 parameter_types! {
@@ -117,8 +126,17 @@ impl pallet_roles::Config for Test {
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
     type SpacePermissionsProvider = Spaces;
     type SpaceFollows = SpaceFollows;
+    type Currency = Balances;
+    type JoinRequestDeposit = ConstU64<0>;
+    type DomainsProvider = ();
     type IsAccountBlocked = MockModeration;
     type IsContentBlocked = MockModeration;
+    type SpaceSettings = Spaces;
+    type OnRoleRemoved = ();
+    type RoleProposalLifetime = ConstU64<100>;
+    type RoleProposalDeposit = ConstU64<0>;
+    type InviteSigner = UintAuthorityId;
+    type InviteSignature = TestSignature;
     type WeightInfo = ();
 }
 
@@ -126,6 +144,7 @@ impl pallet_profiles::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type SpacePermissionsProvider = Spaces;
     type SpacesInterface = Spaces;
+    type MaxLinkedAddresses = ConstU32<10>;
     type WeightInfo = ();
 }
 
@@ -135,17 +154,31 @@ impl pallet_spaces::Config for Test {
     type SpaceFollows = SpaceFollows;
     type IsAccountBlocked = MockModeration;
     type IsContentBlocked = MockModeration;
+    type SpacePosts = Posts;
+    type ProfileManager = Profiles;
+    type OnSpaceDeleted = (SpaceFollows, Roles, SpaceOwnership);
+    type OnSpaceOwnerChanged = SpaceOwnership;
+    type SpaceSettingsHandler = Posts;
+    type SpaceOwnerOrigin = frame_system::EnsureSigned<AccountId>;
+    type SpaceAccounts = ();
+    type Currency = Balances;
+    type BaseSpaceDeposit = ConstU64<0>;
+    type StorageByteDeposit = ConstU64<0>;
     type MaxSpacesPerAccount = ConstU32<100>;
+    type MinHandleLen = ConstU32<5>;
+    type MaxHandleLen = ConstU32<50>;
     type WeightInfo = ();
 }
 
 impl pallet_space_follows::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type SpaceStats = Spaces;
     type WeightInfo = ();
 }
 
 impl pallet_space_ownership::Config for Test {
     type RuntimeEvent = RuntimeEvent;
-    type ProfileManager = Profiles;
+    type MaxExpiredTransfersPerBlock = ConstU32<10>;
+    type MaxOwnershipHistoryLength = ConstU32<10>;
     type WeightInfo = ();
 }
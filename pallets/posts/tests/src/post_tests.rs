@@ -1,8 +1,8 @@
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnRuntimeUpgrade};
 use sp_runtime::DispatchError;
 
 use pallet_permissions::SpacePermission as SP;
-use pallet_posts::{Error as PostsError, Post};
+use pallet_posts::{migration::deposits::ExemptLegacyPosts, Error as PostsError, Post};
 use pallet_spaces::Error as SpacesError;
 use subsocial_support::{mock_functions::*, ContentError, ModerationError, PostId, SpaceId};
 
@@ -473,6 +473,99 @@ fn update_post_should_fail_when_no_right_permission_in_account_roles() {
     );
 }
 
+#[test]
+fn create_post_should_reserve_deposit() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        BasePostDeposit::set(10);
+
+        assert_ok!(_create_default_post());
+
+        assert_eq!(Posts::post_deposit(POST1), 10);
+        assert_eq!(Balances::reserved_balance(ACCOUNT1), 10);
+    });
+}
+
+#[test]
+fn create_post_should_fail_when_deposit_cannot_be_reserved() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        BasePostDeposit::set(1000);
+
+        assert_noop!(
+            _create_default_post(),
+            SpacesError::<Test>::InsufficientBalanceToReserveDeposit
+        );
+    });
+}
+
+#[test]
+fn update_post_should_not_change_deposit_of_owner_when_updated_by_another_account() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateAnyPost]).execute_with(
+        || {
+            assert_ok!(_create_default_post()); // PostId 1
+            BasePostDeposit::set(10);
+            let reserved_by_owner = Balances::reserved_balance(ACCOUNT1);
+
+            assert_ok!(_update_post(
+                Some(RuntimeOrigin::signed(ACCOUNT2)),
+                Some(POST1),
+                Some(post_update(None, Some(updated_post_content()), None))
+            ));
+
+            assert_eq!(Posts::post_deposit(POST1), 0);
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), reserved_by_owner);
+        },
+    );
+}
+
+#[test]
+fn force_remove_post_should_unreserve_deposit() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        BasePostDeposit::set(10);
+        assert_ok!(_create_default_post());
+
+        assert_ok!(Posts::force_remove_post(RuntimeOrigin::root(), POST1));
+
+        assert_eq!(Posts::post_deposit(POST1), 0);
+        assert_eq!(Balances::reserved_balance(ACCOUNT1), 0);
+    });
+}
+
+#[test]
+fn update_post_should_not_reserve_deposit_of_legacy_post() {
+    ExtBuilder::build_with_post().execute_with(|| {
+        BasePostDeposit::set(10);
+
+        ExemptLegacyPosts::<Test>::on_runtime_upgrade();
+        assert_eq!(Posts::first_post_id_with_deposit(), POST2);
+
+        assert_ok!(_update_post(
+            None,
+            None,
+            Some(post_update(None, Some(another_valid_content_ipfs()), None))
+        ));
+
+        assert_eq!(Posts::post_deposit(POST1), 0);
+        assert_eq!(Balances::reserved_balance(ACCOUNT1), 0);
+    });
+}
+
+#[test]
+fn move_post_should_update_posts_counters_of_both_spaces() {
+    ExtBuilder::build_with_post_and_two_spaces().execute_with(|| {
+        assert_ok!(_update_post(None, None, Some(post_update(None, None, Some(true)))));
+
+        assert_ok!(_move_post_1_to_space_2());
+
+        let old_space = Spaces::space_by_id(SPACE1).unwrap();
+        assert_eq!(old_space.posts_count, 0);
+        assert_eq!(old_space.hidden_posts_count, 0);
+
+        let new_space = Spaces::space_by_id(SPACE2).unwrap();
+        assert_eq!(new_space.posts_count, 1);
+        assert_eq!(new_space.hidden_posts_count, 1);
+    });
+}
+
 // TODO: refactor or remove. Deprecated tests
 // Find public post ids tests
 // --------------------------------------------------------------------------------------------
@@ -9,11 +9,11 @@ use sp_core::storage::Storage;
 use sp_io::TestExternalities;
 
 use pallet_permissions::{SpacePermission as SP, SpacePermission, SpacePermissions};
-use pallet_posts::{Comment, PostExtension, PostUpdate};
+use pallet_posts::{Comment, MaxCommentDepthSetting, PostExtension, PostUpdate};
 use pallet_spaces::types::SpaceUpdate;
 use subsocial_support::{
     mock_functions::*,
-    traits::{IsAccountBlocked, IsContentBlocked, IsPostBlocked, IsSpaceBlocked},
+    traits::{IsAccountBlocked, IsContentBlocked, IsPostBlocked, IsSpaceBlocked, SpaceSetting},
     Content, PostId, SpaceId, User,
 };
 
@@ -382,6 +382,14 @@ pub(crate) fn _move_post(
     )
 }
 
+pub(crate) fn _set_max_comment_depth(depth: u32) -> DispatchResult {
+    Spaces::update_space_settings(
+        RuntimeOrigin::signed(ACCOUNT1),
+        SPACE1,
+        vec![(MaxCommentDepthSetting::<Test>::KEY, Some(depth.encode()))],
+    )
+}
+
 pub(crate) fn _create_default_comment() -> DispatchResult {
     _create_comment(None, None, None, None)
 }
@@ -436,6 +444,7 @@ pub fn _create_role(
         time_to_live.unwrap_or_default(), // Should return 'None'
         content.unwrap_or_else(default_role_content_ipfs),
         permissions.unwrap_or_else(permission_set_default),
+        0,
     )
 }
 
@@ -448,6 +457,7 @@ pub fn _grant_role(
         origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
         role_id.unwrap_or(ROLE1),
         users.unwrap_or_else(|| vec![User::Account(ACCOUNT2)]),
+        None,
     )
 }
 
@@ -484,5 +494,6 @@ pub(crate) fn _transfer_space_ownership(
         origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
         space_id.unwrap_or(SPACE1),
         transfer_to.unwrap_or(ACCOUNT2),
+        None,
     )
 }
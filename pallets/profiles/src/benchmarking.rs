@@ -4,16 +4,31 @@ use crate::Pallet as Profiles;
 use frame_benchmarking::{benchmarks, whitelisted_caller};
 use frame_support::dispatch::DispatchError;
 use frame_system::RawOrigin;
+use sp_core::{crypto::KeyTypeId, ecdsa, H160};
 use subsocial_support::{traits::SpacesInterface, Content, SpaceId};
 
+const EVM_KEY_TYPE: KeyTypeId = KeyTypeId(*b"prof");
+
 fn create_space<T: Config>(
     owner: &T::AccountId,
     content: Content,
 ) -> Result<SpaceId, DispatchError> {
+    T::BenchmarkHelper::fund_account(owner);
     let space_id = T::SpacesInterface::create_space(owner, content)?;
     Ok(space_id)
 }
 
+fn sign_evm_link_message<T: Config>(account: &T::AccountId) -> (H160, ecdsa::Signature) {
+    let public = sp_io::crypto::ecdsa_generate(EVM_KEY_TYPE, None);
+    let nonce = Profiles::<T>::address_link_nonce(account);
+    let message_hash = eip191_hash(&Profiles::<T>::evm_link_message_for(account, nonce));
+    let signature = sp_io::crypto::ecdsa_sign_prehashed(EVM_KEY_TYPE, &public, &message_hash)
+        .expect("a key was just generated; qed");
+    let evm_address = Profiles::<T>::recover_evm_address(&signature, &message_hash)
+        .expect("a signature was just made; qed");
+    (evm_address, signature)
+}
+
 benchmarks! {
     set_profile {
         let caller: T::AccountId = whitelisted_caller();
@@ -34,11 +49,35 @@ benchmarks! {
 
     create_space_as_profile {
         let caller: T::AccountId = whitelisted_caller();
+        T::BenchmarkHelper::fund_account(&caller);
         let content = Content::default();
     }: _(RawOrigin::Signed(caller.clone()), content)
     verify {
         assert!(Profiles::<T>::profile_space_id_by_account(&caller).is_some());
     }
 
+    link_evm_address {
+        let caller: T::AccountId = whitelisted_caller();
+        let (evm_address, signature) = sign_evm_link_message::<T>(&caller);
+    }: _(RawOrigin::Signed(caller.clone()), evm_address, signature)
+    verify {
+        let address = ExternalAddress::Evm(evm_address);
+        assert_eq!(Profiles::<T>::account_by_linked_address(address), Some(caller));
+    }
+
+    unlink_address {
+        let caller: T::AccountId = whitelisted_caller();
+        let (evm_address, signature) = sign_evm_link_message::<T>(&caller);
+        Profiles::<T>::link_evm_address(
+            RawOrigin::Signed(caller.clone()).into(),
+            evm_address,
+            signature,
+        )?;
+        let address = ExternalAddress::Evm(evm_address);
+    }: _(RawOrigin::Signed(caller.clone()), address)
+    verify {
+        assert!(Profiles::<T>::account_by_linked_address(address).is_none());
+    }
+
     // impl_benchmark_test_suite!(Profiles, crate::mock::ExtBuilder::build(), crate::mock::Test);
 }
@@ -11,15 +11,20 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod types;
 pub mod weights;
 
 pub use crate::weights::WeightInfo;
+pub use types::*;
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
+    use sp_core::{ecdsa, H160};
+    use sp_runtime::traits::Zero;
+    use sp_std::vec::Vec;
 
     use pallet_permissions::SpacePermissions;
     use subsocial_support::{
@@ -42,6 +47,14 @@ pub mod pallet {
 
         type SpacesInterface: SpacesInterface<Self::AccountId, SpaceId>;
 
+        /// The maximum number of external addresses, that can be linked to a single account.
+        #[pallet::constant]
+        type MaxLinkedAddresses: Get<u32>;
+
+        /// A helper to fund accounts in benchmarks, so that they can create spaces.
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<Self::AccountId>;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -54,17 +67,53 @@ pub mod pallet {
     pub type ProfileSpaceIdByAccount<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, SpaceId>;
 
+    /// External addresses, that are linked to a given account.
+    #[pallet::storage]
+    #[pallet::getter(fn linked_addresses_by_account)]
+    pub type LinkedAddressesByAccount<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<ExternalAddress, T::MaxLinkedAddresses>,
+        ValueQuery,
+    >;
+
+    /// An account, that a given external address is linked to.
+    #[pallet::storage]
+    #[pallet::getter(fn account_by_linked_address)]
+    pub type AccountByLinkedAddress<T: Config> =
+        StorageMap<_, Blake2_128Concat, ExternalAddress, T::AccountId>;
+
+    /// A nonce, that must be included in the next message signed to link an external address
+    /// to a given account. It is increased on every link.
+    #[pallet::storage]
+    #[pallet::getter(fn address_link_nonce)]
+    pub type AddressLinkNonce<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
     pub enum Event<T: Config> {
         /// Profile's space id was updated for this account.
         ProfileUpdated { account: T::AccountId, space_id: Option<SpaceId> },
+        /// An external address was linked to this account.
+        AddressLinked { account: T::AccountId, address: ExternalAddress },
+        /// An external address was unlinked from this account.
+        AddressUnlinked { account: T::AccountId, address: ExternalAddress },
     }
 
     #[pallet::error]
     pub enum Error<T> {
         /// There is no space set as profile.
         NoSpaceSetAsProfile,
+        /// A signature was not made by a given EVM address over the expected link message.
+        InvalidEvmSignature,
+        /// An external address is already linked to some account.
+        AddressAlreadyLinked,
+        /// An external address is not linked to this account.
+        AddressNotLinked,
+        /// This account has reached the maximum number of linked addresses.
+        TooManyLinkedAddresses,
     }
 
     #[pallet::call]
@@ -105,6 +154,62 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Link an EVM address to the caller's account.
+        ///
+        /// `signature` must be made by `evm_address` with `personal_sign` (EIP-191)
+        /// over `evm_link_message_for(caller, address_link_nonce(caller))`.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::link_evm_address())]
+        pub fn link_evm_address(
+            origin: OriginFor<T>,
+            evm_address: H160,
+            signature: ecdsa::Signature,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let nonce = Self::address_link_nonce(&sender);
+            let message_hash = eip191_hash(&Self::evm_link_message_for(&sender, nonce));
+            ensure!(
+                Self::recover_evm_address(&signature, &message_hash) == Some(evm_address),
+                Error::<T>::InvalidEvmSignature
+            );
+
+            let address = ExternalAddress::Evm(evm_address);
+            ensure!(
+                !AccountByLinkedAddress::<T>::contains_key(address),
+                Error::<T>::AddressAlreadyLinked
+            );
+
+            LinkedAddressesByAccount::<T>::try_mutate(&sender, |addresses| {
+                addresses.try_push(address).map_err(|_| Error::<T>::TooManyLinkedAddresses)
+            })?;
+            AccountByLinkedAddress::<T>::insert(address, &sender);
+            AddressLinkNonce::<T>::insert(&sender, nonce.saturating_add(1));
+
+            Self::deposit_event(Event::AddressLinked { account: sender, address });
+            Ok(())
+        }
+
+        /// Unlink an external address from the caller's account.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::unlink_address())]
+        pub fn unlink_address(origin: OriginFor<T>, address: ExternalAddress) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(
+                Self::account_by_linked_address(address).as_ref() == Some(&sender),
+                Error::<T>::AddressNotLinked
+            );
+
+            AccountByLinkedAddress::<T>::remove(address);
+            LinkedAddressesByAccount::<T>::mutate(&sender, |addresses| {
+                addresses.retain(|linked| *linked != address)
+            });
+
+            Self::deposit_event(Event::AddressUnlinked { account: sender, address });
+            Ok(())
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -120,6 +225,24 @@ pub mod pallet {
             Ok(())
         }
 
+        /// A message, that an EVM account signs to be linked to a given account on this chain.
+        /// See `evm_link_message`.
+        pub fn evm_link_message_for(account: &T::AccountId, nonce: u32) -> Vec<u8> {
+            let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+            evm_link_message(account, T::SS58Prefix::get(), genesis_hash.as_ref(), nonce)
+        }
+
+        /// Recover an EVM address, that has made a given signature over a given message hash.
+        pub fn recover_evm_address(
+            signature: &ecdsa::Signature,
+            message_hash: &[u8; 32],
+        ) -> Option<H160> {
+            let public_key =
+                sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, message_hash).ok()?;
+            let public_key_hash = sp_io::hashing::keccak_256(&public_key);
+            Some(H160::from_slice(&public_key_hash[12..]))
+        }
+
         pub fn unlink_space_from_profile(account: &T::AccountId, space_id: SpaceId) {
             if let Some(profile_space_id) = Self::profile_space_id_by_account(account) {
                 if profile_space_id == space_id {
@@ -139,3 +262,15 @@ pub mod pallet {
         }
     }
 }
+
+/// A helper to fund accounts in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId> {
+    /// Give a given account enough funds to reserve storage deposits of a new space.
+    fn fund_account(account: &AccountId);
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl<AccountId> BenchmarkHelper<AccountId> for () {
+    fn fund_account(_account: &AccountId) {}
+}
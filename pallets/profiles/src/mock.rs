@@ -1,5 +1,9 @@
 use crate as pallet_profiles;
-use frame_support::{dispatch::DispatchResult, parameter_types, traits::Everything};
+use frame_support::{
+    dispatch::DispatchResult,
+    parameter_types,
+    traits::{ConstU32, Everything},
+};
 use frame_system as system;
 use lazy_static::lazy_static;
 use mockall::mock;
@@ -69,6 +73,7 @@ impl system::Config for Test {
 
 impl pallet_permissions::Config for Test {
     type DefaultSpacePermissions = DefaultSpacePermissions;
+    type NamespacedPermissions = ();
 }
 
 mock! {
@@ -78,6 +83,8 @@ mock! {
         fn space_permissions_info(id: SpaceId) -> Result<SpacePermissionsInfoOf<Test>, DispatchError>;
 
         fn ensure_space_owner(id: SpaceId, account: &AccountId) -> DispatchResult;
+
+        fn is_space_accessible_by(id: SpaceId, account: &AccountId) -> bool;
     }
 
     impl SpacesInterface<AccountId, SpaceId> for Spaces {
@@ -91,6 +98,9 @@ impl pallet_profiles::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type SpacePermissionsProvider = MockSpaces;
     type SpacesInterface = MockSpaces;
+    type MaxLinkedAddresses = ConstU32<2>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
     type WeightInfo = ();
 }
 
@@ -1,5 +1,6 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_err, assert_ok};
+use crate::{eip191_hash, evm_link_message, mock::*, Error, ExternalAddress};
+use frame_support::{assert_err, assert_noop, assert_ok};
+use sp_core::{bytes::from_hex, ecdsa, Pair, H160};
 use subsocial_support::mock_functions::valid_content_ipfs;
 
 // For now, this one works for `do_set_profile` as well.
@@ -197,3 +198,166 @@ fn unlink_space_from_profile_should_work_when_provided_space_differs_from_profil
         assert_eq!(Profiles::profile_space_id_by_account(account), Some(space_id));
     });
 }
+
+fn sign_evm_link_message(seed: u8, account: AccountId, nonce: u32) -> (H160, ecdsa::Signature) {
+    let pair = ecdsa::Pair::from_seed(&[seed; 32]);
+    let message_hash = eip191_hash(&Profiles::evm_link_message_for(&account, nonce));
+    let signature = pair.sign_prehashed(&message_hash);
+    let evm_address = Profiles::recover_evm_address(&signature, &message_hash).unwrap();
+    (evm_address, signature)
+}
+
+#[test]
+fn recover_evm_address_should_match_personal_sign() {
+    // A signature of "Some data" made by `personal_sign` of a well-known test key.
+    let signature = from_hex(
+        "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd\
+        6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c",
+    )
+    .unwrap();
+    let signature = ecdsa::Signature::from_slice(&signature).unwrap();
+    let evm_address =
+        H160::from_slice(&from_hex("0x2c7536E3605D9C16a7a3D7b1898e529396a65c23").unwrap());
+
+    let message_hash = eip191_hash(b"Some data");
+    assert_eq!(
+        message_hash.to_vec(),
+        from_hex("0x1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655").unwrap()
+    );
+    assert_eq!(Profiles::recover_evm_address(&signature, &message_hash), Some(evm_address));
+}
+
+#[test]
+fn evm_link_message_should_include_ss58_address_and_genesis_hash() {
+    // A public key of the well-known development account Alice.
+    let alice: [u8; 32] =
+        from_hex("0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+    assert_eq!(
+        evm_link_message(&alice, 42, &[0xab; 2], 7),
+        b"Link to Subsocial account: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY, \
+        genesis: 0xabab, nonce: 7"
+            .to_vec()
+    );
+}
+
+#[test]
+fn link_evm_address_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        let account = 1;
+        let (evm_address, signature) = sign_evm_link_message(1, account, 0);
+
+        assert_ok!(Profiles::link_evm_address(
+            RuntimeOrigin::signed(account),
+            evm_address,
+            signature
+        ));
+
+        let address = ExternalAddress::Evm(evm_address);
+        assert_eq!(Profiles::account_by_linked_address(address), Some(account));
+        assert_eq!(Profiles::linked_addresses_by_account(account).to_vec(), vec![address]);
+        assert_eq!(Profiles::address_link_nonce(account), 1);
+    });
+}
+
+#[test]
+fn link_evm_address_should_fail_when_signed_for_another_account() {
+    ExtBuilder::build().execute_with(|| {
+        let (evm_address, signature) = sign_evm_link_message(1, 2, 0);
+
+        assert_noop!(
+            Profiles::link_evm_address(RuntimeOrigin::signed(1), evm_address, signature),
+            Error::<Test>::InvalidEvmSignature
+        );
+    });
+}
+
+#[test]
+fn link_evm_address_should_fail_when_signed_for_another_chain() {
+    ExtBuilder::build().execute_with(|| {
+        let account = 1;
+        let pair = ecdsa::Pair::from_seed(&[1; 32]);
+        let message_hash = eip191_hash(&evm_link_message(&account, 42, &[0xab; 32], 0));
+        let signature = pair.sign_prehashed(&message_hash);
+        let evm_address = Profiles::recover_evm_address(&signature, &message_hash).unwrap();
+
+        assert_noop!(
+            Profiles::link_evm_address(RuntimeOrigin::signed(account), evm_address, signature),
+            Error::<Test>::InvalidEvmSignature
+        );
+    });
+}
+
+#[test]
+fn link_evm_address_should_fail_when_signature_is_replayed() {
+    ExtBuilder::build().execute_with(|| {
+        let account = 1;
+        let (evm_address, signature) = sign_evm_link_message(1, account, 0);
+
+        assert_ok!(Profiles::link_evm_address(
+            RuntimeOrigin::signed(account),
+            evm_address,
+            signature.clone()
+        ));
+        assert_ok!(Profiles::unlink_address(
+            RuntimeOrigin::signed(account),
+            ExternalAddress::Evm(evm_address)
+        ));
+
+        assert_noop!(
+            Profiles::link_evm_address(RuntimeOrigin::signed(account), evm_address, signature),
+            Error::<Test>::InvalidEvmSignature
+        );
+    });
+}
+
+#[test]
+fn link_evm_address_should_fail_when_already_linked_to_another_account() {
+    ExtBuilder::build().execute_with(|| {
+        let (evm_address, signature) = sign_evm_link_message(1, 1, 0);
+        assert_ok!(Profiles::link_evm_address(RuntimeOrigin::signed(1), evm_address, signature));
+
+        let (_, signature) = sign_evm_link_message(1, 2, 0);
+        assert_noop!(
+            Profiles::link_evm_address(RuntimeOrigin::signed(2), evm_address, signature),
+            Error::<Test>::AddressAlreadyLinked
+        );
+    });
+}
+
+#[test]
+fn link_evm_address_should_fail_when_too_many_addresses_linked() {
+    ExtBuilder::build().execute_with(|| {
+        let account = 1;
+        for (nonce, seed) in (1..=2).enumerate() {
+            let (evm_address, signature) = sign_evm_link_message(seed, account, nonce as u32);
+            assert_ok!(Profiles::link_evm_address(
+                RuntimeOrigin::signed(account),
+                evm_address,
+                signature
+            ));
+        }
+
+        let (evm_address, signature) = sign_evm_link_message(3, account, 2);
+        assert_noop!(
+            Profiles::link_evm_address(RuntimeOrigin::signed(account), evm_address, signature),
+            Error::<Test>::TooManyLinkedAddresses
+        );
+    });
+}
+
+#[test]
+fn unlink_address_should_fail_when_not_linked_to_caller() {
+    ExtBuilder::build().execute_with(|| {
+        let (evm_address, signature) = sign_evm_link_message(1, 1, 0);
+        assert_ok!(Profiles::link_evm_address(RuntimeOrigin::signed(1), evm_address, signature));
+
+        assert_noop!(
+            Profiles::unlink_address(RuntimeOrigin::signed(2), ExternalAddress::Evm(evm_address)),
+            Error::<Test>::AddressNotLinked
+        );
+    });
+}
@@ -0,0 +1,125 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::H160;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// An address of an account on another chain, that can be linked to a profile.
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum ExternalAddress {
+    /// An Ethereum (or any other EVM chain) address.
+    Evm(H160),
+}
+
+/// A prefix of a message, that an EVM account signs to be linked to a Subsocial account.
+pub const EVM_LINK_MESSAGE_PREFIX: &[u8] = b"Link to Subsocial account: ";
+
+/// A message that an EVM account signs to be linked to a given Subsocial account:
+/// `Link to Subsocial account: <SS58 address>, genesis: 0x<genesis hash in hex>, nonce: <nonce>`.
+///
+/// The account is shown as an SS58 address of its' SCALE encoding with a given prefix,
+/// as wallets show it. The genesis hash binds a signature to a single chain,
+/// and the nonce is increased on every link, so that a signature cannot be replayed.
+pub fn evm_link_message<AccountId: Encode>(
+    account: &AccountId,
+    ss58_prefix: u16,
+    genesis_hash: &[u8],
+    nonce: u32,
+) -> Vec<u8> {
+    let mut message = EVM_LINK_MESSAGE_PREFIX.to_vec();
+    push_ss58(&mut message, ss58_prefix, &account.encode());
+
+    message.extend_from_slice(b", genesis: 0x");
+    push_hex(&mut message, genesis_hash);
+
+    message.extend_from_slice(b", nonce: ");
+    push_decimal(&mut message, nonce);
+    message
+}
+
+/// A hash of a message as it is signed by `personal_sign` in EVM wallets (EIP-191).
+pub fn eip191_hash(message: &[u8]) -> [u8; 32] {
+    let mut prefixed = b"\x19Ethereum Signed Message:\n".to_vec();
+    push_decimal(&mut prefixed, message.len() as u32);
+    prefixed.extend_from_slice(message);
+    sp_io::hashing::keccak_256(&prefixed)
+}
+
+fn push_decimal(buf: &mut Vec<u8>, mut number: u32) {
+    let start = buf.len();
+    loop {
+        buf.push(b'0' + (number % 10) as u8);
+        number /= 10;
+        if number == 0 {
+            break
+        }
+    }
+    buf[start..].reverse();
+}
+
+fn push_hex(buf: &mut Vec<u8>, bytes: &[u8]) {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    for byte in bytes {
+        buf.push(HEX_DIGITS[(byte >> 4) as usize]);
+        buf.push(HEX_DIGITS[(byte & 0x0f) as usize]);
+    }
+}
+
+/// Push an SS58 address of a given payload: base58 of the address type (prefix),
+/// the payload and a checksum, which is the beginning of `blake2_512("SS58PRE" ++ data)`.
+fn push_ss58(buf: &mut Vec<u8>, prefix: u16, payload: &[u8]) {
+    let mut data = match prefix {
+        0..=63 => sp_std::vec![prefix as u8],
+        _ => {
+            // A two bytes address type, as described in the SS58 format:
+            let ident = prefix & 0b0011_1111_1111_1111;
+            let first = ((ident & 0b0000_0000_1111_1100) as u8 >> 2) | 0b0100_0000;
+            let second = ((ident >> 8) as u8) | ((ident & 0b0000_0000_0000_0011) as u8) << 6;
+            sp_std::vec![first, second]
+        },
+    };
+    data.extend_from_slice(payload);
+
+    let mut preimage = b"SS58PRE".to_vec();
+    preimage.extend_from_slice(&data);
+    let checksum_len = if payload.len() >= 32 { 2 } else { 1 };
+    data.extend_from_slice(&sp_io::hashing::blake2_512(&preimage)[..checksum_len]);
+
+    push_base58(buf, &data);
+}
+
+fn push_base58(buf: &mut Vec<u8>, bytes: &[u8]) {
+    const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    // Base58 digits of a big-endian number, the least significant digit goes first:
+    let mut digits: Vec<u8> = Vec::new();
+    for byte in bytes {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let leading_zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+    buf.extend(sp_std::iter::repeat(ALPHABET[0]).take(leading_zeros));
+    buf.extend(digits.iter().rev().map(|digit| ALPHABET[*digit as usize]));
+}
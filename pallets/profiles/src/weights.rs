@@ -5,6 +5,9 @@
 //! DATE: 2023-02-15, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! HOSTNAME: `benchmarks-ci`, CPU: `Intel(R) Xeon(R) Platinum 8280 CPU @ 2.70GHz`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! Entries marked as "Not benchmarked yet" were added by hand after this run,
+//! and must be regenerated with the command below.
 
 // Executed Command:
     // ./scripts/../target/release/subsocial-collator
@@ -44,6 +47,8 @@ pub trait WeightInfo {
     fn set_profile() -> Weight;
     fn reset_profile() -> Weight;
     fn create_space_as_profile() -> Weight;
+    fn link_evm_address() -> Weight;
+    fn unlink_address() -> Weight;
 }
 
 /// Weights for pallet_profiles using the Substrate node and recommended hardware.
@@ -75,6 +80,24 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
             .saturating_add(T::DbWeight::get().reads(3))
             .saturating_add(T::DbWeight::get().writes(4))
         }
+            // Storage: Profiles AddressLinkNonce (r:1 w:1)
+            // Storage: System BlockHash (r:1 w:0)
+            // Storage: Profiles AccountByLinkedAddress (r:1 w:1)
+            // Storage: Profiles LinkedAddressesByAccount (r:1 w:1)
+        fn link_evm_address() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(64_871_000)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(3))
+        }
+            // Storage: Profiles AccountByLinkedAddress (r:1 w:1)
+            // Storage: Profiles LinkedAddressesByAccount (r:1 w:1)
+        fn unlink_address() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(29_413_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+        }
     }
 
     // For backwards compatibility and tests
@@ -105,4 +128,22 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
             .saturating_add(RocksDbWeight::get().reads(3))
             .saturating_add(RocksDbWeight::get().writes(4))
         }
+            // Storage: Profiles AddressLinkNonce (r:1 w:1)
+            // Storage: System BlockHash (r:1 w:0)
+            // Storage: Profiles AccountByLinkedAddress (r:1 w:1)
+            // Storage: Profiles LinkedAddressesByAccount (r:1 w:1)
+        fn link_evm_address() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(64_871_000)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(3))
+        }
+            // Storage: Profiles AccountByLinkedAddress (r:1 w:1)
+            // Storage: Profiles LinkedAddressesByAccount (r:1 w:1)
+        fn unlink_address() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(29_413_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+        }
     }
@@ -4,16 +4,26 @@
 
 use super::*;
 use frame_benchmarking::{benchmarks, whitelisted_caller};
-use frame_support::dispatch::DispatchError;
+use frame_support::{dispatch::DispatchError, traits::Currency};
 use frame_system::RawOrigin;
 use pallet_posts::{Post, PostExtension};
-use pallet_spaces::types::Space;
+use pallet_spaces::{types::Space, BalanceOf};
+use sp_runtime::traits::Bounded;
 use sp_std::vec;
 use subsocial_support::Content;
 
+fn fund_account<T: Config>(origin: &RawOrigin<T::AccountId>) {
+    if let RawOrigin::Signed(account) = origin {
+        let balance = BalanceOf::<T>::max_value() / 2u32.into();
+        <T as pallet_spaces::Config>::Currency::make_free_balance_be(account, balance);
+    }
+}
+
 fn create_dummy_space<T: Config>(
     origin: RawOrigin<T::AccountId>,
 ) -> Result<Space<T>, DispatchError> {
+    fund_account::<T>(&origin);
+
     let space_id = pallet_spaces::NextSpaceId::<T>::get();
 
     pallet_spaces::Pallet::<T>::create_space(origin.clone().into(), Content::None, None)?;
@@ -1,21 +1,25 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode};
-use frame_support::{dispatch::DispatchResult, ensure, traits::Get};
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::{Get, ReservableCurrency},
+};
 use frame_system::ensure_signed;
 use scale_info::TypeInfo;
 
 #[cfg(feature = "std")]
 use serde::Deserialize;
-use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_runtime::{traits::Zero, DispatchError, RuntimeDebug};
 use sp_std::prelude::*;
 
 use pallet_permissions::SpacePermission;
 use pallet_posts::{Pallet as Posts, PostById};
-use pallet_spaces::Pallet as Spaces;
+use pallet_spaces::{BalanceOf, Pallet as Spaces};
 use subsocial_support::{
-    new_who_and_when, remove_from_vec, traits::IsAccountBlocked, ModerationError, PostId,
-    WhoAndWhenOf,
+    new_who_and_when, remove_from_vec, space_owner_walk_weight, traits::IsAccountBlocked,
+    ModerationError, PostId, WhoAndWhenOf,
 };
 
 pub use pallet::*;
@@ -68,6 +72,10 @@ pub mod pallet {
         /// The overarching event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+        /// The amount held on deposit for storing a reaction.
+        #[pallet::constant]
+        type BaseReactionDeposit: Get<BalanceOf<Self>>;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -101,6 +109,12 @@ pub mod pallet {
     pub type PostReactionIdByAccount<T: Config> =
         StorageMap<_, Blake2_128Concat, (T::AccountId, PostId), ReactionId, ValueQuery>;
 
+    /// Get the storage deposit reserved from the owner of a reaction by the reaction id.
+    #[pallet::storage]
+    #[pallet::getter(fn reaction_deposit)]
+    pub type ReactionDeposits<T: Config> =
+        StorageMap<_, Twox64Concat, ReactionId, BalanceOf<T>, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -156,7 +170,10 @@ pub mod pallet {
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
-        #[pallet::weight(< T as Config >::WeightInfo::create_post_reaction())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::create_post_reaction()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn create_post_reaction(
             origin: OriginFor<T>,
             post_id: PostId,
@@ -172,6 +189,7 @@ pub mod pallet {
 
             let space = post.get_space()?;
             ensure!(!space.hidden, Error::<T>::CannotReactWhenSpaceHidden);
+            Spaces::<T>::ensure_space_not_archived(space.id)?;
             ensure!(
                 Posts::<T>::is_root_post_visible(post_id)?,
                 Error::<T>::CannotReactWhenPostHidden
@@ -205,6 +223,7 @@ pub mod pallet {
 
             PostById::<T>::insert(post_id, post.clone());
             let reaction_id = Self::insert_new_reaction(owner.clone(), kind);
+            Self::reserve_reaction_deposit(&owner, reaction_id)?;
             ReactionIdsByPostId::<T>::mutate(post.id, |ids| ids.push(reaction_id));
             PostReactionIdByAccount::<T>::insert((owner.clone(), post_id), reaction_id);
 
@@ -218,7 +237,10 @@ pub mod pallet {
         }
 
         #[pallet::call_index(1)]
-        #[pallet::weight(< T as Config >::WeightInfo::update_post_reaction())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::update_post_reaction()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn update_post_reaction(
             origin: OriginFor<T>,
             post_id: PostId,
@@ -271,7 +293,10 @@ pub mod pallet {
         }
 
         #[pallet::call_index(2)]
-        #[pallet::weight(< T as Config >::WeightInfo::delete_post_reaction())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::delete_post_reaction()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn delete_post_reaction(
             origin: OriginFor<T>,
             post_id: PostId,
@@ -305,6 +330,7 @@ pub mod pallet {
             ReactionById::<T>::remove(reaction_id);
             ReactionIdsByPostId::<T>::mutate(post.id, |ids| remove_from_vec(ids, reaction_id));
             PostReactionIdByAccount::<T>::remove((owner.clone(), post_id));
+            Self::unreserve_reaction_deposit(&owner, reaction_id);
 
             Self::deposit_event(Event::PostReactionDeleted {
                 account: owner,
@@ -382,7 +408,8 @@ pub mod pallet {
                 Ok(Pays::No.into())
             })?;
             ReactionById::<T>::remove(reaction_id);
-            PostReactionIdByAccount::<T>::remove((who, post_id));
+            PostReactionIdByAccount::<T>::remove((who.clone(), post_id));
+            Self::unreserve_reaction_deposit(&who, reaction_id);
 
             Ok(Pays::No.into())
         }
@@ -417,6 +444,23 @@ impl<T: Config> Pallet<T> {
         id
     }
 
+    fn reserve_reaction_deposit(owner: &T::AccountId, reaction_id: ReactionId) -> DispatchResult {
+        let reaction = Self::require_reaction(reaction_id)?;
+        let deposit =
+            Spaces::<T>::storage_deposit(T::BaseReactionDeposit::get(), reaction.encoded_size());
+
+        Spaces::<T>::update_reserved_deposit(owner, Zero::zero(), deposit)?;
+        if !deposit.is_zero() {
+            ReactionDeposits::<T>::insert(reaction_id, deposit);
+        }
+        Ok(())
+    }
+
+    fn unreserve_reaction_deposit(owner: &T::AccountId, reaction_id: ReactionId) {
+        let deposit = ReactionDeposits::<T>::take(reaction_id);
+        <T as pallet_spaces::Config>::Currency::unreserve(owner, deposit);
+    }
+
     /// Get `Reaction` by id from the storage or return `ReactionNotFound` error.
     pub fn require_reaction(reaction_id: ReactionId) -> Result<Reaction<T>, DispatchError> {
         Ok(Self::reaction_by_id(reaction_id).ok_or(Error::<T>::ReactionNotFound)?)
@@ -1,7 +1,7 @@
-use frame_support::{pallet_prelude::ConstU32, parameter_types, traits::Everything};
+use frame_support::{pallet_prelude::{ConstU32, ConstU64}, parameter_types, traits::Everything};
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
 };
 use sp_std::convert::{TryFrom, TryInto};
@@ -96,13 +96,17 @@ parameter_types! {
 
 impl pallet_posts::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type SpaceStats = Spaces;
     type MaxCommentDepth = MaxCommentDepth;
     type IsPostBlocked = ();
+    type BasePostDeposit = ConstU64<0>;
+    type BaseCommentDeposit = ConstU64<0>;
     type WeightInfo = ();
 }
 
 impl pallet_permissions::Config for Test {
     type DefaultSpacePermissions = pallet_permissions::default_permissions::DefaultSpacePermissions;
+    type NamespacedPermissions = ();
 }
 
 parameter_types! {
@@ -114,8 +118,17 @@ impl pallet_roles::Config for Test {
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
     type SpacePermissionsProvider = Spaces;
     type SpaceFollows = SpaceFollows;
+    type Currency = Balances;
+    type JoinRequestDeposit = ConstU64<0>;
+    type DomainsProvider = ();
     type IsAccountBlocked = ();
     type IsContentBlocked = ();
+    type SpaceSettings = Spaces;
+    type OnRoleRemoved = ();
+    type RoleProposalLifetime = ConstU64<100>;
+    type RoleProposalDeposit = ConstU64<0>;
+    type InviteSigner = UintAuthorityId;
+    type InviteSignature = TestSignature;
     type WeightInfo = ();
 }
 
@@ -125,16 +138,34 @@ impl pallet_spaces::Config for Test {
     type SpaceFollows = SpaceFollows;
     type IsAccountBlocked = ();
     type IsContentBlocked = ();
+    type SpacePosts = Posts;
+    type ProfileManager = ();
+    type OnSpaceDeleted = (SpaceFollows, Roles);
+    type OnSpaceOwnerChanged = ();
+    type SpaceSettingsHandler = Posts;
+    type SpaceOwnerOrigin = frame_system::EnsureSigned<AccountId>;
+    type SpaceAccounts = ();
+    type Currency = Balances;
+    type BaseSpaceDeposit = ConstU64<0>;
+    type StorageByteDeposit = ConstU64<0>;
     type MaxSpacesPerAccount = ConstU32<100>;
+    type MinHandleLen = ConstU32<5>;
+    type MaxHandleLen = ConstU32<50>;
     type WeightInfo = ();
 }
 
 impl pallet_space_follows::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type SpaceStats = Spaces;
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub static BaseReactionDeposit: Balance = 0;
+}
+
 impl pallet_reactions::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type BaseReactionDeposit = BaseReactionDeposit;
     type WeightInfo = ();
 }
@@ -2,6 +2,7 @@ use frame_support::{assert_noop, assert_ok};
 
 use pallet_posts::Error as PostsError;
 use pallet_reactions::Error as ReactionsError;
+use pallet_spaces::Error as SpacesError;
 
 use crate::{mock::*, tests_utils::*};
 
@@ -95,3 +96,40 @@ fn create_post_reaction_should_fail_when_trying_to_react_on_hidden_post() {
         );
     });
 }
+
+#[test]
+fn create_post_reaction_should_reserve_deposit() {
+    ExtBuilder::build_with_post().execute_with(|| {
+        BaseReactionDeposit::set(5);
+
+        assert_ok!(_create_default_post_reaction()); // ReactionId 1 by ACCOUNT1
+
+        assert_eq!(Reactions::reaction_deposit(REACTION1), 5);
+        assert_eq!(Balances::reserved_balance(ACCOUNT1), 5);
+    });
+}
+
+#[test]
+fn create_post_reaction_should_fail_when_deposit_cannot_be_reserved() {
+    ExtBuilder::build_with_post().execute_with(|| {
+        BaseReactionDeposit::set(1000);
+
+        assert_noop!(
+            _create_default_post_reaction(),
+            SpacesError::<Test>::InsufficientBalanceToReserveDeposit
+        );
+    });
+}
+
+#[test]
+fn delete_post_reaction_should_unreserve_deposit() {
+    ExtBuilder::build_with_post().execute_with(|| {
+        BaseReactionDeposit::set(5);
+        assert_ok!(_create_default_post_reaction()); // ReactionId 1 by ACCOUNT1
+
+        assert_ok!(_delete_post_reaction(None, None, REACTION1));
+
+        assert_eq!(Reactions::reaction_deposit(REACTION1), 0);
+        assert_eq!(Balances::reserved_balance(ACCOUNT1), 0);
+    });
+}
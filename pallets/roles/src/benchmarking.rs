@@ -6,17 +6,34 @@
 
 use super::*;
 use frame_benchmarking::{account, benchmarks};
-use frame_support::dispatch::DispatchError;
+use frame_support::{dispatch::DispatchError, traits::Currency};
 use frame_system::RawOrigin;
 use pallet_permissions::SpacePermission as SP;
-use pallet_spaces::types::Space;
+use pallet_spaces::{types::Space, BalanceOf};
+use sp_runtime::traits::Bounded;
 use sp_std::{prelude::Vec, vec};
 use subsocial_support::{Content, User};
 use subsocial_support::mock_functions::{valid_content_ipfs, another_valid_content_ipfs};
 
+use crate::weights::WeightInfo;
+
+fn fund_account<T: Config + pallet_spaces::Config>(origin: &RawOrigin<T::AccountId>) {
+    if let RawOrigin::Signed(account) = origin {
+        let balance = BalanceOf::<T>::max_value() / 2u32.into();
+        <T as pallet_spaces::Config>::Currency::make_free_balance_be(account, balance);
+    }
+}
+
+fn fund_requester<T: Config>(requester: &T::AccountId) {
+    let balance = crate::BalanceOf::<T>::max_value() / 2u32.into();
+    <T as Config>::Currency::make_free_balance_be(requester, balance);
+}
+
 fn create_dummy_space<T: Config + pallet_spaces::Config>(
     origin: RawOrigin<T::AccountId>,
 ) -> Result<Space<T>, DispatchError> {
+    fund_account::<T>(&origin);
+
     let space_id = pallet_spaces::NextSpaceId::<T>::get();
 
     pallet_spaces::Pallet::<T>::create_space(origin.into(), Content::None, None)?;
@@ -51,6 +68,7 @@ fn create_dummy_role<T: Config>(
         Some(100u32.into()),
         Content::None,
         vec![SP::ManageRoles],
+        0,
     )?;
 
     let role = RoleById::<T>::get(role_id).ok_or(DispatchError::Other("Role not found"))?;
@@ -58,7 +76,7 @@ fn create_dummy_role<T: Config>(
     let users_to_grant = dummy_list_of_users::<T>(num_of_users);
 
     if !users_to_grant.is_empty() {
-        Pallet::<T>::grant_role(origin.into(), role.id, users_to_grant.clone())?;
+        Pallet::<T>::grant_role(origin.into(), role.id, users_to_grant.clone(), None)?;
     }
 
     Ok((role, users_to_grant))
@@ -74,13 +92,14 @@ benchmarks! {
         let content = valid_content_ipfs();
         let perms = vec![SP::ManageRoles];
         let role_id = NextRoleId::<T>::get();
-    }: _(caller_origin, space.id, time_to_live, content, perms)
+    }: _(caller_origin, space.id, time_to_live, content, perms, 1)
     verify {
         let role = RoleById::<T>::get(role_id).unwrap();
         let space_roles_ids = RoleIdsBySpaceId::<T>::get(space.id);
 
         ensure!(role.id == role_id, "Role id doesn't match");
         ensure!(space_roles_ids.contains(&role_id), "Role id not in space roles");
+        ensure!(RoleRankById::<T>::get(role_id) == 1, "Role rank doesn't match");
     }
 
     update_role {
@@ -101,6 +120,46 @@ benchmarks! {
         ensure!(role.disabled, "Role should be disabled");
     }
 
+    set_role_rank {
+        let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(caller_origin.clone())?;
+        let (role, _) = create_dummy_role::<T>(caller_origin.clone(), space.id, 0)?;
+        let rank: RoleRank = 10;
+    }: _(caller_origin, role.id, rank)
+    verify {
+        ensure!(RoleRankById::<T>::get(role.id) == rank, "Role rank should be updated");
+    }
+
+    set_role_condition {
+        let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(caller_origin.clone())?;
+        let (role, _) = create_dummy_role::<T>(caller_origin.clone(), space.id, 0)?;
+        let min_balance = <T as Config>::Currency::minimum_balance();
+    }: _(caller_origin, role.id, Some(RoleCondition::MinBalance(min_balance)))
+    verify {
+        ensure!(RoleConditionById::<T>::contains_key(role.id), "Role condition should be set");
+    }
+
+    refresh_conditional_role {
+        let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(caller_origin.clone())?;
+        let (role, _) = create_dummy_role::<T>(caller_origin.clone(), space.id, 0)?;
+
+        let min_balance = <T as Config>::Currency::minimum_balance();
+        Pallet::<T>::set_role_condition(
+            caller_origin.into(),
+            role.id,
+            Some(RoleCondition::MinBalance(min_balance)),
+        )?;
+
+        let claimer = account::<T::AccountId>("Acc2", 2, 0);
+        <T as Config>::Currency::make_free_balance_be(&claimer, min_balance * 10u32.into());
+    }: _(RawOrigin::Signed(claimer.clone()), role.id, claimer.clone())
+    verify {
+        let granted_users = UsersByRoleId::<T>::get(role.id);
+        ensure!(granted_users.contains(&User::Account(claimer)), "Role should be granted");
+    }
+
     delete_role {
         let x in 0..T::MaxUsersToProcessPerDeleteRole::get().into();
         let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
@@ -112,6 +171,61 @@ benchmarks! {
         ensure!(deleted, "Role should be deleted");
     }
 
+    delete_expired_role {
+        let x in 0..T::MaxUsersToProcessPerDeleteRole::get().into();
+        let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(caller_origin.clone())?;
+        let (role, _) = create_dummy_role::<T>(caller_origin, space.id, x)?;
+        let expires_at = role.expires_at.ok_or(DispatchError::Other("Role should expire"))?;
+        let max_users = T::MaxUsersToProcessPerDeleteRole::get().into();
+    }: {
+        if Pallet::<T>::delete_expired_role_step(role.id, max_users) {
+            RoleIdsExpiringAt::<T>::remove(expires_at, role.id);
+        }
+    }
+    verify {
+        let deleted = RoleById::<T>::get(role.id).is_none();
+        ensure!(deleted, "Role should be deleted");
+        ensure!(
+            !RoleIdsExpiringAt::<T>::contains_key(expires_at, role.id),
+            "Role expiration should be removed"
+        );
+    }
+
+    clean_deleted_role {
+        let x in 0..T::MaxUsersToProcessPerDeleteRole::get().into();
+        let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(caller_origin.clone())?;
+        let (role, _) = create_dummy_role::<T>(caller_origin, space.id, x)?;
+
+        RoleById::<T>::remove(role.id);
+        RolesPendingDeletion::<T>::insert(role.id, role.clone());
+        let weight = <T as Config>::WeightInfo::clean_deleted_role(x);
+    }: {
+        Pallet::<T>::clean_deleted_roles(&mut WeightMeter::new(weight.saturating_mul(2)));
+    }
+    verify {
+        ensure!(!RolesPendingDeletion::<T>::contains_key(role.id), "Role should be cleaned up");
+        ensure!(UsersByRoleId::<T>::get(role.id).is_empty(), "Role should be revoked");
+    }
+
+    clean_disabled_role {
+        let x in 0..T::MaxUsersToProcessPerDeleteRole::get().into();
+        let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(caller_origin.clone())?;
+        let (mut role, _) = create_dummy_role::<T>(caller_origin, space.id, x)?;
+
+        role.disabled = true;
+        RoleById::<T>::insert(role.id, role.clone());
+        DisabledRolesPendingCleanup::<T>::insert(role.id, x);
+        let weight = <T as Config>::WeightInfo::clean_disabled_role(x);
+    }: {
+        Pallet::<T>::clean_disabled_roles(&mut WeightMeter::new(weight.saturating_mul(2)));
+    }
+    verify {
+        ensure!(!DisabledRolesPendingCleanup::<T>::contains_key(role.id), "Role should be cleaned up");
+    }
+
     grant_role {
         let x in 1..500;
         let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
@@ -119,7 +233,8 @@ benchmarks! {
         let (role, _) = create_dummy_role::<T>(caller_origin.clone(), space.id, 0)?;
 
         let users_to_grant = dummy_list_of_users::<T>(x);
-    }: _(caller_origin, role.id, users_to_grant.clone())
+        let expires_at: Option<T::BlockNumber> = Some(100u32.into());
+    }: _(caller_origin, role.id, users_to_grant.clone(), expires_at)
     verify {
         let granted_users = UsersByRoleId::<T>::get(role.id);
         for user in users_to_grant {
@@ -127,6 +242,32 @@ benchmarks! {
         }
     }
 
+    revoke_expired_role_grant {
+        let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(caller_origin.clone())?;
+        let (role, _) = create_dummy_role::<T>(caller_origin.clone(), space.id, 0)?;
+
+        let user = User::Account(account::<T::AccountId>("Acc2", 2, 0));
+        let expires_at: T::BlockNumber = 100u32.into();
+        Pallet::<T>::grant_role(
+            caller_origin.into(),
+            role.id,
+            vec![user.clone()],
+            Some(expires_at),
+        )?;
+
+        let weight = <T as Config>::WeightInfo::revoke_expired_role_grant();
+    }: {
+        Pallet::<T>::clean_expired_role_grants(
+            expires_at,
+            &mut WeightMeter::new(weight.saturating_mul(2)),
+        );
+    }
+    verify {
+        let granted_users = UsersByRoleId::<T>::get(role.id);
+        ensure!(!granted_users.contains(&user), "Role grant should be revoked");
+    }
+
     revoke_role {
         let x in 1..500;
         let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
@@ -138,6 +279,155 @@ benchmarks! {
         ensure!(granted_users.is_empty(), "Role should have zero users");
     }
 
+    set_content_public_key {
+        let caller = account::<T::AccountId>("Acc1", 1, 0);
+    }: _(RawOrigin::Signed(caller.clone()), Some([1; 32]))
+    verify {
+        ensure!(ContentPublicKeyByAccount::<T>::contains_key(&caller), "Public key should be set");
+    }
+
+    publish_content_key_envelopes {
+        let x in 1..500;
+        let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(caller_origin.clone())?;
+        let (_, members) = create_dummy_role::<T>(caller_origin.clone(), space.id, x)?;
+
+        let mut envelopes = Vec::new();
+        for member in members {
+            let member = member.maybe_account().expect("qed; dummy users are accounts");
+            ContentPublicKeyByAccount::<T>::insert(&member, [1; 32]);
+            envelopes.push((member, vec![0; MAX_ENCRYPTED_CONTENT_KEY_LEN as usize]));
+        }
+    }: _(caller_origin, space.id, 1, envelopes, 0)
+    verify {
+        let key_version = ContentKeyVersionBySpaceId::<T>::get(space.id);
+        ensure!(key_version == 1, "Content key should be rotated");
+    }
+
+    request_to_join_space {
+        let owner_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(owner_origin)?;
+        let caller = account::<T::AccountId>("Acc2", 2, 0);
+        fund_requester::<T>(&caller);
+    }: _(RawOrigin::Signed(caller.clone()), space.id)
+    verify {
+        ensure!(JoinRequests::<T>::contains_key(space.id, &caller), "Join request should exist");
+    }
+
+    approve_join_request {
+        let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(caller_origin.clone())?;
+        let (role, _) = create_dummy_role::<T>(caller_origin.clone(), space.id, 10)?;
+
+        let requester = account::<T::AccountId>("Acc2", 2, 0);
+        fund_requester::<T>(&requester);
+        Pallet::<T>::request_to_join_space(RawOrigin::Signed(requester.clone()).into(), space.id)?;
+    }: _(caller_origin, role.id, requester.clone())
+    verify {
+        let granted_users = UsersByRoleId::<T>::get(role.id);
+        ensure!(granted_users.contains(&User::Account(requester)), "Role should be granted");
+    }
+
+    reject_join_request {
+        let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(caller_origin.clone())?;
+
+        let requester = account::<T::AccountId>("Acc2", 2, 0);
+        fund_requester::<T>(&requester);
+        Pallet::<T>::request_to_join_space(RawOrigin::Signed(requester.clone()).into(), space.id)?;
+    }: _(caller_origin, space.id, requester.clone())
+    verify {
+        ensure!(
+            !JoinRequests::<T>::contains_key(space.id, &requester),
+            "Join request should be removed"
+        );
+    }
+
+    cancel_join_request {
+        let owner_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(owner_origin)?;
+
+        let caller = account::<T::AccountId>("Acc2", 2, 0);
+        fund_requester::<T>(&caller);
+        Pallet::<T>::request_to_join_space(RawOrigin::Signed(caller.clone()).into(), space.id)?;
+    }: _(RawOrigin::Signed(caller.clone()), space.id)
+    verify {
+        ensure!(
+            !JoinRequests::<T>::contains_key(space.id, &caller),
+            "Join request should be removed"
+        );
+    }
+
+    redeem_invite_voucher {
+        let voucher = InviteVoucherOf::<T> {
+            role_id: NextRoleId::<T>::get(),
+            expires_at: 100u32.into(),
+            nonce: 0,
+        };
+        let (signer, signature) =
+            T::BenchmarkHelper::sign(&Pallet::<T>::invite_voucher_signing_payload(&voucher));
+
+        let signer_origin = RawOrigin::Signed(signer.clone());
+        let space = create_dummy_space::<T>(signer_origin.clone())?;
+        let (role, _) = create_dummy_role::<T>(signer_origin, space.id, 10)?;
+
+        let caller = account::<T::AccountId>("Acc2", 2, 0);
+    }: _(RawOrigin::Signed(caller.clone()), signer, voucher, signature)
+    verify {
+        let granted_users = UsersByRoleId::<T>::get(role.id);
+        ensure!(granted_users.contains(&User::Account(caller)), "Role should be granted");
+    }
+
+    approve_role_proposal {
+        let caller_origin = RawOrigin::Signed(account::<T::AccountId>("Acc1", 1, 0));
+        let space = create_dummy_space::<T>(caller_origin.clone())?;
+        let (role, users) = create_dummy_role::<T>(caller_origin.clone(), space.id, 1)?;
+
+        pallet_spaces::Pallet::<T>::update_space_settings(
+            caller_origin.clone().into(),
+            space.id,
+            vec![(RoleApprovalsSetting::<T>::KEY, Some(3u32.encode()))],
+        )?;
+
+        let proposer = account::<T::AccountId>("Acc2", 2, 0);
+        fund_requester::<T>(&proposer);
+        let proposal_id = NextRoleProposalId::<T>::get();
+        Pallet::<T>::create_role_proposal(
+            proposer.clone(),
+            proposer,
+            role.id,
+            RoleChange::RevokeRole { users },
+            true,
+        )?;
+    }: _(caller_origin, proposal_id)
+    verify {
+        let proposal = RoleProposalById::<T>::get(proposal_id)
+            .ok_or(DispatchError::Other("Role proposal not found"))?;
+        ensure!(proposal.approvals.len() == 2, "Role proposal should be approved");
+    }
+
+    cancel_role_proposal {
+        let caller = account::<T::AccountId>("Acc1", 1, 0);
+        let space = create_dummy_space::<T>(RawOrigin::Signed(caller.clone()))?;
+        let (role, _) = create_dummy_role::<T>(RawOrigin::Signed(caller.clone()), space.id, 0)?;
+
+        fund_requester::<T>(&caller);
+        let proposal_id = NextRoleProposalId::<T>::get();
+        Pallet::<T>::create_role_proposal(
+            caller.clone(),
+            caller.clone(),
+            role.id,
+            RoleChange::DeleteRole,
+            true,
+        )?;
+    }: _(RawOrigin::Signed(caller), proposal_id)
+    verify {
+        ensure!(
+            !RoleProposalById::<T>::contains_key(proposal_id),
+            "Role proposal should be removed"
+        );
+    }
+
     impl_benchmark_test_suite!(
         Pallet,
         crate::mock::ExtBuilder::build(),
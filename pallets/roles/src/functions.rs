@@ -1,7 +1,13 @@
 use super::*;
 
 use frame_support::dispatch::DispatchError;
-use pallet_permissions::SpacePermissionsContext;
+use pallet_permissions::{
+    PermissionCheckOutcome, PermissionCheckStep, PermissionSource, PermissionTrace,
+    SpacePermissionsContext,
+};
+use sp_runtime::traits::Saturating;
+
+use crate::weights::WeightInfo;
 
 impl<T: Config> Pallet<T> {
     /// Check that there is a `Role` with such `role_id` in the storage
@@ -30,20 +36,166 @@ impl<T: Config> Pallet<T> {
         )
     }
 
+    /// Ensure that this account is a role manager, who can manage a given role.
+    pub fn ensure_role_manager_of(account: T::AccountId, role: &Role<T>) -> DispatchResult {
+        Self::ensure_role_manager_of_rank(account, role.space_id, Self::role_rank(role.id))
+    }
+
+    /// Ensure that this account is a role manager in a given space,
+    /// whose highest managing role ranks above a given rank.
+    ///
+    /// The space owner can manage roles of any rank. In a space without ranked roles
+    /// every manager can manage any unranked role, but only the space owner can rank them.
+    /// Once a space has ranked roles, a manager without ranked roles cannot manage any roles,
+    /// as no rank is below `0`.
+    pub fn ensure_role_manager_of_rank(
+        account: T::AccountId,
+        space_id: SpaceId,
+        rank: RoleRank,
+    ) -> DispatchResult {
+        Self::ensure_role_manager(account.clone(), space_id)?;
+
+        if let Some(manager_rank) = Self::role_manager_rank(account, space_id) {
+            if Self::ranked_roles_count(space_id) == 0 {
+                ensure!(rank == 0, Error::<T>::RoleRankTooHigh);
+            } else {
+                ensure!(rank < manager_rank, Error::<T>::RoleRankTooHigh);
+            }
+        }
+        Ok(())
+    }
+
+    /// Set a rank of a given role, and keep track of the number of ranked roles in its' space.
+    /// A zero rank removes the rank of a role.
+    pub(crate) fn update_role_rank(space_id: SpaceId, role_id: RoleId, rank: RoleRank) {
+        let old_rank = RoleRankById::<T>::take(role_id);
+        if rank > 0 {
+            RoleRankById::<T>::insert(role_id, rank);
+        }
+
+        match (old_rank > 0, rank > 0) {
+            (false, true) => RankedRolesCountBySpaceId::<T>::mutate(space_id, |count| {
+                count.saturating_inc();
+            }),
+            (true, false) => RankedRolesCountBySpaceId::<T>::mutate_exists(space_id, |count| {
+                *count = count.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+            }),
+            _ => {},
+        }
+    }
+
+    /// Get the highest rank of active roles with `ManageRoles` permission,
+    /// that a given account has in a given space, or `None` if it is the space owner.
+    pub fn role_manager_rank(account: T::AccountId, space_id: SpaceId) -> Option<RoleRank> {
+        if T::SpacePermissionsProvider::ensure_space_owner(space_id, &account).is_ok() {
+            return None
+        }
+
+        let role_ids = Self::active_role_ids_with_permission(
+            User::Account(account),
+            space_id,
+            SpacePermission::ManageRoles,
+        );
+        Some(role_ids.into_iter().map(Self::role_rank).max().unwrap_or_default())
+    }
+
+    /// Ensure that this account has all given permissions in a given space,
+    /// so it can give them to other users via roles.
+    pub fn ensure_can_give_permissions(
+        account: T::AccountId,
+        space_id: SpaceId,
+        permissions: impl IntoIterator<Item = SpacePermission>,
+    ) -> DispatchResult {
+        let user = User::Account(account);
+        let ctx = Self::load_space_permissions_context(&user, space_id)?;
+
+        if ctx.is_space_owner {
+            return Ok(())
+        }
+
+        for permission in permissions {
+            Self::ensure_user_has_space_permission(
+                user.clone(),
+                ctx.clone(),
+                permission,
+                Error::<T>::CannotGivePermissionsNotHeld.into(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Ensure that this account is not blocked and has either 'ManageRoles'
+    /// or `REJECT_JOIN_REQUESTS` permission in a given space.
+    pub fn ensure_join_request_moderator(
+        account: T::AccountId,
+        space_id: SpaceId,
+    ) -> DispatchResult {
+        ensure!(
+            T::IsAccountBlocked::is_allowed_account(account.clone(), space_id),
+            ModerationError::AccountIsBlocked
+        );
+        Self::ensure_user_has_space_permission_with_load_space(
+            User::Account(account.clone()),
+            space_id,
+            REJECT_JOIN_REQUESTS.into(),
+            Error::<T>::NoPermissionToManageRoles.into(),
+        )
+        .or_else(|_| Self::ensure_role_manager(account, space_id))
+    }
+
+    /// Ensure that this account is not blocked and has 'ManageContentKeys' permission
+    /// in a given space.
+    pub fn ensure_content_key_manager(account: T::AccountId, space_id: SpaceId) -> DispatchResult {
+        ensure!(
+            T::IsAccountBlocked::is_allowed_account(account.clone(), space_id),
+            ModerationError::AccountIsBlocked
+        );
+        Self::ensure_user_has_space_permission_with_load_space(
+            User::Account(account),
+            space_id,
+            SpacePermission::ManageContentKeys,
+            Error::<T>::NoPermissionToManageContentKeys.into(),
+        )
+    }
+
+    /// Remove a content key envelope of an account that is no longer a member of a space,
+    /// and require a content key of this space to be rotated.
+    pub(crate) fn on_member_removed(account: &T::AccountId, space_id: SpaceId) {
+        // Envelopes of a deleted space are removed anyway, and there is nothing to rotate.
+        if Self::is_space_pending_cleanup(space_id) {
+            return
+        }
+
+        if ContentKeyEnvelopes::<T>::take(space_id, account).is_some() {
+            ContentKeyRotationRequired::<T>::insert(space_id, true);
+            Self::deposit_event(Event::ContentKeyRotationRequired { space_id });
+        }
+    }
+
     fn ensure_user_has_space_permission_with_load_space(
         user: User<T::AccountId>,
         space_id: SpaceId,
         permission: SpacePermission,
         error: DispatchError,
     ) -> DispatchResult {
+        let ctx = Self::load_space_permissions_context(&user, space_id)?;
+        Self::ensure_user_has_space_permission(user, ctx, permission, error)
+    }
+
+    fn load_space_permissions_context(
+        user: &User<T::AccountId>,
+        space_id: SpaceId,
+    ) -> Result<SpacePermissionsContext, DispatchError> {
         let space = T::SpacePermissionsProvider::space_permissions_info(space_id)?;
 
         let mut is_owner = false;
         let mut is_follower = false;
 
-        match &user {
+        match user {
             User::Account(account) => {
-                is_owner = *account == space.owner;
+                // An owner may own a space through another space, so let spaces resolve it:
+                is_owner = *account == space.owner ||
+                    T::SpacePermissionsProvider::ensure_space_owner(space_id, account).is_ok();
 
                 // No need to check if a user is follower, if they already are an owner:
                 is_follower =
@@ -52,17 +204,12 @@ impl<T: Config> Pallet<T> {
             User::Space(_) => (/* Not implemented yet. */),
         }
 
-        Self::ensure_user_has_space_permission(
-            user,
-            SpacePermissionsContext {
-                space_id,
-                is_space_owner: is_owner,
-                is_space_follower: is_follower,
-                space_perms: space.permissions,
-            },
-            permission,
-            error,
-        )
+        Ok(SpacePermissionsContext {
+            space_id,
+            is_space_owner: is_owner,
+            is_space_follower: is_follower,
+            space_perms: space.permissions,
+        })
     }
 
     fn ensure_user_has_space_permission(
@@ -86,28 +233,686 @@ impl<T: Config> Pallet<T> {
         permission: SpacePermission,
         error: DispatchError,
     ) -> DispatchResult {
-        let role_ids = Self::role_ids_by_user_in_space(user, space_id);
+        let role_ids = Self::role_ids_by_user_in_space(&user, space_id);
 
         for role_id in role_ids {
             if let Some(role) = Self::role_by_id(role_id) {
-                if role.disabled {
+                if role.is_active() &&
+                    role.permissions.contains(&permission) &&
+                    Self::is_role_grant_active(role_id, &user)
+                {
+                    return Ok(())
+                }
+            }
+        }
+
+        Err(error)
+    }
+
+    /// Get ids of enabled and not expired roles of a given user within a given space,
+    /// that include a given permission.
+    pub fn active_role_ids_with_permission(
+        user: User<T::AccountId>,
+        space_id: SpaceId,
+        permission: SpacePermission,
+    ) -> Vec<RoleId> {
+        Self::role_ids_by_user_in_space(&user, space_id)
+            .into_iter()
+            .filter(|role_id| {
+                Self::role_by_id(role_id).map_or(false, |role| {
+                    role.is_active() &&
+                        role.permissions.contains(&permission) &&
+                        Self::is_role_grant_active(*role_id, &user)
+                })
+            })
+            .collect()
+    }
+
+    /// Explain whether an account has a given permission in a given space, by listing
+    /// every source of permissions that is checked, including roles granted to the account.
+    /// A privacy of a space and a moderation are checked first, the same way as
+    /// `ensure_account_has_space_permission` in spaces pallet does.
+    /// Nothing is changed in the storage.
+    pub fn trace_account_space_permission(
+        account: T::AccountId,
+        space_id: SpaceId,
+        permission: SpacePermission,
+    ) -> Result<PermissionTrace<RoleId>, DispatchError> {
+        let user = User::Account(account.clone());
+        let ctx = Self::load_space_permissions_context(&user, space_id)?;
+        let mut steps = Vec::new();
+
+        let access_checks = [
+            (
+                PermissionSource::SpacePrivacy,
+                T::SpacePermissionsProvider::is_space_accessible_by(space_id, &account),
+            ),
+            (
+                PermissionSource::Moderation,
+                T::IsAccountBlocked::is_allowed_account(account, space_id),
+            ),
+        ];
+        for (source, passed) in access_checks {
+            let outcome = if passed {
+                PermissionCheckOutcome::Passed
+            } else {
+                PermissionCheckOutcome::Forbidden
+            };
+            steps.push(PermissionCheckStep { source, outcome });
+
+            if !passed {
+                return Ok(PermissionTrace { permission, granted: false, steps })
+            }
+        }
+
+        let (resolved, space_steps) = Permissions::<T>::trace_space_permission(ctx, permission);
+        steps.extend(space_steps);
+
+        if let Some(granted) = resolved {
+            return Ok(PermissionTrace { permission, granted, steps })
+        }
+
+        let mut granted = false;
+        for role_id in Self::role_ids_by_user_in_space(&user, space_id) {
+            if let Some(role) = Self::role_by_id(role_id) {
+                let outcome = if !role.permissions.contains(&permission) {
+                    PermissionCheckOutcome::NotIncluded
+                } else if role.disabled {
+                    PermissionCheckOutcome::RoleDisabled
+                } else if !role.is_active() {
+                    PermissionCheckOutcome::RoleExpired
+                } else if !Self::is_role_grant_active(role_id, &user) {
+                    PermissionCheckOutcome::RoleGrantExpired
+                } else {
+                    PermissionCheckOutcome::Granted
+                };
+                steps
+                    .push(PermissionCheckStep { source: PermissionSource::Role(role_id), outcome });
+
+                if outcome == PermissionCheckOutcome::Granted {
+                    granted = true;
+                    break
+                }
+            }
+        }
+
+        Ok(PermissionTrace { permission, granted, steps })
+    }
+
+    /// Check whether an account meets a condition of a role within a given space.
+    pub fn is_role_condition_met(
+        account: &T::AccountId,
+        space_id: SpaceId,
+        condition: &RoleConditionOf<T>,
+    ) -> bool {
+        match condition {
+            RoleCondition::MinBalance(min_balance) =>
+                T::Currency::free_balance(account) >= *min_balance,
+            RoleCondition::OwnsDomain => T::DomainsProvider::owns_any_domain(account),
+            RoleCondition::FollowsSpaceFor(blocks) => {
+                let followed_at = T::SpaceFollows::space_followed_at(account.clone(), space_id);
+                let now = <system::Pallet<T>>::block_number();
+                followed_at.map_or(false, |followed_at| followed_at.saturating_add(*blocks) <= now)
+            },
+        }
+    }
+
+    /// A payload that must be signed to issue a given invite voucher on this chain.
+    pub fn invite_voucher_signing_payload(voucher: &InviteVoucherOf<T>) -> Vec<u8> {
+        let genesis_hash = <system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+        voucher.signing_payload(&genesis_hash)
+    }
+
+    /// Remove a request of a given account to join a given space, if there is one,
+    /// and unreserve its' deposit.
+    pub(crate) fn remove_join_request(
+        space_id: SpaceId,
+        requester: &T::AccountId,
+    ) -> Option<JoinRequestOf<T>> {
+        let join_request = JoinRequests::<T>::take(space_id, requester)?;
+        T::Currency::unreserve(requester, join_request.deposit);
+        Some(join_request)
+    }
+
+    /// Check whether a given role granted to a given user has not expired yet.
+    pub fn is_role_grant_active(role_id: RoleId, user: &User<T::AccountId>) -> bool {
+        match Self::role_grant_expires_at(role_id, user) {
+            Some(expires_at) => expires_at > <system::Pallet<T>>::block_number(),
+            None => true,
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Delete roles, that expired at or before a given block, while there is enough weight left.
+    ///
+    /// An expired role is revoked from at most `MaxUsersToProcessPerDeleteRole` users per step,
+    /// so a role with many users is deleted in several steps.
+    pub(crate) fn clean_expired_roles(now: T::BlockNumber, meter: &mut WeightMeter) {
+        let db_weight = T::DbWeight::get();
+        if !meter.try_consume(db_weight.reads_writes(1, 1)) {
+            return
+        }
+
+        let max_users = T::MaxUsersToProcessPerDeleteRole::get() as u32;
+
+        let next_block = process_block_queue(
+            Self::next_role_expiration_block(),
+            now,
+            meter,
+            db_weight.reads(1),
+            |block| RoleIdsExpiringAt::<T>::iter_key_prefix(block).next(),
+            |role_id| {
+                let users_count =
+                    UsersByRoleId::<T>::decode_len(role_id).unwrap_or_default() as u32;
+                T::WeightInfo::delete_expired_role(users_count.min(max_users))
+            },
+            |block, role_id| {
+                if Self::delete_expired_role_step(role_id, max_users) {
+                    RoleIdsExpiringAt::<T>::remove(block, role_id);
+                }
+            },
+        );
+
+        NextRoleExpirationBlock::<T>::put(next_block);
+    }
+
+    /// Revoke an expired role from at most `max_users` users,
+    /// and delete it, if it has no users left. Returns `true`, if the role is deleted.
+    pub(crate) fn delete_expired_role_step(role_id: RoleId, max_users: u32) -> bool {
+        let role = match Self::role_by_id(role_id) {
+            Some(role) => role,
+            // A role was already deleted, so there is nothing to clean up.
+            None => return true,
+        };
+
+        if !Self::remove_role_step(&role, max_users) {
+            return false
+        }
+
+        Self::deposit_event(Event::RoleExpired { space_id: role.space_id, role_id });
+        true
+    }
+
+    /// Revoke a role from at most `max_users` users, and remove it from the storage,
+    /// if it has no users left. Returns `true`, if the role is removed.
+    fn remove_role_step(role: &Role<T>, max_users: u32) -> bool {
+        if role.revoke_from_users_in_batch(max_users) {
+            return false
+        }
+
+        let role_id = role.id;
+        RoleIdsBySpaceId::<T>::mutate(role.space_id, |role_ids| {
+            if let Some(role_idx) = role_ids.iter().position(|id| *id == role_id) {
+                role_ids.swap_remove(role_idx);
+            }
+        });
+        RoleById::<T>::remove(role_id);
+        UsersByRoleId::<T>::remove(role_id);
+        Self::update_role_rank(role.space_id, role_id, 0);
+        RoleConditionById::<T>::remove(role_id);
+        T::OnRoleRemoved::on_role_removed(role_id);
+        true
+    }
+
+    /// Clean up roles and content keys of deleted spaces, while there is enough weight left.
+    ///
+    /// Roles of a deleted space are removed one by one, and each of them is revoked from
+    /// at most `MaxUsersToProcessPerDeleteRole` users per step. Content key envelopes and
+    /// join requests are removed in batches of the same size after all the roles are gone.
+    pub(crate) fn clean_deleted_spaces(meter: &mut WeightMeter) {
+        let db_weight = T::DbWeight::get();
+        if !meter.try_consume(db_weight.reads(1)) {
+            return
+        }
+
+        let max_users = T::MaxUsersToProcessPerDeleteRole::get() as u32;
+
+        while let Some(space_id) = SpacesPendingCleanup::<T>::iter_keys().next() {
+            if let Some(role_id) = Self::role_ids_by_space_id(space_id).last().copied() {
+                let users_count =
+                    UsersByRoleId::<T>::decode_len(role_id).unwrap_or_default() as u32;
+                let step_weight = db_weight
+                    .reads(1)
+                    .saturating_add(T::WeightInfo::delete_expired_role(users_count.min(max_users)));
+                if !meter.try_consume(step_weight) {
+                    return
+                }
+
+                match Self::role_by_id(role_id) {
+                    Some(role) =>
+                        if Self::remove_role_step(&role, max_users) {
+                            if let Some(expires_at) = role.expires_at {
+                                RoleIdsExpiringAt::<T>::remove(expires_at, role_id);
+                            }
+                        },
+                    None => RoleIdsBySpaceId::<T>::mutate(space_id, |role_ids| {
+                        role_ids.pop();
+                    }),
+                }
+                continue
+            }
+
+            // Removing a join request also unreserves its' deposit.
+            let batch_weight = db_weight.reads_writes(2 * max_users as u64, 2 * max_users as u64);
+            if !meter.try_consume(db_weight.reads(2).saturating_add(batch_weight)) {
+                return
+            }
+
+            let envelope_keys: Vec<_> = ContentKeyEnvelopes::<T>::iter_key_prefix(space_id)
+                .take(max_users as usize)
+                .collect();
+            if !envelope_keys.is_empty() {
+                for account in envelope_keys {
+                    ContentKeyEnvelopes::<T>::remove(space_id, account);
+                }
+                continue
+            }
+
+            let join_request_keys: Vec<_> =
+                JoinRequests::<T>::iter_key_prefix(space_id).take(max_users as usize).collect();
+            if !join_request_keys.is_empty() {
+                for account in join_request_keys {
+                    Self::remove_join_request(space_id, &account);
+                }
+                continue
+            }
+
+            if !meter.try_consume(db_weight.writes(4)) {
+                return
+            }
+
+            RoleIdsBySpaceId::<T>::remove(space_id);
+            ContentKeyVersionBySpaceId::<T>::remove(space_id);
+            ContentKeyRotationRequired::<T>::remove(space_id);
+            SpacesPendingCleanup::<T>::remove(space_id);
+        }
+    }
+
+    /// Remove content keys of users of disabled roles, that were disabled with too many users
+    /// to check at once, while there is enough weight left.
+    pub(crate) fn clean_disabled_roles(meter: &mut WeightMeter) {
+        if !meter.try_consume(T::DbWeight::get().reads(1)) {
+            return
+        }
+
+        let max_users = T::MaxUsersToProcessPerDeleteRole::get() as u32;
+
+        while let Some((role_id, users_left)) = DisabledRolesPendingCleanup::<T>::iter().next() {
+            let step_weight = T::WeightInfo::clean_disabled_role(users_left.min(max_users));
+            if !meter.try_consume(step_weight) {
+                break
+            }
+
+            // A deleted role is revoked from its users, and users of a re-enabled role
+            // are space members again, so there is nothing left to check.
+            let role = match Self::role_by_id(role_id).filter(|role| role.disabled) {
+                Some(role) => role,
+                None => {
+                    DisabledRolesPendingCleanup::<T>::remove(role_id);
                     continue
+                },
+            };
+
+            match role.remove_content_keys_of_non_members(users_left, max_users) {
+                Some(users_left) => DisabledRolesPendingCleanup::<T>::insert(role_id, users_left),
+                None => DisabledRolesPendingCleanup::<T>::remove(role_id),
+            }
+        }
+    }
+
+    /// Revoke roles, that were deleted via `delete_role` with too many users to process at once,
+    /// from their remaining users, while there is enough weight left.
+    pub(crate) fn clean_deleted_roles(meter: &mut WeightMeter) {
+        if !meter.try_consume(T::DbWeight::get().reads(1)) {
+            return
+        }
+
+        let max_users = T::MaxUsersToProcessPerDeleteRole::get() as u32;
+
+        while let Some((role_id, role)) = RolesPendingDeletion::<T>::iter().next() {
+            let users_count = UsersByRoleId::<T>::decode_len(role_id).unwrap_or_default() as u32;
+            if !meter.try_consume(T::WeightInfo::clean_deleted_role(users_count.min(max_users))) {
+                break
+            }
+
+            if !role.revoke_from_users_in_batch(max_users) {
+                UsersByRoleId::<T>::remove(role_id);
+                RolesPendingDeletion::<T>::remove(role_id);
+            }
+        }
+    }
+
+    /// Revoke role grants, that expired at or before a given block,
+    /// while there is enough weight left.
+    pub(crate) fn clean_expired_role_grants(now: T::BlockNumber, meter: &mut WeightMeter) {
+        let db_weight = T::DbWeight::get();
+        if !meter.try_consume(db_weight.reads_writes(1, 1)) {
+            return
+        }
+
+        let next_block = process_block_queue(
+            Self::next_role_grant_expiration_block(),
+            now,
+            meter,
+            db_weight.reads(1),
+            |block| RoleGrantsExpiringAt::<T>::iter_key_prefix(block).next(),
+            |_| T::WeightInfo::revoke_expired_role_grant(),
+            |block, (role_id, user)| {
+                RoleGrantsExpiringAt::<T>::remove(block, (role_id, user.clone()));
+
+                // The grant could be revoked or extended after it was indexed.
+                if Self::role_grant_expires_at(role_id, &user) != Some(block) {
+                    return
+                }
+
+                if let Some(role) = Self::role_by_id(role_id) {
+                    role.revoke_from_users(vec![user.clone()]);
+                    Self::deposit_event(Event::RoleGrantExpired { role_id, user });
                 }
+            },
+        );
+
+        NextRoleGrantExpirationBlock::<T>::put(next_block);
+    }
+
+    /// Remove hashes of invite vouchers, that expired at or before a given block,
+    /// while there is enough weight left.
+    pub(crate) fn clean_used_invite_vouchers(now: T::BlockNumber, meter: &mut WeightMeter) {
+        let db_weight = T::DbWeight::get();
+        if !meter.try_consume(db_weight.reads_writes(1, 1)) {
+            return
+        }
+
+        let next_block = process_block_queue(
+            Self::next_invite_voucher_expiration_block(),
+            now,
+            meter,
+            db_weight.reads(1),
+            |block| UsedInviteVouchers::<T>::iter_key_prefix(block).next(),
+            |_| db_weight.reads_writes(1, 1),
+            |block, voucher_hash| UsedInviteVouchers::<T>::remove(block, voucher_hash),
+        );
+
+        NextInviteVoucherExpirationBlock::<T>::put(next_block);
+    }
+
+    /// Remove role proposals, that expired at or before a given block, and unreserve
+    /// their deposits, while there is enough weight left.
+    pub(crate) fn clean_expired_role_proposals(now: T::BlockNumber, meter: &mut WeightMeter) {
+        let db_weight = T::DbWeight::get();
+        if !meter.try_consume(db_weight.reads_writes(1, 1)) {
+            return
+        }
+
+        let next_block = process_block_queue(
+            Self::next_role_proposal_expiration_block(),
+            now,
+            meter,
+            db_weight.reads(1),
+            |block| RoleProposalsExpiringAt::<T>::iter_key_prefix(block).next(),
+            // Read a proposal and the depositor's account, remove the proposal with its'
+            // indexes, and unreserve its' deposit.
+            |_| db_weight.reads_writes(2, 4),
+            |block, proposal_id| {
+                RoleProposalsExpiringAt::<T>::remove(block, proposal_id);
+
+                if let Some(proposal) = Self::role_proposal_by_id(proposal_id) {
+                    Self::remove_role_proposal(&proposal);
+                    Self::deposit_event(Event::RoleProposalExpired { proposal_id });
+                }
+            },
+        );
+
+        NextRoleProposalExpirationBlock::<T>::put(next_block);
+    }
+}
 
-                let mut is_expired = false;
+impl<T: Config> Pallet<T> {
+    /// Get `RoleProposal` by id from the storage or return `RoleProposalNotFound` error.
+    pub fn require_role_proposal(
+        proposal_id: RoleProposalId,
+    ) -> Result<RoleProposal<T>, DispatchError> {
+        Ok(Self::role_proposal_by_id(proposal_id).ok_or(Error::<T>::RoleProposalNotFound)?)
+    }
+
+    /// Get the number of role managers, that must approve sensitive role changes in a space.
+    pub fn required_role_approvals(space_id: SpaceId) -> u32 {
+        T::SpaceSettings::space_setting::<RoleApprovalsSetting<T>>(space_id)
+    }
+
+    /// Whether a given change of a role, made by a given account,
+    /// must be approved by other role managers before it is applied.
+    pub(crate) fn requires_role_approvals(
+        account: &T::AccountId,
+        role: &Role<T>,
+        change: &RoleChangeOf<T>,
+    ) -> bool {
+        let gives_manage_roles = role.permissions.contains(&SpacePermission::ManageRoles);
+
+        let is_sensitive_change = match change {
+            RoleChange::GrantRole { .. } | RoleChange::GrantRoleByCondition { .. } =>
+                gives_manage_roles,
+            // A condition lets anyone claim the role, so it is the same as granting it.
+            RoleChange::SetRoleCondition { condition } => condition.is_some() && gives_manage_roles,
+            RoleChange::UpdateRole { update } => {
+                let disables = update.disabled == Some(true) && !role.disabled;
+                let changes_permissions =
+                    update.permissions.as_ref().map_or(false, |permissions| {
+                        let adds_manage_roles = !gives_manage_roles &&
+                            permissions.contains(&SpacePermission::ManageRoles);
+                        let takes_permissions =
+                            !role.permissions.difference(permissions).is_empty();
+                        !permissions.is_empty() && (adds_manage_roles || takes_permissions)
+                    });
+                disables || changes_permissions
+            },
+            RoleChange::DeleteRole |
+            RoleChange::RevokeRole { .. } |
+            RoleChange::RevokeRoleByCondition { .. } => true,
+        };
+
+        is_sensitive_change &&
+            Self::required_role_approvals(role.space_id) > 1 &&
+            T::SpacePermissionsProvider::ensure_space_owner(role.space_id, account).is_err()
+    }
+
+    /// Create a proposal to change a role, that is approved by its' proposer,
+    /// if `approved_by_proposer` is true.
+    pub(crate) fn create_role_proposal(
+        proposer: T::AccountId,
+        depositor: T::AccountId,
+        role_id: RoleId,
+        change: RoleChangeOf<T>,
+        approved_by_proposer: bool,
+    ) -> DispatchResult {
+        if let RoleChange::GrantRole { users, .. } | RoleChange::RevokeRole { users } = &change {
+            ensure!(
+                users.len() <= T::MaxUsersToProcessPerDeleteRole::get().into(),
+                Error::<T>::TooManyUsersInRoleProposal
+            );
+        }
+
+        let now = <system::Pallet<T>>::block_number();
+        let change_hash = T::Hashing::hash_of(&change);
+        if let Some(existing) = RoleProposalIdByChange::<T>::get(role_id, change_hash)
+            .and_then(Self::role_proposal_by_id)
+        {
+            // An expired proposal, that is not removed in `on_idle` yet, is replaced.
+            ensure!(existing.expires_at <= now, Error::<T>::RoleProposalAlreadyExists);
+            Self::remove_role_proposal(&existing);
+        }
+
+        let deposit = T::RoleProposalDeposit::get();
+        T::Currency::reserve(&depositor, deposit)
+            .map_err(|_| Error::<T>::InsufficientBalanceToReserveDeposit)?;
+
+        let proposal_id = Self::next_role_proposal_id();
+        let expires_at = now.saturating_add(T::RoleProposalLifetime::get());
+
+        let proposal = RoleProposal::<T> {
+            id: proposal_id,
+            proposer: proposer.clone(),
+            role_id,
+            change,
+            approvals: if approved_by_proposer { vec![proposer.clone()] } else { Vec::new() },
+            expires_at,
+            depositor,
+            deposit,
+        };
+
+        RoleProposalById::<T>::insert(proposal_id, proposal);
+        RoleProposalIdByChange::<T>::insert(role_id, change_hash, proposal_id);
+        RoleProposalsExpiringAt::<T>::insert(expires_at, proposal_id, ());
+        NextRoleProposalId::<T>::mutate(|n| *n = n.saturating_add(1));
+
+        Self::deposit_event(Event::RoleProposalCreated { account: proposer, role_id, proposal_id });
+        Ok(())
+    }
+
+    /// Remove a given role proposal together with its' indexes, and unreserve its' deposit.
+    pub(crate) fn remove_role_proposal(proposal: &RoleProposal<T>) {
+        RoleProposalById::<T>::remove(proposal.id);
+        RoleProposalIdByChange::<T>::remove(
+            proposal.role_id,
+            T::Hashing::hash_of(&proposal.change),
+        );
+        RoleProposalsExpiringAt::<T>::remove(proposal.expires_at, proposal.id);
+        T::Currency::unreserve(&proposal.depositor, proposal.deposit);
+    }
+
+    /// Apply a given change to a role, that has already been checked or approved.
+    pub(crate) fn apply_role_change(
+        account: T::AccountId,
+        role: Role<T>,
+        change: RoleChangeOf<T>,
+    ) -> DispatchResult {
+        let role_id = role.id;
+
+        match change {
+            RoleChange::DeleteRole => {
+                RoleIdsBySpaceId::<T>::mutate(role.space_id, |role_ids| {
+                    if let Some(role_idx) = role_ids.iter().position(|id| *id == role_id) {
+                        role_ids.swap_remove(role_idx);
+                    }
+                });
+
+                RoleById::<T>::remove(role_id);
+                Self::update_role_rank(role.space_id, role_id, 0);
+                RoleConditionById::<T>::remove(role_id);
                 if let Some(expires_at) = role.expires_at {
-                    if expires_at <= <system::Pallet<T>>::block_number() {
-                        is_expired = true;
+                    RoleIdsExpiringAt::<T>::remove(expires_at, role_id);
+                }
+                T::OnRoleRemoved::on_role_removed(role_id);
+
+                let max_users = T::MaxUsersToProcessPerDeleteRole::get().into();
+                if role.revoke_from_users_in_batch(max_users) {
+                    RolesPendingDeletion::<T>::insert(role_id, role);
+                } else {
+                    UsersByRoleId::<T>::remove(role_id);
+                }
+
+                Self::deposit_event(Event::RoleDeleted { account, role_id });
+            },
+            RoleChange::GrantRole { users, expires_at } => {
+                for user in users.iter() {
+                    let is_granted = Self::role_ids_by_user_in_space(user.clone(), role.space_id)
+                        .contains(&role_id);
+                    // Re-granting a role changes when it expires, e.g. makes a temporary grant
+                    // permanent, so it must be visible to users.
+                    if is_granted && Self::role_grant_expires_at(role_id, user) != expires_at {
+                        Self::deposit_event(Event::RoleGrantExpirationUpdated {
+                            account: account.clone(),
+                            role_id,
+                            user: user.clone(),
+                            expires_at,
+                        });
                     }
+
+                    role.grant_to_user(user.clone());
+                    role.set_grant_expiration(user.clone(), expires_at);
                 }
 
-                if !is_expired && role.permissions.contains(&permission) {
-                    return Ok(())
+                Self::deposit_event(Event::RoleGranted { account, role_id, users });
+            },
+            RoleChange::RevokeRole { users } => {
+                role.revoke_from_users(users.clone());
+
+                Self::deposit_event(Event::RoleRevoked { account, role_id, users });
+            },
+            RoleChange::UpdateRole { update } => {
+                let mut role = role;
+                let was_disabled = role.disabled;
+                if role.apply_update(update)? {
+                    // Users, that are no longer space members, must not keep their content keys.
+                    if role.disabled && !was_disabled {
+                        let users_count =
+                            UsersByRoleId::<T>::decode_len(role_id).unwrap_or_default() as u32;
+                        let max_users = T::MaxUsersToProcessPerDeleteRole::get().into();
+                        if let Some(users_left) =
+                            role.remove_content_keys_of_non_members(users_count, max_users)
+                        {
+                            DisabledRolesPendingCleanup::<T>::insert(role_id, users_left);
+                        }
+                    }
+
+                    RoleById::<T>::insert(role_id, role);
+                    Self::deposit_event(Event::RoleUpdated { account, role_id });
                 }
-            }
+            },
+            RoleChange::SetRoleCondition { condition } => {
+                RoleConditionById::<T>::set(role_id, condition);
+
+                Self::deposit_event(Event::RoleConditionUpdated { account, role_id });
+            },
+            RoleChange::GrantRoleByCondition { account: grantee } => {
+                // The condition is checked again, as it may have changed since the proposal.
+                ensure!(
+                    Self::conditional_role_refresh(&role, &grantee)?,
+                    Error::<T>::NothingToRefresh
+                );
+
+                let user = User::Account(grantee);
+                role.grant_to_user(user.clone());
+                ConditionalRoleGrants::<T>::insert(role_id, &user, ());
+
+                Self::deposit_event(Event::RoleGranted { account, role_id, users: vec![user] });
+            },
+            RoleChange::RevokeRoleByCondition { account: revokee } => {
+                ensure!(
+                    !Self::conditional_role_refresh(&role, &revokee)?,
+                    Error::<T>::NothingToRefresh
+                );
+
+                let user = User::Account(revokee);
+                role.revoke_from_users(vec![user.clone()]);
+
+                Self::deposit_event(Event::RoleRevoked { account, role_id, users: vec![user] });
+            },
         }
+        Ok(())
+    }
 
-        Err(error)
+    /// Check whether a conditional role should be granted to a given account (`true`),
+    /// because it meets the role's condition, or revoked from it (`false`),
+    /// because it was granted by the condition, that the account does not meet anymore.
+    pub(crate) fn conditional_role_refresh(
+        role: &Role<T>,
+        account: &T::AccountId,
+    ) -> Result<bool, DispatchError> {
+        let condition = Self::role_condition(role.id).ok_or(Error::<T>::RoleHasNoCondition)?;
+
+        let user = User::Account(account.clone());
+        let has_role = Self::role_ids_by_user_in_space(&user, role.space_id).contains(&role.id);
+        let granted_by_condition = ConditionalRoleGrants::<T>::contains_key(role.id, &user);
+        let meets_condition = Self::is_role_condition_met(account, role.space_id, &condition) &&
+            T::IsAccountBlocked::is_allowed_account(account.clone(), role.space_id);
+
+        match (has_role, meets_condition) {
+            (false, true) => Ok(true),
+            (true, false) if granted_by_condition => Ok(false),
+            _ => Err(Error::<T>::NothingToRefresh.into()),
+        }
     }
 }
 
@@ -117,7 +922,7 @@ impl<T: Config> Role<T> {
         space_id: SpaceId,
         time_to_live: Option<T::BlockNumber>,
         content: Content,
-        permissions: BTreeSet<SpacePermission>,
+        permissions: SpacePermissionSet,
     ) -> Result<Self, DispatchError> {
         let role_id = Pallet::<T>::next_role_id();
 
@@ -139,6 +944,15 @@ impl<T: Config> Role<T> {
         Ok(new_role)
     }
 
+    /// Check that this role is neither disabled nor expired.
+    pub fn is_active(&self) -> bool {
+        let is_expired = self
+            .expires_at
+            .map_or(false, |expires_at| expires_at <= <system::Pallet<T>>::block_number());
+
+        !self.disabled && !is_expired
+    }
+
     pub fn set_disabled(&mut self, disable: bool) -> DispatchResult {
         if self.disabled && disable {
             return Err(Error::<T>::RoleAlreadyDisabled.into())
@@ -151,10 +965,130 @@ impl<T: Config> Role<T> {
         Ok(())
     }
 
+    /// Apply a given update to this role and check that it is valid.
+    /// Return whether anything has been changed.
+    pub fn apply_update(&mut self, update: RoleUpdate) -> Result<bool, DispatchError> {
+        let mut is_update_applied = false;
+
+        if let Some(disabled) = update.disabled {
+            if disabled != self.disabled {
+                self.set_disabled(disabled)?;
+                is_update_applied = true;
+            }
+        }
+
+        if let Some(content) = update.content {
+            if content != self.content {
+                ensure_content_is_valid(content.clone())?;
+                ensure!(
+                    T::IsContentBlocked::is_allowed_content(content.clone(), self.space_id),
+                    ModerationError::ContentIsBlocked
+                );
+
+                self.content = content;
+                is_update_applied = true;
+            }
+        }
+
+        if let Some(permissions) = update.permissions {
+            if !permissions.is_empty() && permissions != self.permissions {
+                Permissions::<T>::ensure_permissions_are_declared(&permissions)?;
+
+                self.permissions = permissions;
+                is_update_applied = true;
+            }
+        }
+
+        Ok(is_update_applied)
+    }
+
+    /// Grant this role to a given user, if they don't have it yet.
+    /// The grant is kept, even if the user stops meeting a condition of this role.
+    pub fn grant_to_user(&self, user: User<T::AccountId>) {
+        <ConditionalRoleGrants<T>>::remove(self.id, &user);
+        if !Pallet::<T>::users_by_role_id(self.id).contains(&user) {
+            <UsersByRoleId<T>>::mutate(self.id, |users| {
+                users.push(user.clone());
+            });
+        }
+        if !Pallet::<T>::role_ids_by_user_in_space(user.clone(), self.space_id).contains(&self.id) {
+            <RoleIdsByUserInSpace<T>>::mutate(user, self.space_id, |roles| {
+                roles.push(self.id);
+            })
+        }
+    }
+
+    /// Set a block at which this role granted to a given user expires,
+    /// or make the grant permanent, if `expires_at` is `None`.
+    pub fn set_grant_expiration(
+        &self,
+        user: User<T::AccountId>,
+        expires_at: Option<T::BlockNumber>,
+    ) {
+        if let Some(old_expires_at) = <RoleGrantExpiresAt<T>>::take(self.id, &user) {
+            <RoleGrantsExpiringAt<T>>::remove(old_expires_at, (self.id, user.clone()));
+        }
+
+        if let Some(expires_at) = expires_at {
+            <RoleGrantExpiresAt<T>>::insert(self.id, &user, expires_at);
+            <RoleGrantsExpiringAt<T>>::insert(expires_at, (self.id, user), ());
+        }
+    }
+
+    /// Revoke this role from at most `max_users` of its users.
+    /// Returns `true`, if the role still has more users to be revoked from.
+    pub(crate) fn revoke_from_users_in_batch(&self, max_users: u32) -> bool {
+        let users: Vec<_> = Pallet::<T>::users_by_role_id(self.id)
+            .into_iter()
+            .take(max_users as usize)
+            .collect();
+        let has_more_users = users.len() as u32 == max_users &&
+            UsersByRoleId::<T>::decode_len(self.id).unwrap_or_default() as u32 > max_users;
+
+        if !users.is_empty() {
+            self.revoke_from_users(users);
+        }
+
+        has_more_users
+    }
+
+    /// Remove content keys of users of this role, that are no longer space members,
+    /// checking at most `max_users` of the first `users_left` users of this role,
+    /// from the last one. Returns the number of users, that are left to be checked, if any.
+    ///
+    /// Users are checked from the end of the list, because revoking a role moves its' last
+    /// user to the position of a revoked one, so that no unchecked user is skipped.
+    pub(crate) fn remove_content_keys_of_non_members(
+        &self,
+        users_left: u32,
+        max_users: u32,
+    ) -> Option<u32> {
+        let users = Pallet::<T>::users_by_role_id(self.id);
+        let end = (users_left as usize).min(users.len());
+        let start = end.saturating_sub(max_users as usize);
+
+        for user in users[start..end].iter() {
+            if let User::Account(account) = user {
+                if !Pallet::<T>::is_space_member(account, self.space_id) {
+                    Pallet::<T>::on_member_removed(account, self.space_id);
+                }
+            }
+        }
+
+        if start > 0 {
+            Some(start as u32)
+        } else {
+            None
+        }
+    }
+
     pub fn revoke_from_users(&self, users: Vec<User<T::AccountId>>) {
         let mut users_by_role = <UsersByRoleId<T>>::take(self.id);
 
         for user in users.iter() {
+            self.set_grant_expiration(user.clone(), None);
+            <ConditionalRoleGrants<T>>::remove(self.id, user);
+
             let role_idx_by_user_opt = Pallet::<T>::role_ids_by_user_in_space(&user, self.space_id)
                 .iter()
                 .position(|x| *x == self.id);
@@ -168,6 +1102,12 @@ impl<T: Config> Role<T> {
             if let Some(user_idx) = user_idx_by_role_opt {
                 users_by_role.swap_remove(user_idx);
             }
+
+            if let User::Account(account) = user {
+                if !Pallet::<T>::is_space_member(account, self.space_id) {
+                    Pallet::<T>::on_member_removed(account, self.space_id);
+                }
+            }
         }
         <UsersByRoleId<T>>::insert(self.id, users_by_role);
     }
@@ -185,3 +1125,38 @@ impl<T: Config> PermissionChecker for Pallet<T> {
         Self::ensure_user_has_space_permission(user, ctx, permission, error)
     }
 }
+
+impl<T: Config> NamespacedPermissionsRegistry for Pallet<T> {
+    fn is_permission_declared(permission: &NamespacedPermission) -> bool {
+        *permission == REJECT_JOIN_REQUESTS
+    }
+}
+
+impl<T: Config> OnSpaceDeleted<T::AccountId> for Pallet<T> {
+    /// Roles and content keys of a deleted space are cleaned up later in `on_idle`,
+    /// because a space can have any number of roles and members.
+    fn on_space_deleted(_owner: &T::AccountId, space_id: SpaceId) {
+        SpacesPendingCleanup::<T>::insert(space_id, true);
+    }
+}
+
+impl<T: Config> SpaceMembersProvider<T::AccountId> for Pallet<T> {
+    /// An account is a space member, if it has at least one active role in this space.
+    fn is_space_member(account: &T::AccountId, space_id: SpaceId) -> bool {
+        let user = User::Account(account.clone());
+        Self::role_ids_by_user_in_space(&user, space_id).into_iter().any(|role_id| {
+            Self::role_by_id(role_id).map_or(false, |role| role.is_active()) &&
+                Self::is_role_grant_active(role_id, &user)
+        })
+    }
+}
+
+impl<T: Config> SpaceSettingsHandler for Pallet<T> {
+    fn validate_space_setting(key: &SpaceSettingKey, raw_value: &[u8]) -> Option<DispatchResult> {
+        if *key == RoleApprovalsSetting::<T>::KEY {
+            return Some(RoleApprovalsSetting::<T>::decode_and_validate(raw_value))
+        }
+
+        None
+    }
+}
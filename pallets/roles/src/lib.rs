@@ -11,28 +11,47 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode};
-use frame_support::{dispatch::DispatchResult, ensure, traits::Get};
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::{ConstU32, Currency, Get, ReservableCurrency},
+    BoundedVec,
+};
 use frame_system::{self as system, ensure_signed};
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
-use sp_std::{collections::btree_set::BTreeSet, prelude::*};
+use sp_runtime::{
+    traits::{Hash, IdentifyAccount, Verify, Zero},
+    RuntimeDebug,
+};
+use sp_std::{collections::btree_set::BTreeSet, marker::PhantomData, prelude::*, vec};
 
 use pallet_permissions::{
-    Pallet as Permissions, PermissionChecker, SpacePermission, SpacePermissionSet,
+    NamespacedPermission, NamespacedPermissionsRegistry, Pallet as Permissions, PermissionChecker,
+    PermissionNamespace, SpacePermission, SpacePermissionSet,
 };
 use subsocial_support::{
+    cleanup::{process_block_queue, WeightMeter},
     convert_users_vec_to_btree_set, ensure_content_is_valid, new_who_and_when,
-    traits::{IsAccountBlocked, IsContentBlocked, SpaceFollowsProvider, SpacePermissionsProvider},
-    Content, ModerationError, SpaceId, User, WhoAndWhenOf,
+    space_owner_walk_weight,
+    traits::{
+        DomainsProvider, IsAccountBlocked, IsContentBlocked, OnSpaceDeleted, SpaceFollowsProvider,
+        SpaceMembersProvider, SpacePermissionsProvider, SpaceSetting, SpaceSettingsHandler,
+        SpaceSettingsProvider,
+    },
+    Content, ModerationError, SpaceId, SpaceSettingKey, User, WhoAndWhenOf,
 };
 
 pub use pallet::*;
 pub mod functions;
+pub mod migration;
 
 pub mod types;
 pub use types::*;
 // pub mod rpc;
 
+/// The log target of this pallet.
+pub const LOG_TARGET: &str = "runtime::roles";
+
 #[cfg(test)]
 mod mock;
 
@@ -59,8 +78,11 @@ pub mod pallet {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
         /// When deleting a role via `delete_role()` dispatch, this parameter is checked.
-        /// If the number of users that own a given role is greater or equal to this number,
-        /// then `TooManyUsersToDeleteRole` error will be returned and the dispatch will fail.
+        /// If the number of users that own a given role is greater than this number,
+        /// then the role is deleted at once, but revoked from its users in `on_idle`.
+        /// It is also the maximum number of users, that an expired or deleted role is revoked
+        /// from, or that are checked for content keys after a role is disabled,
+        /// in a single cleanup step.
         #[pallet::constant]
         type MaxUsersToProcessPerDeleteRole: Get<u16>;
 
@@ -69,19 +91,63 @@ pub mod pallet {
             SpacePermissionsInfoOf<Self>,
         >;
 
-        type SpaceFollows: SpaceFollowsProvider<AccountId = Self::AccountId>;
+        type SpaceFollows: SpaceFollowsProvider<
+            AccountId = Self::AccountId,
+            BlockNumber = Self::BlockNumber,
+        >;
+
+        /// The currency, whose free balance is checked by `RoleCondition::MinBalance`,
+        /// and that is used to reserve deposits of join requests.
+        type Currency: ReservableCurrency<Self::AccountId>;
+
+        /// The amount held on deposit for a request to join a space.
+        #[pallet::constant]
+        type JoinRequestDeposit: Get<BalanceOf<Self>>;
+
+        /// A provider of domains ownership, that is checked by `RoleCondition::OwnsDomain`.
+        type DomainsProvider: DomainsProvider<Self::AccountId>;
 
         type IsAccountBlocked: IsAccountBlocked<Self::AccountId>;
 
         type IsContentBlocked: IsContentBlocked;
 
+        /// A provider of space settings, that is used to read `RoleApprovalsSetting`.
+        type SpaceSettings: SpaceSettingsProvider;
+
+        /// A handler that is called when a role is deleted, expires or is removed
+        /// together with its' space.
+        type OnRoleRemoved: OnRoleRemoved;
+
+        /// How many blocks a role proposal can be approved for, after it has been created.
+        #[pallet::constant]
+        type RoleProposalLifetime: Get<Self::BlockNumber>;
+
+        /// The amount held on deposit for a role proposal, until it is executed, cancelled
+        /// or removed after it expires.
+        #[pallet::constant]
+        type RoleProposalDeposit: Get<BalanceOf<Self>>;
+
+        /// A public key that is used to verify signatures of invite vouchers.
+        type InviteSigner: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// A signature of an invite voucher, that a role manager signs off-chain.
+        type InviteSignature: Parameter + Verify<Signer = Self::InviteSigner>;
+
+        /// A helper to sign invite vouchers in benchmarks.
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<Self::AccountId, Self::InviteSignature>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
 
+    /// The current storage version
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
     #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::event]
@@ -92,6 +158,39 @@ pub mod pallet {
         RoleDeleted { account: T::AccountId, role_id: RoleId },
         RoleGranted { account: T::AccountId, role_id: RoleId, users: Vec<User<T::AccountId>> },
         RoleRevoked { account: T::AccountId, role_id: RoleId, users: Vec<User<T::AccountId>> },
+        ContentPublicKeyUpdated { account: T::AccountId },
+        ContentKeyEnvelopesPublished {
+            account: T::AccountId,
+            space_id: SpaceId,
+            key_version: ContentKeyVersion,
+            members: Vec<T::AccountId>,
+        },
+        ContentKeyRotationRequired { space_id: SpaceId },
+        JoinRequestCreated { account: T::AccountId, space_id: SpaceId },
+        JoinRequestApproved {
+            account: T::AccountId,
+            space_id: SpaceId,
+            requester: T::AccountId,
+            role_id: RoleId,
+        },
+        JoinRequestRejected { account: T::AccountId, space_id: SpaceId, requester: T::AccountId },
+        InviteVoucherRedeemed { account: T::AccountId, signer: T::AccountId, role_id: RoleId },
+        RoleExpired { space_id: SpaceId, role_id: RoleId },
+        RoleGrantExpired { role_id: RoleId, user: User<T::AccountId> },
+        RoleRankUpdated { account: T::AccountId, role_id: RoleId, rank: RoleRank },
+        RoleConditionUpdated { account: T::AccountId, role_id: RoleId },
+        RoleProposalCreated { account: T::AccountId, role_id: RoleId, proposal_id: RoleProposalId },
+        RoleProposalApproved { account: T::AccountId, proposal_id: RoleProposalId },
+        RoleProposalExecuted { account: T::AccountId, proposal_id: RoleProposalId },
+        RoleProposalCancelled { account: T::AccountId, proposal_id: RoleProposalId },
+        JoinRequestCancelled { account: T::AccountId, space_id: SpaceId },
+        RoleGrantExpirationUpdated {
+            account: T::AccountId,
+            role_id: RoleId,
+            user: User<T::AccountId>,
+            expires_at: Option<T::BlockNumber>,
+        },
+        RoleProposalExpired { proposal_id: RoleProposalId },
     }
 
     #[pallet::error]
@@ -118,6 +217,7 @@ pub mod pallet {
 
         /// Canot remove a role from this many users in a single transaction.
         /// See `MaxUsersToProcessPerDeleteRole` parameter of this trait.
+        /// Not used anymore: such roles are revoked from their users in `on_idle`.
         TooManyUsersToDeleteRole,
 
         /// The user count sent doesn't match the real user count.
@@ -128,6 +228,93 @@ pub mod pallet {
 
         /// Cannot enable a role that is already enabled.
         RoleAlreadyEnabled,
+
+        /// Account does not have permission to manage content keys in this space.
+        NoPermissionToManageContentKeys,
+
+        /// No content key envelopes provided.
+        NoEnvelopesProvided,
+
+        /// A content key envelope can be published only for the space owner or a space member.
+        NotASpaceMember,
+
+        /// A member has not published their X25519 public key yet.
+        ContentPublicKeyNotFound,
+
+        /// A key version must be equal to the current one (to add new members)
+        /// or be the next one (to rotate a content key).
+        InvalidContentKeyVersion,
+
+        /// A content key must be rotated, because some member was removed from a space.
+        ContentKeyMustBeRotated,
+
+        /// An encrypted content key is too long.
+        EncryptedContentKeyTooLong,
+
+        /// Account is already the owner or a member of this space.
+        AlreadyASpaceMember,
+
+        /// Account has already requested to join this space.
+        JoinRequestAlreadyExists,
+
+        /// There is no request to join this space from a given account.
+        JoinRequestNotFound,
+
+        /// Invite voucher has expired.
+        InviteVoucherExpired,
+
+        /// Invite voucher has already been redeemed.
+        InviteVoucherAlreadyUsed,
+
+        /// Invite voucher signature does not match its' signer.
+        InvalidInviteVoucherSignature,
+
+        /// A role grant must expire at a future block.
+        InvalidGrantExpiration,
+
+        /// Account can manage only roles that rank below its highest role with
+        /// `ManageRoles` permission.
+        RoleRankTooHigh,
+
+        /// Account cannot give permissions via a role, that it does not have itself.
+        CannotGivePermissionsNotHeld,
+
+        /// This role has no condition to be claimed or refreshed by.
+        RoleHasNoCondition,
+
+        /// Account already has this role and meets its condition,
+        /// or does not have it and does not meet its condition.
+        NothingToRefresh,
+
+        /// Role proposal was not found by id.
+        RoleProposalNotFound,
+
+        /// Role proposal has expired and cannot be approved anymore.
+        RoleProposalExpired,
+
+        /// Account has already approved this role proposal.
+        RoleProposalAlreadyApproved,
+
+        /// Only the proposer can cancel a role proposal before it expires.
+        NotARoleProposer,
+
+        /// Cannot propose to grant or revoke a role to or from this many users at once.
+        /// See `MaxUsersToProcessPerDeleteRole` parameter of this trait.
+        TooManyUsersInRoleProposal,
+
+        /// A rotated content key must be published for every remaining member,
+        /// that had an envelope of the previous key.
+        ContentKeyEnvelopeMissing,
+
+        /// A space has more content key envelopes than declared in `existing_envelopes`.
+        TooManyExistingEnvelopes,
+
+        /// Account has not enough free balance to reserve a deposit for a join request
+        /// or a role proposal.
+        InsufficientBalanceToReserveDeposit,
+
+        /// The same change of this role has already been proposed and is waiting for approvals.
+        RoleProposalAlreadyExists,
     }
 
     #[pallet::type_value]
@@ -172,6 +359,193 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Ids of roles indexed by the block at which they expire.
+    #[pallet::storage]
+    pub type RoleIdsExpiringAt<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, RoleId, ()>;
+
+    /// The first block, whose expired roles may not be cleaned up yet.
+    #[pallet::storage]
+    #[pallet::getter(fn next_role_expiration_block)]
+    pub type NextRoleExpirationBlock<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// Get a rank of a given role within its space.
+    #[pallet::storage]
+    #[pallet::getter(fn role_rank)]
+    pub type RoleRankById<T: Config> = StorageMap<_, Twox64Concat, RoleId, RoleRank, ValueQuery>;
+
+    /// Get the number of ranked roles within a given space.
+    /// Spaces without ranked roles follow the flat model of role management.
+    #[pallet::storage]
+    #[pallet::getter(fn ranked_roles_count)]
+    pub type RankedRolesCountBySpaceId<T: Config> =
+        StorageMap<_, Twox64Concat, SpaceId, u32, ValueQuery>;
+
+    /// Get a condition, that an account must meet to claim a given role.
+    #[pallet::storage]
+    #[pallet::getter(fn role_condition)]
+    pub type RoleConditionById<T: Config> = StorageMap<_, Twox64Concat, RoleId, RoleConditionOf<T>>;
+
+    /// Roles, that were deleted, but still have to be revoked from some of their users.
+    #[pallet::storage]
+    #[pallet::getter(fn role_pending_deletion)]
+    pub type RolesPendingDeletion<T: Config> = StorageMap<_, Twox64Concat, RoleId, Role<T>>;
+
+    /// Roles, that were disabled, but whose users still have to be checked for content keys,
+    /// mapped to the number of users, that are left to be checked.
+    #[pallet::storage]
+    pub type DisabledRolesPendingCleanup<T: Config> = StorageMap<_, Twox64Concat, RoleId, u32>;
+
+    /// Spaces, that were deleted, but whose roles and content keys are not cleaned up yet.
+    #[pallet::storage]
+    #[pallet::getter(fn is_space_pending_cleanup)]
+    pub type SpacesPendingCleanup<T: Config> =
+        StorageMap<_, Twox64Concat, SpaceId, bool, ValueQuery>;
+
+    /// Get a block number at which a role granted to a given user expires,
+    /// if it was granted temporarily.
+    #[pallet::storage]
+    #[pallet::getter(fn role_grant_expires_at)]
+    pub type RoleGrantExpiresAt<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        RoleId,
+        Blake2_128Concat,
+        User<T::AccountId>,
+        T::BlockNumber,
+    >;
+
+    /// Temporary role grants indexed by the block at which they expire.
+    #[pallet::storage]
+    pub type RoleGrantsExpiringAt<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        Blake2_128Concat,
+        (RoleId, User<T::AccountId>),
+        (),
+    >;
+
+    /// The first block, whose expired role grants may not be pruned yet.
+    #[pallet::storage]
+    #[pallet::getter(fn next_role_grant_expiration_block)]
+    pub type NextRoleGrantExpirationBlock<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// Users, that were granted a given role, because they met its' condition.
+    /// Only these grants are revoked, when users do not meet the condition anymore.
+    #[pallet::storage]
+    pub type ConditionalRoleGrants<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, RoleId, Blake2_128Concat, User<T::AccountId>, ()>;
+
+    /// Get an X25519 public key that an account uses to receive content keys
+    /// of members-only spaces.
+    #[pallet::storage]
+    #[pallet::getter(fn content_public_key_by_account)]
+    pub type ContentPublicKeyByAccount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, X25519PublicKey>;
+
+    /// The current version of a content key in a given space.
+    /// `0` means that no content key has been published yet.
+    #[pallet::storage]
+    #[pallet::getter(fn content_key_version)]
+    pub type ContentKeyVersionBySpaceId<T: Config> =
+        StorageMap<_, Twox64Concat, SpaceId, ContentKeyVersion, ValueQuery>;
+
+    /// Whether a content key of a given space must be rotated, because some member was removed.
+    #[pallet::storage]
+    #[pallet::getter(fn is_content_key_rotation_required)]
+    pub type ContentKeyRotationRequired<T: Config> =
+        StorageMap<_, Twox64Concat, SpaceId, bool, ValueQuery>;
+
+    /// Get a content key envelope of a given member in a given space.
+    #[pallet::storage]
+    #[pallet::getter(fn content_key_envelope)]
+    pub type ContentKeyEnvelopes<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        SpaceId,
+        Blake2_128Concat,
+        T::AccountId,
+        ContentKeyEnvelope,
+    >;
+
+    /// Get a request of a given account to join a given space.
+    #[pallet::storage]
+    #[pallet::getter(fn join_request)]
+    pub type JoinRequests<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        SpaceId,
+        Blake2_128Concat,
+        T::AccountId,
+        JoinRequestOf<T>,
+    >;
+
+    /// Hashes of invite vouchers that have already been redeemed, by their expiration block.
+    /// A voucher cannot be redeemed after it expires, so its' hash is removed then.
+    #[pallet::storage]
+    pub type UsedInviteVouchers<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Identity, T::Hash, bool, ValueQuery>;
+
+    /// A block, starting from which hashes of expired invite vouchers are removed in `on_idle`.
+    #[pallet::storage]
+    #[pallet::getter(fn next_invite_voucher_expiration_block)]
+    pub type NextInviteVoucherExpirationBlock<T: Config> =
+        StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    #[pallet::type_value]
+    pub fn DefaultForNextRoleProposalId() -> RoleProposalId {
+        FIRST_ROLE_PROPOSAL_ID
+    }
+
+    /// The next role proposal id.
+    #[pallet::storage]
+    #[pallet::getter(fn next_role_proposal_id)]
+    pub type NextRoleProposalId<T: Config> =
+        StorageValue<_, RoleProposalId, ValueQuery, DefaultForNextRoleProposalId>;
+
+    /// Role changes that are waiting for approvals of role managers.
+    /// See `RoleApprovalsSetting`.
+    #[pallet::storage]
+    #[pallet::getter(fn role_proposal_by_id)]
+    pub type RoleProposalById<T: Config> =
+        StorageMap<_, Twox64Concat, RoleProposalId, RoleProposal<T>>;
+
+    /// Get an id of an open proposal by a role id and a hash of the proposed change,
+    /// so that the same change cannot be proposed twice.
+    #[pallet::storage]
+    pub type RoleProposalIdByChange<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, RoleId, Identity, T::Hash, RoleProposalId>;
+
+    /// Ids of role proposals by the block they expire at.
+    /// Expired proposals are removed in `on_idle`, and their deposits are unreserved.
+    #[pallet::storage]
+    pub type RoleProposalsExpiringAt<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, RoleProposalId, ()>;
+
+    /// The first block, whose expired role proposals may not be removed yet.
+    #[pallet::storage]
+    #[pallet::getter(fn next_role_proposal_expiration_block)]
+    pub type NextRoleProposalExpirationBlock<T: Config> =
+        StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            let mut meter = WeightMeter::new(remaining_weight);
+
+            Self::clean_deleted_spaces(&mut meter);
+            Self::clean_deleted_roles(&mut meter);
+            Self::clean_disabled_roles(&mut meter);
+            Self::clean_expired_roles(now, &mut meter);
+            Self::clean_expired_role_grants(now, &mut meter);
+            Self::clean_used_invite_vouchers(now, &mut meter);
+            Self::clean_expired_role_proposals(now, &mut meter);
+
+            meter.consumed()
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Create a new role, with a list of permissions, within a given space.
@@ -179,15 +553,23 @@ pub mod pallet {
         /// `content` can optionally contain additional information associated with a role,
         /// such as a name, description, and image for a role. This may be useful for end users.
         ///
+        /// `rank` must be below the rank of the caller's highest role with `ManageRoles`
+        /// permission, unless the caller is the space owner. In a space without ranked roles
+        /// only the space owner can create a ranked role.
+        ///
         /// Only the space owner or a user with `ManageRoles` permission can call this dispatch.
         #[pallet::call_index(0)]
-        #[pallet::weight(<T as Config>::WeightInfo::create_role())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::create_role()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn create_role(
             origin: OriginFor<T>,
             space_id: SpaceId,
             time_to_live: Option<T::BlockNumber>,
             content: Content,
             permissions: Vec<SpacePermission>,
+            rank: RoleRank,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -199,9 +581,12 @@ pub mod pallet {
                 ModerationError::ContentIsBlocked,
             );
 
-            Self::ensure_role_manager(who.clone(), space_id)?;
+            Self::ensure_role_manager_of_rank(who.clone(), space_id, rank)?;
+            Self::ensure_can_give_permissions(who.clone(), space_id, permissions.iter().cloned())?;
 
             let permissions_set = permissions.into_iter().collect();
+            Permissions::<T>::ensure_permissions_are_declared(&permissions_set)?;
+
             let new_role =
                 Role::<T>::new(who.clone(), space_id, time_to_live, content, permissions_set)?;
 
@@ -211,6 +596,10 @@ pub mod pallet {
 
             RoleById::<T>::insert(new_role.id, new_role.clone());
             RoleIdsBySpaceId::<T>::mutate(space_id, |role_ids| role_ids.push(new_role.id));
+            if let Some(expires_at) = new_role.expires_at {
+                RoleIdsExpiringAt::<T>::insert(expires_at, new_role.id, ());
+            }
+            Self::update_role_rank(space_id, new_role.id, rank);
 
             Self::deposit_event(Event::RoleCreated {
                 account: who,
@@ -221,9 +610,20 @@ pub mod pallet {
         }
 
         /// Update an existing role by a given id.
+        ///
+        /// If the update disables the role, takes permissions away from it or gives it
+        /// `ManageRoles` permission, and the space requires approvals of role managers
+        /// (see `RoleApprovalsSetting`), a role proposal is created instead.
+        ///
         /// Only the space owner or a user with `ManageRoles` permission can call this dispatch.
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config>::WeightInfo::update_role())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::update_role()
+                .saturating_add(<T as Config>::WeightInfo::clean_disabled_role(
+                    T::MaxUsersToProcessPerDeleteRole::get().into()
+                ))
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn update_role(
             origin: OriginFor<T>,
             role_id: RoleId,
@@ -237,55 +637,46 @@ pub mod pallet {
 
             ensure!(has_updates, Error::<T>::NoUpdatesProvided);
 
-            let mut role = Self::require_role(role_id)?;
-
-            Self::ensure_role_manager(who.clone(), role.space_id)?;
-
-            let mut is_update_applied = false;
-
-            if let Some(disabled) = update.disabled {
-                if disabled != role.disabled {
-                    role.set_disabled(disabled)?;
-                    is_update_applied = true;
-                }
-            }
-
-            if let Some(content) = update.content {
-                if content != role.content {
-                    ensure_content_is_valid(content.clone())?;
-                    ensure!(
-                        T::IsContentBlocked::is_allowed_content(content.clone(), role.space_id),
-                        ModerationError::ContentIsBlocked
-                    );
+            let role = Self::require_role(role_id)?;
 
-                    role.content = content;
-                    is_update_applied = true;
-                }
+            Self::ensure_role_manager_of(who.clone(), &role)?;
+            if let Some(permissions) = &update.permissions {
+                Self::ensure_can_give_permissions(
+                    who.clone(),
+                    role.space_id,
+                    permissions.difference(&role.permissions).iter(),
+                )?;
             }
 
-            if let Some(permissions) = update.permissions {
-                if !permissions.is_empty() {
-                    let permissions_diff: Vec<_> =
-                        permissions.symmetric_difference(&role.permissions).cloned().collect();
-
-                    if !permissions_diff.is_empty() {
-                        role.permissions = permissions;
-                        is_update_applied = true;
-                    }
+            let change = RoleChange::UpdateRole { update: update.clone() };
+            if Self::requires_role_approvals(&who, &role, &change) {
+                // Check the update before proposing it, and don't propose an empty one.
+                if !role.clone().apply_update(update)? {
+                    return Ok(())
                 }
+                return Self::create_role_proposal(who.clone(), who, role_id, change, true)
             }
 
-            if is_update_applied {
-                <RoleById<T>>::insert(role_id, role);
-                Self::deposit_event(Event::RoleUpdated { account: who, role_id });
-            }
-            Ok(())
+            Self::apply_role_change(who, role, change)
         }
 
         /// Delete a given role and clean all associated storage items.
+        ///
+        /// If the role has more than `MaxUsersToProcessPerDeleteRole` users, it is deleted
+        /// at once and grants nothing, but it is revoked from its users in `on_idle`
+        /// over the next blocks.
+        ///
+        /// If the space requires approvals of role managers (see `RoleApprovalsSetting`),
+        /// a role proposal is created instead, that deletes the role once approved.
+        ///
         /// Only the space owner or a user with `ManageRoles` permission can call this dispatch.
         #[pallet::call_index(2)]
-        #[pallet::weight(<T as Config>::WeightInfo::delete_role(*user_count))]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::delete_role(
+                (*user_count).min(T::MaxUsersToProcessPerDeleteRole::get().into())
+            )
+            .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn delete_role(
             origin: OriginFor<T>,
             role_id: RoleId,
@@ -295,75 +686,87 @@ pub mod pallet {
 
             let role = Self::require_role(role_id)?;
 
-            Self::ensure_role_manager(who.clone(), role.space_id)?;
+            Self::ensure_role_manager_of(who.clone(), &role)?;
 
-            let users = Self::users_by_role_id(role_id);
-            ensure!(users.len() as u32 == user_count, Error::<T>::IncorrectUserCount);
-            ensure!(
-                users.len() <= T::MaxUsersToProcessPerDeleteRole::get() as usize,
-                Error::<T>::TooManyUsersToDeleteRole
-            );
-
-            let role_idx_by_space_opt =
-                Self::role_ids_by_space_id(role.space_id).iter().position(|x| *x == role_id);
+            let users_count = UsersByRoleId::<T>::decode_len(role_id).unwrap_or_default() as u32;
+            ensure!(users_count == user_count, Error::<T>::IncorrectUserCount);
 
-            if let Some(role_idx) = role_idx_by_space_opt {
-                RoleIdsBySpaceId::<T>::mutate(role.space_id, |n| n.swap_remove(role_idx));
+            if Self::requires_role_approvals(&who, &role, &RoleChange::DeleteRole) {
+                return Self::create_role_proposal(
+                    who.clone(),
+                    who,
+                    role_id,
+                    RoleChange::DeleteRole,
+                    true,
+                )
             }
 
-            role.revoke_from_users(users);
-
-            <RoleById<T>>::remove(role_id);
-            <UsersByRoleId<T>>::remove(role_id);
-
-            Self::deposit_event(Event::RoleDeleted { account: who, role_id });
-            Ok(())
+            Self::apply_role_change(who, role, RoleChange::DeleteRole)
         }
 
         /// Grant a given role to a list of users.
+        ///
+        /// If `expires_at` is provided, the role is granted to these users until a given block,
+        /// after which the grant is ignored and pruned automatically. Otherwise it is permanent.
+        /// If a user already has this role, its' grant expires at the new block instead,
+        /// or becomes permanent, and `RoleGrantExpirationUpdated` event is emitted.
+        ///
+        /// If the role has `ManageRoles` permission and the space requires approvals of role
+        /// managers (see `RoleApprovalsSetting`), a role proposal is created instead.
+        ///
         /// Only the space owner or a user with `ManageRoles` permission can call this dispatch.
         #[pallet::call_index(3)]
-        #[pallet::weight(<T as Config>::WeightInfo::grant_role(users.len() as u32))]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::grant_role(users.len() as u32)
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn grant_role(
             origin: OriginFor<T>,
             role_id: RoleId,
             users: Vec<User<T::AccountId>>,
+            expires_at: Option<T::BlockNumber>,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
             ensure!(!users.is_empty(), Error::<T>::NoUsersProvided);
             let users_set: BTreeSet<User<T::AccountId>> = convert_users_vec_to_btree_set(users)?;
 
-            let role = Self::require_role(role_id)?;
+            if let Some(expires_at) = expires_at {
+                ensure!(
+                    expires_at > frame_system::Pallet::<T>::block_number(),
+                    Error::<T>::InvalidGrantExpiration
+                );
+            }
 
-            Self::ensure_role_manager(who.clone(), role.space_id)?;
+            let role = Self::require_role(role_id)?;
 
-            for user in users_set.iter() {
-                if !Self::users_by_role_id(role_id).contains(user) {
-                    <UsersByRoleId<T>>::mutate(role_id, |users| {
-                        users.push(user.clone());
-                    });
-                }
-                if !Self::role_ids_by_user_in_space(user.clone(), role.space_id).contains(&role_id)
-                {
-                    <RoleIdsByUserInSpace<T>>::mutate(user.clone(), role.space_id, |roles| {
-                        roles.push(role_id);
-                    })
-                }
+            Self::ensure_role_manager_of(who.clone(), &role)?;
+            Self::ensure_can_give_permissions(
+                who.clone(),
+                role.space_id,
+                role.permissions.iter(),
+            )?;
+
+            let users: Vec<_> = users_set.into_iter().collect();
+            let change = RoleChange::GrantRole { users, expires_at };
+            if Self::requires_role_approvals(&who, &role, &change) {
+                return Self::create_role_proposal(who.clone(), who, role_id, change, true)
             }
 
-            Self::deposit_event(Event::RoleGranted {
-                account: who,
-                role_id,
-                users: users_set.iter().cloned().collect(),
-            });
-            Ok(())
+            Self::apply_role_change(who, role, change)
         }
 
         /// Revoke a given role from a list of users.
+        ///
+        /// If the space requires approvals of role managers (see `RoleApprovalsSetting`),
+        /// a role proposal is created instead, that revokes the role once approved.
+        ///
         /// Only the space owner or a user with `ManageRoles` permission can call this dispatch.
         #[pallet::call_index(4)]
-        #[pallet::weight(<T as Config>::WeightInfo::revoke_role(users.len() as u32))]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::revoke_role(users.len() as u32)
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn revoke_role(
             origin: OriginFor<T>,
             role_id: RoleId,
@@ -375,12 +778,14 @@ pub mod pallet {
 
             let role = Self::require_role(role_id)?;
 
-            Self::ensure_role_manager(who.clone(), role.space_id)?;
+            Self::ensure_role_manager_of(who.clone(), &role)?;
 
-            role.revoke_from_users(users.clone());
+            let change = RoleChange::RevokeRole { users };
+            if Self::requires_role_approvals(&who, &role, &change) {
+                return Self::create_role_proposal(who.clone(), who, role_id, change, true)
+            }
 
-            Self::deposit_event(Event::RoleRevoked { account: who, role_id, users });
-            Ok(())
+            Self::apply_role_change(who, role, change)
         }
 
         #[pallet::call_index(5)]
@@ -446,22 +851,13 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             ensure_root(origin)?;
 
-            let space_id = Self::require_role(role_id)?.space_id;
-            let space = T::SpacePermissionsProvider::space_permissions_info(space_id)?;
+            let role = Self::require_role(role_id)?;
+            let space = T::SpacePermissionsProvider::space_permissions_info(role.space_id)?;
 
             let users_set: BTreeSet<User<T::AccountId>> = convert_users_vec_to_btree_set(users)?;
 
             for user in users_set.iter() {
-                if !Self::users_by_role_id(role_id).contains(user) {
-                    <UsersByRoleId<T>>::mutate(role_id, |users| {
-                        users.push(user.clone());
-                    });
-                }
-                if !Self::role_ids_by_user_in_space(user.clone(), space_id).contains(&role_id) {
-                    <RoleIdsByUserInSpace<T>>::mutate(user.clone(), space_id, |roles| {
-                        roles.push(role_id);
-                    })
-                }
+                role.grant_to_user(user.clone());
             }
 
             Self::deposit_event(Event::RoleGranted {
@@ -486,5 +882,535 @@ pub mod pallet {
             NextRoleId::<T>::put(role_id);
             Ok(Pays::No.into())
         }
+
+        /// Set or remove (if `None`) an X25519 public key of the caller,
+        /// that space managers use to encrypt content keys of members-only spaces.
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_content_public_key())]
+        pub fn set_content_public_key(
+            origin: OriginFor<T>,
+            public_key_opt: Option<X25519PublicKey>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ContentPublicKeyByAccount::<T>::set(&who, public_key_opt);
+
+            Self::deposit_event(Event::ContentPublicKeyUpdated { account: who });
+            Ok(())
+        }
+
+        /// Publish content key envelopes for a list of members of a given space.
+        ///
+        /// If `key_version` is equal to the current one, envelopes are added for new members.
+        /// If `key_version` is the next one, a content key is rotated. A content key must be
+        /// rotated after a member has been removed from a space.
+        ///
+        /// A rotated key must be published for every remaining member, that had an envelope
+        /// of the previous key, while envelopes of former members are removed.
+        /// `existing_envelopes` must be at least the number of envelopes in a space before
+        /// the rotation, so that they all can be checked.
+        ///
+        /// Only the space owner or a user with `ManageContentKeys` permission can call this
+        /// dispatch.
+        #[pallet::call_index(9)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::publish_content_key_envelopes(envelopes.len() as u32)
+                .saturating_add(T::DbWeight::get().reads_writes(
+                    (*existing_envelopes as u64).saturating_mul(2),
+                    *existing_envelopes as u64,
+                ))
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn publish_content_key_envelopes(
+            origin: OriginFor<T>,
+            space_id: SpaceId,
+            key_version: ContentKeyVersion,
+            envelopes: Vec<(T::AccountId, Vec<u8>)>,
+            existing_envelopes: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!envelopes.is_empty(), Error::<T>::NoEnvelopesProvided);
+
+            Self::ensure_content_key_manager(who.clone(), space_id)?;
+
+            let current_version = Self::content_key_version(space_id);
+            let is_rotation = Some(key_version) == current_version.checked_add(1);
+
+            if !is_rotation {
+                ensure!(
+                    key_version == current_version && current_version > 0,
+                    Error::<T>::InvalidContentKeyVersion
+                );
+                ensure!(
+                    !Self::is_content_key_rotation_required(space_id),
+                    Error::<T>::ContentKeyMustBeRotated
+                );
+            }
+
+            let space_owner = T::SpacePermissionsProvider::space_permissions_info(space_id)?.owner;
+
+            if is_rotation {
+                let previous_holders: Vec<_> = ContentKeyEnvelopes::<T>::iter_key_prefix(space_id)
+                    .take((existing_envelopes as usize).saturating_add(1))
+                    .collect();
+                ensure!(
+                    previous_holders.len() <= existing_envelopes as usize,
+                    Error::<T>::TooManyExistingEnvelopes
+                );
+
+                for holder in previous_holders {
+                    if envelopes.iter().any(|(member, _)| *member == holder) {
+                        continue
+                    }
+
+                    ensure!(
+                        holder != space_owner && !Self::is_space_member(&holder, space_id),
+                        Error::<T>::ContentKeyEnvelopeMissing
+                    );
+                    ContentKeyEnvelopes::<T>::remove(space_id, &holder);
+                }
+            }
+
+            let mut members = Vec::new();
+
+            for (member, encrypted_key) in envelopes {
+                ensure!(
+                    member == space_owner || Self::is_space_member(&member, space_id),
+                    Error::<T>::NotASpaceMember
+                );
+                ensure!(
+                    ContentPublicKeyByAccount::<T>::contains_key(&member),
+                    Error::<T>::ContentPublicKeyNotFound
+                );
+
+                let encrypted_key = EncryptedContentKey::try_from(encrypted_key)
+                    .map_err(|_| Error::<T>::EncryptedContentKeyTooLong)?;
+
+                ContentKeyEnvelopes::<T>::insert(
+                    space_id,
+                    &member,
+                    ContentKeyEnvelope { key_version, encrypted_key },
+                );
+                members.push(member);
+            }
+
+            if is_rotation {
+                ContentKeyVersionBySpaceId::<T>::insert(space_id, key_version);
+                ContentKeyRotationRequired::<T>::remove(space_id);
+            }
+
+            Self::deposit_event(Event::ContentKeyEnvelopesPublished {
+                account: who,
+                space_id,
+                key_version,
+                members,
+            });
+            Ok(())
+        }
+
+        /// Request to join a given space. A role manager of this space can either approve
+        /// this request with a role to grant or reject it.
+        ///
+        /// `JoinRequestDeposit` is reserved from the requester, until the request is approved,
+        /// rejected or cancelled.
+        #[pallet::call_index(10)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::request_to_join_space()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn request_to_join_space(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                T::IsAccountBlocked::is_allowed_account(who.clone(), space_id),
+                ModerationError::AccountIsBlocked
+            );
+
+            let space = T::SpacePermissionsProvider::space_permissions_info(space_id)?;
+            ensure!(
+                who != space.owner && !Self::is_space_member(&who, space_id),
+                Error::<T>::AlreadyASpaceMember
+            );
+            ensure!(
+                !JoinRequests::<T>::contains_key(space_id, &who),
+                Error::<T>::JoinRequestAlreadyExists
+            );
+
+            let deposit = T::JoinRequestDeposit::get();
+            T::Currency::reserve(&who, deposit)
+                .map_err(|_| Error::<T>::InsufficientBalanceToReserveDeposit)?;
+
+            JoinRequests::<T>::insert(
+                space_id,
+                &who,
+                JoinRequest { created_at: frame_system::Pallet::<T>::block_number(), deposit },
+            );
+
+            Self::deposit_event(Event::JoinRequestCreated { account: who, space_id });
+            Ok(())
+        }
+
+        /// Approve a request to join a space and grant a given role to the requester.
+        ///
+        /// If the role has `ManageRoles` permission and the space requires approvals of role
+        /// managers (see `RoleApprovalsSetting`), a role proposal is created instead,
+        /// that grants the role to the requester once approved.
+        ///
+        /// Only the space owner or a user with `ManageRoles` permission can call this dispatch.
+        #[pallet::call_index(11)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::approve_join_request()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn approve_join_request(
+            origin: OriginFor<T>,
+            role_id: RoleId,
+            requester: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let role = Self::require_role(role_id)?;
+
+            Self::ensure_role_manager_of(who.clone(), &role)?;
+            Self::ensure_can_give_permissions(
+                who.clone(),
+                role.space_id,
+                role.permissions.iter(),
+            )?;
+
+            Self::remove_join_request(role.space_id, &requester)
+                .ok_or(Error::<T>::JoinRequestNotFound)?;
+
+            let space_id = role.space_id;
+            let change = RoleChange::GrantRole {
+                users: vec![User::Account(requester.clone())],
+                expires_at: None,
+            };
+            if Self::requires_role_approvals(&who, &role, &change) {
+                Self::create_role_proposal(who.clone(), who.clone(), role_id, change, true)?;
+            } else {
+                Self::apply_role_change(who.clone(), role, change)?;
+            }
+
+            Self::deposit_event(Event::JoinRequestApproved {
+                account: who,
+                space_id,
+                requester,
+                role_id,
+            });
+            Ok(())
+        }
+
+        /// Reject a request to join a given space.
+        /// Only the space owner or a user with `ManageRoles` or `REJECT_JOIN_REQUESTS` permission
+        /// can call this dispatch.
+        #[pallet::call_index(12)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::reject_join_request()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn reject_join_request(
+            origin: OriginFor<T>,
+            space_id: SpaceId,
+            requester: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_join_request_moderator(who.clone(), space_id)?;
+
+            Self::remove_join_request(space_id, &requester)
+                .ok_or(Error::<T>::JoinRequestNotFound)?;
+
+            Self::deposit_event(Event::JoinRequestRejected { account: who, space_id, requester });
+            Ok(())
+        }
+
+        /// Redeem an invite voucher, signed off-chain by `signer`, and receive a role
+        /// from this voucher.
+        ///
+        /// A voucher can be redeemed only once, only before its' expiration block, and only
+        /// on the chain it was signed for (see `InviteVoucher::signing_payload`).
+        /// `signer` must still be the space owner or have `ManageRoles` permission
+        /// at the moment of redemption.
+        ///
+        /// If the role has `ManageRoles` permission and the space requires approvals of role
+        /// managers (see `RoleApprovalsSetting`), a role proposal on behalf of `signer`
+        /// is created instead, that grants the role to the caller once approved.
+        #[pallet::call_index(13)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::redeem_invite_voucher()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn redeem_invite_voucher(
+            origin: OriginFor<T>,
+            signer: T::AccountId,
+            voucher: InviteVoucherOf<T>,
+            signature: T::InviteSignature,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                frame_system::Pallet::<T>::block_number() < voucher.expires_at,
+                Error::<T>::InviteVoucherExpired
+            );
+
+            let voucher_hash = T::Hashing::hash_of(&(&signer, &voucher));
+            ensure!(
+                !UsedInviteVouchers::<T>::get(voucher.expires_at, voucher_hash),
+                Error::<T>::InviteVoucherAlreadyUsed
+            );
+            ensure!(
+                signature.verify(&Self::invite_voucher_signing_payload(&voucher)[..], &signer),
+                Error::<T>::InvalidInviteVoucherSignature
+            );
+
+            let role = Self::require_role(voucher.role_id)?;
+
+            Self::ensure_role_manager_of(signer.clone(), &role)?;
+            Self::ensure_can_give_permissions(
+                signer.clone(),
+                role.space_id,
+                role.permissions.iter(),
+            )?;
+
+            UsedInviteVouchers::<T>::insert(voucher.expires_at, voucher_hash, true);
+            Self::remove_join_request(role.space_id, &who);
+
+            let role_id = role.id;
+            let change =
+                RoleChange::GrantRole { users: vec![User::Account(who.clone())], expires_at: None };
+            if Self::requires_role_approvals(&signer, &role, &change) {
+                Self::create_role_proposal(signer.clone(), who.clone(), role_id, change, true)?;
+            } else {
+                Self::apply_role_change(signer.clone(), role, change)?;
+            }
+
+            Self::deposit_event(Event::InviteVoucherRedeemed { account: who, signer, role_id });
+            Ok(())
+        }
+
+        /// Set a rank of a given role within its space.
+        ///
+        /// Only the space owner or a user with `ManageRoles` permission,
+        /// whose highest managing role ranks above both the current and the new rank
+        /// of a given role, can call this dispatch.
+        #[pallet::call_index(14)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::set_role_rank()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn set_role_rank(
+            origin: OriginFor<T>,
+            role_id: RoleId,
+            rank: RoleRank,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let role = Self::require_role(role_id)?;
+
+            Self::ensure_role_manager_of(who.clone(), &role)?;
+            Self::ensure_role_manager_of_rank(who.clone(), role.space_id, rank)?;
+
+            Self::update_role_rank(role.space_id, role_id, rank);
+
+            Self::deposit_event(Event::RoleRankUpdated { account: who, role_id, rank });
+            Ok(())
+        }
+
+        /// Set or remove a condition, that lets any account, which meets it,
+        /// claim a given role via `refresh_conditional_role`.
+        ///
+        /// If the role has `ManageRoles` permission and the space requires approvals of role
+        /// managers (see `RoleApprovalsSetting`), a role proposal is created instead,
+        /// that sets a given condition once approved.
+        ///
+        /// Only the space owner or a user with `ManageRoles` permission can call this dispatch.
+        #[pallet::call_index(15)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::set_role_condition()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn set_role_condition(
+            origin: OriginFor<T>,
+            role_id: RoleId,
+            condition: Option<RoleConditionOf<T>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let role = Self::require_role(role_id)?;
+
+            Self::ensure_role_manager_of(who.clone(), &role)?;
+            // A condition lets anyone claim the role, so it gives away its' permissions.
+            Self::ensure_can_give_permissions(who.clone(), role.space_id, role.permissions.iter())?;
+
+            let change = RoleChange::SetRoleCondition { condition };
+            if Self::requires_role_approvals(&who, &role, &change) {
+                return Self::create_role_proposal(who.clone(), who, role_id, change, true)
+            }
+
+            Self::apply_role_change(who, role, change)
+        }
+
+        /// Grant a conditional role to a given account, if it meets the role's condition,
+        /// or revoke this role from it, if it does not meet the condition anymore.
+        /// Only a role, that was granted by its' condition, is revoked this way:
+        /// a role granted by a role manager is kept.
+        ///
+        /// If the space requires approvals of role managers (see `RoleApprovalsSetting`)
+        /// to revoke the role, or to grant it, when it has `ManageRoles` permission,
+        /// a role proposal is created instead. Such a proposal is not approved by the caller,
+        /// so it must be approved by as many role managers as the space requires.
+        ///
+        /// Anyone can call this dispatch.
+        #[pallet::call_index(16)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::refresh_conditional_role()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn refresh_conditional_role(
+            origin: OriginFor<T>,
+            role_id: RoleId,
+            account: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let role = Self::require_role(role_id)?;
+
+            let change = if Self::conditional_role_refresh(&role, &account)? {
+                RoleChange::GrantRoleByCondition { account }
+            } else {
+                RoleChange::RevokeRoleByCondition { account }
+            };
+            if Self::requires_role_approvals(&who, &role, &change) {
+                return Self::create_role_proposal(who.clone(), who, role_id, change, false)
+            }
+
+            Self::apply_role_change(who, role, change)
+        }
+
+        /// Approve a given role proposal. Once it is approved by as many role managers as
+        /// `RoleApprovalsSetting` of its' space requires, the proposed change is applied
+        /// on behalf of the caller, as the proposer may not be a role manager anymore,
+        /// and the deposit of the proposal is unreserved.
+        ///
+        /// Only the space owner or a user with `ManageRoles` permission can call this dispatch.
+        #[pallet::call_index(17)]
+        #[pallet::weight({
+            let max_users = T::MaxUsersToProcessPerDeleteRole::get().into();
+            <T as Config>::WeightInfo::approve_role_proposal().saturating_add(
+                <T as Config>::WeightInfo::delete_role(max_users)
+                    .max(<T as Config>::WeightInfo::grant_role(max_users))
+                    .max(<T as Config>::WeightInfo::revoke_role(max_users))
+                    .max(
+                        <T as Config>::WeightInfo::update_role()
+                            .saturating_add(<T as Config>::WeightInfo::clean_disabled_role(max_users)),
+                    )
+                    .max(<T as Config>::WeightInfo::set_role_condition())
+                    .max(<T as Config>::WeightInfo::refresh_conditional_role()),
+            )
+            .saturating_add(space_owner_walk_weight::<T>())
+        })]
+        pub fn approve_role_proposal(
+            origin: OriginFor<T>,
+            proposal_id: RoleProposalId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut proposal = Self::require_role_proposal(proposal_id)?;
+            ensure!(
+                proposal.expires_at > <system::Pallet<T>>::block_number(),
+                Error::<T>::RoleProposalExpired
+            );
+            ensure!(!proposal.approvals.contains(&who), Error::<T>::RoleProposalAlreadyApproved);
+
+            let role = Self::require_role(proposal.role_id)?;
+
+            Self::ensure_role_manager_of(who.clone(), &role)?;
+            match &proposal.change {
+                RoleChange::GrantRole { .. } |
+                RoleChange::GrantRoleByCondition { .. } |
+                RoleChange::SetRoleCondition { condition: Some(_) } =>
+                    Self::ensure_can_give_permissions(
+                        who.clone(),
+                        role.space_id,
+                        role.permissions.iter(),
+                    )?,
+                RoleChange::UpdateRole {
+                    update: RoleUpdate { permissions: Some(permissions), .. },
+                } => Self::ensure_can_give_permissions(
+                    who.clone(),
+                    role.space_id,
+                    permissions.difference(&role.permissions).iter(),
+                )?,
+                _ => {},
+            }
+
+            proposal.approvals.push(who.clone());
+
+            if proposal.approvals.len() as u32 >= Self::required_role_approvals(role.space_id) {
+                Self::remove_role_proposal(&proposal);
+                Self::apply_role_change(who.clone(), role, proposal.change)?;
+                Self::deposit_event(Event::RoleProposalExecuted { account: who, proposal_id });
+            } else {
+                RoleProposalById::<T>::insert(proposal_id, proposal);
+                Self::deposit_event(Event::RoleProposalApproved { account: who, proposal_id });
+            }
+            Ok(())
+        }
+
+        /// Cancel a given role proposal, and unreserve its' deposit.
+        ///
+        /// The proposer can cancel its' proposal at any time.
+        /// Anyone can remove a proposal that has expired.
+        #[pallet::call_index(18)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_role_proposal())]
+        pub fn cancel_role_proposal(
+            origin: OriginFor<T>,
+            proposal_id: RoleProposalId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let proposal = Self::require_role_proposal(proposal_id)?;
+
+            let is_expired = proposal.expires_at <= <system::Pallet<T>>::block_number();
+            ensure!(is_expired || proposal.proposer == who, Error::<T>::NotARoleProposer);
+
+            Self::remove_role_proposal(&proposal);
+
+            Self::deposit_event(Event::RoleProposalCancelled { account: who, proposal_id });
+            Ok(())
+        }
+
+        /// Cancel an own request to join a given space, and get its' deposit back.
+        #[pallet::call_index(19)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_join_request())]
+        pub fn cancel_join_request(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::remove_join_request(space_id, &who).ok_or(Error::<T>::JoinRequestNotFound)?;
+
+            Self::deposit_event(Event::JoinRequestCancelled { account: who, space_id });
+            Ok(())
+        }
     }
 }
+
+/// A handler that is called when a role is removed from the storage.
+///
+/// It is called from `on_idle` cleanups too, so it should only remove a few storage entries
+/// related to a given role.
+pub trait OnRoleRemoved {
+    fn on_role_removed(role_id: RoleId);
+}
+
+impl OnRoleRemoved for () {
+    fn on_role_removed(_role_id: RoleId) {}
+}
+
+/// A helper to sign invite vouchers in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId, Signature> {
+    /// Sign a given payload with some key and return an account of this key and a signature.
+    fn sign(payload: &[u8]) -> (AccountId, Signature);
+}
@@ -0,0 +1,330 @@
+use frame_support::{log, traits::OnRuntimeUpgrade};
+use sp_runtime::Saturating;
+
+use pallet_permissions::migration::{v0 as permissions_v0, v1 as permissions_v1};
+
+use super::*;
+
+pub mod v1 {
+    use frame_support::{pallet_prelude::*, weights::Weight};
+
+    use super::*;
+
+    // Old role, that stores permissions as an ordered set
+    #[derive(Encode, Decode)]
+    pub struct OldRole<T: Config> {
+        pub(super) created: WhoAndWhenOf<T>,
+        pub(super) id: RoleId,
+        pub(super) space_id: SpaceId,
+        pub(super) disabled: bool,
+        pub(super) expires_at: Option<T::BlockNumber>,
+        pub(super) content: Content,
+        pub(super) permissions: permissions_v0::SpacePermissionSet,
+    }
+
+    impl<T: Config> OldRole<T> {
+        fn migrate_to_v1(self) -> Role<T> {
+            Role {
+                created: self.created,
+                id: self.id,
+                space_id: self.space_id,
+                disabled: self.disabled,
+                expires_at: self.expires_at,
+                content: self.content,
+                permissions: permissions_v0::migrate_permission_set(self.permissions),
+            }
+        }
+    }
+
+    /// Converts permissions of every role, including roles pending deletion, into bitsets.
+    ///
+    /// Roles are written in their latest layout and indexed by their expiration
+    /// (see `v2::MigrateToV2`), so the storage goes straight to the current version,
+    /// and `v2::MigrateToV2` and `v3::MigrateToV3` are skipped afterwards.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let current_version = Pallet::<T>::current_storage_version();
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+
+            log::info!(
+                target: LOG_TARGET,
+                "Running migration with current storage version {:?} / onchain {:?}",
+                current_version,
+                onchain_version
+            );
+
+            if onchain_version == 0 {
+                let mut translated = 0u64;
+                let mut migrate = |_key, old_value: OldRole<T>| {
+                    translated.saturating_inc();
+                    Some(old_value.migrate_to_v1())
+                };
+                RoleById::<T>::translate::<OldRole<T>, _>(&mut migrate);
+                RolesPendingDeletion::<T>::translate::<OldRole<T>, _>(&mut migrate);
+
+                let (read, indexed) = v2::index_expiring_roles::<T>();
+
+                current_version.put::<Pallet<T>>();
+
+                log::info!(
+                    target: LOG_TARGET,
+                    "Upgraded {} records, indexed {} expiring roles, storage to version {:?}",
+                    translated,
+                    indexed,
+                    current_version
+                );
+                T::DbWeight::get().reads_writes(
+                    translated + read.saturating_mul(2) + 3,
+                    translated + indexed.saturating_mul(2) + 1,
+                )
+            } else {
+                log::info!(
+                    target: LOG_TARGET,
+                    "Migration did not execute. This probably should be removed"
+                );
+                T::DbWeight::get().reads(1)
+            }
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            ensure!(onchain_version == 0, "migration from version 0 to 1.");
+            let prev_count =
+                RoleById::<T>::iter_keys().count() + RolesPendingDeletion::<T>::iter_keys().count();
+            Ok((prev_count as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(prev_count: Vec<u8>) -> Result<(), &'static str> {
+            let prev_count: u32 = Decode::decode(&mut prev_count.as_slice()).expect(
+                "the state parameter should be something that was generated by pre_upgrade",
+            );
+            let post_count =
+                (RoleById::<T>::iter().count() + RolesPendingDeletion::<T>::iter().count()) as u32;
+            ensure!(
+                prev_count == post_count,
+                "the records count before and after the migration should be the same"
+            );
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == Pallet::<T>::current_storage_version(),
+                "wrong storage version"
+            );
+
+            Ok(())
+        }
+    }
+}
+
+pub mod v2 {
+    use frame_support::{pallet_prelude::*, storage::unhashed, weights::Weight};
+
+    use super::*;
+
+    // The leading fields of a role, that are enough to find out when it expires,
+    // whatever layout its' permissions are stored in.
+    #[derive(Encode, Decode)]
+    pub struct RoleExpiration<T: Config> {
+        pub(super) created: WhoAndWhenOf<T>,
+        pub(super) id: RoleId,
+        pub(super) space_id: SpaceId,
+        pub(super) disabled: bool,
+        pub(super) expires_at: Option<T::BlockNumber>,
+    }
+
+    /// Returns the numbers of read and indexed roles. See `MigrateToV2`.
+    pub(super) fn index_expiring_roles<T: Config>() -> (u64, u64) {
+        let first_block = frame_system::Pallet::<T>::block_number()
+            .max(Pallet::<T>::next_role_expiration_block());
+
+        let mut read = 0u64;
+        let mut indexed = 0u64;
+        for role_id in RoleById::<T>::iter_keys() {
+            read.saturating_inc();
+            let role: Option<RoleExpiration<T>> =
+                unhashed::get(&RoleById::<T>::hashed_key_for(role_id));
+
+            if let Some(expires_at) = role.and_then(|role| role.expires_at) {
+                indexed.saturating_inc();
+                // A role, that has already expired, may be indexed at a block,
+                // that will never be cleaned up again.
+                RoleIdsExpiringAt::<T>::remove(expires_at, role_id);
+                RoleIdsExpiringAt::<T>::insert(expires_at.max(first_block), role_id, ());
+            }
+        }
+
+        (read, indexed)
+    }
+
+    /// Indexes every role with an expiration in `RoleIdsExpiringAt`, so that it is cleaned up.
+    /// Roles, that have already expired, are indexed at the first block, that is not
+    /// cleaned up yet.
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let current_version = Pallet::<T>::current_storage_version();
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+
+            log::info!(
+                target: LOG_TARGET,
+                "Running migration with current storage version {:?} / onchain {:?}",
+                current_version,
+                onchain_version
+            );
+
+            if onchain_version == 1 && current_version >= 2 {
+                let (read, indexed) = index_expiring_roles::<T>();
+
+                StorageVersion::new(2).put::<Pallet<T>>();
+
+                log::info!(
+                    target: LOG_TARGET,
+                    "Indexed {} expiring roles out of {}, storage to version 2",
+                    indexed,
+                    read
+                );
+                T::DbWeight::get()
+                    .reads_writes(read.saturating_mul(2) + 3, indexed.saturating_mul(2) + 1)
+            } else {
+                log::info!(
+                    target: LOG_TARGET,
+                    "Migration did not execute. This probably should be removed"
+                );
+                T::DbWeight::get().reads(1)
+            }
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            let current_version = Pallet::<T>::current_storage_version();
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            ensure!(onchain_version == 1 && current_version >= 2, "migration from version 1 to 2.");
+            Ok(Vec::new())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            for role_id in RoleById::<T>::iter_keys() {
+                let role: Option<RoleExpiration<T>> =
+                    unhashed::get(&RoleById::<T>::hashed_key_for(role_id));
+                if role.and_then(|role| role.expires_at).is_some() {
+                    ensure!(
+                        RoleIdsExpiringAt::<T>::iter().any(|(_, id, _)| id == role_id),
+                        "every expiring role should be indexed"
+                    );
+                }
+            }
+
+            ensure!(Pallet::<T>::on_chain_storage_version() == 2, "wrong storage version");
+
+            Ok(())
+        }
+    }
+}
+
+pub mod v3 {
+    use frame_support::{pallet_prelude::*, weights::Weight};
+
+    use super::*;
+
+    // Old role, that stores permissions as a bitset of core permissions only
+    #[derive(Encode, Decode)]
+    pub struct OldRole<T: Config> {
+        pub(super) created: WhoAndWhenOf<T>,
+        pub(super) id: RoleId,
+        pub(super) space_id: SpaceId,
+        pub(super) disabled: bool,
+        pub(super) expires_at: Option<T::BlockNumber>,
+        pub(super) content: Content,
+        pub(super) permissions: permissions_v1::SpacePermissionSet,
+    }
+
+    impl<T: Config> OldRole<T> {
+        fn migrate_to_v3(self) -> Role<T> {
+            Role {
+                created: self.created,
+                id: self.id,
+                space_id: self.space_id,
+                disabled: self.disabled,
+                expires_at: self.expires_at,
+                content: self.content,
+                permissions: permissions_v1::migrate_permission_set(self.permissions),
+            }
+        }
+    }
+
+    /// Converts permissions of every role, including roles pending deletion, into sets,
+    /// that can also contain namespaced permissions.
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let current_version = Pallet::<T>::current_storage_version();
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+
+            log::info!(
+                target: LOG_TARGET,
+                "Running migration with current storage version {:?} / onchain {:?}",
+                current_version,
+                onchain_version
+            );
+
+            if onchain_version == 2 && current_version == 3 {
+                let mut translated = 0u64;
+                let mut migrate = |_key, old_value: OldRole<T>| {
+                    translated.saturating_inc();
+                    Some(old_value.migrate_to_v3())
+                };
+                RoleById::<T>::translate::<OldRole<T>, _>(&mut migrate);
+                RolesPendingDeletion::<T>::translate::<OldRole<T>, _>(&mut migrate);
+
+                current_version.put::<Pallet<T>>();
+
+                log::info!(
+                    target: LOG_TARGET,
+                    "Upgraded {} records, storage to version {:?}",
+                    translated,
+                    current_version
+                );
+                T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+            } else {
+                log::info!(
+                    target: LOG_TARGET,
+                    "Migration did not execute. This probably should be removed"
+                );
+                T::DbWeight::get().reads(1)
+            }
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            let current_version = Pallet::<T>::current_storage_version();
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            ensure!(onchain_version == 2 && current_version == 3, "migration from version 2 to 3.");
+            let prev_count =
+                RoleById::<T>::iter_keys().count() + RolesPendingDeletion::<T>::iter_keys().count();
+            Ok((prev_count as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(prev_count: Vec<u8>) -> Result<(), &'static str> {
+            let prev_count: u32 = Decode::decode(&mut prev_count.as_slice()).expect(
+                "the state parameter should be something that was generated by pre_upgrade",
+            );
+            let post_count =
+                (RoleById::<T>::iter().count() + RolesPendingDeletion::<T>::iter().count()) as u32;
+            ensure!(
+                prev_count == post_count,
+                "the records count before and after the migration should be the same"
+            );
+
+            ensure!(Pallet::<T>::on_chain_storage_version() == 3, "wrong storage version");
+
+            Ok(())
+        }
+    }
+}
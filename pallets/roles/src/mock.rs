@@ -2,22 +2,28 @@ use super::*;
 
 use sp_core::H256;
 use sp_io::TestExternalities;
-use sp_std::{collections::btree_set::BTreeSet, prelude::Vec};
+use sp_std::prelude::Vec;
 
 use frame_support::{
     assert_ok,
     dispatch::{DispatchError, DispatchResult},
     parameter_types,
-    traits::{ConstU32, Everything},
+    traits::{ConstU32, ConstU64, Everything, GenesisBuild},
 };
 use sp_runtime::{
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
 };
 
-use pallet_permissions::{SpacePermission, SpacePermission as SP, SpacePermissions};
+use pallet_permissions::{
+    NamespacedPermission, NamespacedPermissionsRegistry, PermissionNamespace, SpacePermission,
+    SpacePermission as SP, SpacePermissionSet, SpacePermissions,
+};
 use subsocial_support::{
-    traits::{SpaceFollowsProvider, SpacePermissionsProvider as SpacePermissionsProviderT},
+    traits::{
+        DomainsProvider, SpaceFollowsProvider,
+        SpacePermissionsProvider as SpacePermissionsProviderT,
+    },
     Content, SpaceId, SpacePermissionsInfo, User,
 };
 
@@ -35,6 +41,7 @@ frame_support::construct_runtime!(
         System: system::{Pallet, Call, Config, Storage, Event<T>},
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        Permissions: pallet_permissions,
         Roles: roles::{Pallet, Call, Storage, Event<T>},
         Spaces: pallet_spaces::{Pallet, Call, Storage, Event<T>},
     }
@@ -107,10 +114,13 @@ use pallet_permissions::default_permissions::DefaultSpacePermissions;
 
 impl pallet_permissions::Config for Test {
     type DefaultSpacePermissions = DefaultSpacePermissions;
+    type NamespacedPermissions = (MockNamespacedPermissions, Roles);
 }
 
 parameter_types! {
   pub const MaxUsersToProcessPerDeleteRole: u16 = 20;
+  pub static DomainOwners: Vec<AccountId> = Vec::new();
+  pub static SpaceOutsiders: Vec<AccountId> = Vec::new();
 }
 
 impl Config for Test {
@@ -121,18 +131,52 @@ impl Config for Test {
     #[cfg(not(feature = "runtime-benchmarks"))]
     type SpacePermissionsProvider = Self;
     type SpaceFollows = Roles;
+    type Currency = Balances;
+    type JoinRequestDeposit = ConstU64<JOIN_REQUEST_DEPOSIT>;
+    type DomainsProvider = MockDomains;
     type IsAccountBlocked = ();
     type IsContentBlocked = ();
+    type SpaceSettings = Spaces;
+    type OnRoleRemoved = ();
+    type RoleProposalLifetime = ConstU64<10>;
+    type RoleProposalDeposit = ConstU64<ROLE_PROPOSAL_DEPOSIT>;
+    type InviteSigner = UintAuthorityId;
+    type InviteSignature = TestSignature;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = MockBenchmarkHelper;
     type WeightInfo = ();
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<AccountId, TestSignature> for MockBenchmarkHelper {
+    fn sign(payload: &[u8]) -> (AccountId, TestSignature) {
+        let signer = 100;
+        (signer, TestSignature(signer, payload.to_vec()))
+    }
+}
+
 impl pallet_spaces::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type Roles = Roles;
     type SpaceFollows = Roles;
     type IsAccountBlocked = ();
     type IsContentBlocked = ();
+    type SpacePosts = ();
+    type ProfileManager = ();
+    type OnSpaceDeleted = Roles;
+    type OnSpaceOwnerChanged = ();
+    type SpaceSettingsHandler = Roles;
+    type SpaceOwnerOrigin = frame_system::EnsureSigned<AccountId>;
+    type SpaceAccounts = ();
+    type Currency = Balances;
+    type BaseSpaceDeposit = ConstU64<0>;
+    type StorageByteDeposit = ConstU64<0>;
     type MaxSpacesPerAccount = ConstU32<100>;
+    type MinHandleLen = ConstU32<5>;
+    type MaxHandleLen = ConstU32<50>;
     type WeightInfo = ();
 }
 
@@ -160,32 +204,79 @@ impl SpacePermissionsProviderT<AccountId, SpacePermissionsInfo<AccountId, SpaceP
 
         Err("mock:NotSpaceOwner".into())
     }
+
+    // Accounts from `SpaceOutsiders` cannot access any space, as if all spaces were members-only
+    fn is_space_accessible_by(id: SpaceId, account: &AccountId) -> bool {
+        valid_space_ids().contains(&id) && !SpaceOutsiders::get().contains(account)
+    }
 }
 
 impl<T: Config> SpaceFollowsProvider for Pallet<T> {
     type AccountId = AccountId;
+    type BlockNumber = BlockNumber;
 
     fn is_space_follower(_account: Self::AccountId, _space_id: u64) -> bool {
         true
     }
+
+    fn space_followers_count(_space_id: u64) -> u32 {
+        0
+    }
+
+    fn space_followed_at(_account: Self::AccountId, _space_id: u64) -> Option<BlockNumber> {
+        Some(1)
+    }
+}
+
+pub struct MockDomains;
+
+impl DomainsProvider<AccountId> for MockDomains {
+    fn owns_any_domain(account: &AccountId) -> bool {
+        DomainOwners::get().contains(account)
+    }
+}
+
+pub(crate) const TEST_PERMISSIONS_NAMESPACE: PermissionNamespace = *b"testperm";
+
+/// Declares two namespaced permissions with indexes `0` and `1`.
+pub struct MockNamespacedPermissions;
+
+impl NamespacedPermissionsRegistry for MockNamespacedPermissions {
+    fn is_permission_declared(permission: &NamespacedPermission) -> bool {
+        permission.namespace == TEST_PERMISSIONS_NAMESPACE && permission.index < 2
+    }
+}
+
+pub(crate) fn namespaced_permission(index: u8) -> SpacePermission {
+    NamespacedPermission::new(TEST_PERMISSIONS_NAMESPACE, index).into()
 }
 
 pub struct ExtBuilder;
 
 impl ExtBuilder {
-    pub fn build() -> TestExternalities {
-        let storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    fn storage() -> sp_runtime::Storage {
+        let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
-        let mut ext = TestExternalities::from(storage);
+        // Requesters of join requests and proposers of role changes must be able
+        // to reserve deposits
+        pallet_balances::GenesisConfig::<Test> {
+            balances: vec![(ACCOUNT1, 100), (ACCOUNT2, 100), (ACCOUNT3, 100)],
+        }
+        .assimilate_storage(&mut storage)
+        .unwrap();
+
+        storage
+    }
+
+    pub fn build() -> TestExternalities {
+        let mut ext = TestExternalities::from(Self::storage());
         ext.execute_with(|| System::set_block_number(1));
 
         ext
     }
 
     pub fn build_with_a_few_roles_granted_to_account2() -> TestExternalities {
-        let storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
-
-        let mut ext = TestExternalities::from(storage);
+        let mut ext = TestExternalities::from(Self::storage());
         ext.execute_with(|| {
             System::set_block_number(1);
             let user = User::Account(ACCOUNT2);
@@ -195,6 +286,28 @@ impl ExtBuilder {
 
             assert_ok!(_grant_role(None, Some(ROLE1), Some(vec![user.clone()])));
             assert_ok!(_grant_role(None, Some(ROLE2), Some(vec![user])));
+
+            // ACCOUNT2 manages roles via RoleId 2, so it must rank above unranked roles
+            assert_ok!(_set_role_rank(None, Some(ROLE2), 1));
+        });
+
+        ext
+    }
+
+    /// Custom ext configuration, where the space requires two approvals of role changes,
+    /// ACCOUNT2 manages roles via RoleId 1 of rank 1, and ACCOUNT3 has RoleId 2 with
+    /// `UpdateOwnPosts`
+    pub fn build_with_required_role_approvals() -> TestExternalities {
+        let mut ext = Self::build();
+        ext.execute_with(|| {
+            assert_ok!(_create_default_role()); // RoleId 1
+            assert_ok!(_create_role(None, None, None, None, Some(vec![SP::UpdateOwnPosts]))); // RoleId 2
+
+            assert_ok!(_grant_default_role());
+            assert_ok!(_grant_role(None, Some(ROLE2), Some(vec![User::Account(ACCOUNT3)])));
+            assert_ok!(_set_role_rank(None, Some(ROLE1), 1));
+
+            set_required_role_approvals(2);
         });
 
         ext
@@ -210,6 +323,11 @@ pub(crate) const ROLE2: RoleId = 2;
 pub(crate) const ROLE3: RoleId = 3;
 pub(crate) const ROLE4: RoleId = 4;
 
+pub(crate) const ROLE_PROPOSAL1: RoleProposalId = 1;
+
+pub(crate) const JOIN_REQUEST_DEPOSIT: Balance = 10;
+pub(crate) const ROLE_PROPOSAL_DEPOSIT: Balance = 5;
+
 pub(crate) const SPACE1: SpaceId = 1;
 pub(crate) const SPACE2: SpaceId = 2;
 
@@ -252,7 +370,7 @@ pub(crate) fn permission_set_empty() -> Vec<SpacePermission> {
 pub(crate) fn role_update(
     disabled: Option<bool>,
     content: Option<Content>,
-    permissions: Option<BTreeSet<SpacePermission>>,
+    permissions: Option<SpacePermissionSet>,
 ) -> RoleUpdate {
     RoleUpdate { disabled, content, permissions }
 }
@@ -274,9 +392,16 @@ pub(crate) fn _create_role(
         time_to_live.unwrap_or_default(), // Should return 'None'
         content.unwrap_or_else(self::default_role_content_ipfs),
         permissions.unwrap_or_else(self::permission_set_default),
+        0,
     )
 }
 
+pub(crate) const ROLE_TTL: BlockNumber = 10;
+
+pub(crate) fn _create_expiring_role() -> DispatchResult {
+    _create_role(None, None, Some(Some(ROLE_TTL)), None, None)
+}
+
 pub(crate) fn _update_default_role() -> DispatchResult {
     _update_role(None, None, None)
 }
@@ -307,11 +432,25 @@ pub(crate) fn _grant_role(
     origin: Option<RuntimeOrigin>,
     role_id: Option<RoleId>,
     users: Option<Vec<User<AccountId>>>,
+) -> DispatchResult {
+    _grant_role_until(origin, role_id, users, None)
+}
+
+pub(crate) fn _grant_default_role_until(expires_at: BlockNumber) -> DispatchResult {
+    _grant_role_until(None, None, None, Some(expires_at))
+}
+
+pub(crate) fn _grant_role_until(
+    origin: Option<RuntimeOrigin>,
+    role_id: Option<RoleId>,
+    users: Option<Vec<User<AccountId>>>,
+    expires_at: Option<BlockNumber>,
 ) -> DispatchResult {
     Roles::grant_role(
         origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
         role_id.unwrap_or(ROLE1),
         users.unwrap_or_else(|| vec![User::Account(ACCOUNT2)]),
+        expires_at,
     )
 }
 
@@ -343,3 +482,114 @@ pub(crate) fn _delete_role(origin: Option<RuntimeOrigin>, role_id: Option<RoleId
         UsersByRoleId::<Test>::get(role_id).len() as u32,
     )
 }
+
+pub(crate) fn _set_role_rank(
+    origin: Option<RuntimeOrigin>,
+    role_id: Option<RoleId>,
+    rank: RoleRank,
+) -> DispatchResult {
+    Roles::set_role_rank(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        role_id.unwrap_or(ROLE1),
+        rank,
+    )
+}
+
+pub(crate) fn _set_role_condition(
+    origin: Option<RuntimeOrigin>,
+    role_id: Option<RoleId>,
+    condition: Option<RoleConditionOf<Test>>,
+) -> DispatchResult {
+    Roles::set_role_condition(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        role_id.unwrap_or(ROLE1),
+        condition,
+    )
+}
+
+pub(crate) fn _refresh_conditional_role(account: AccountId) -> DispatchResult {
+    Roles::refresh_conditional_role(RuntimeOrigin::signed(ACCOUNT1), ROLE1, account)
+}
+
+pub(crate) fn set_required_role_approvals(approvals: u32) {
+    let value = approvals.encode().try_into().expect("qed; setting value is short");
+    pallet_spaces::SpaceSettings::<Test>::insert(SPACE1, RoleApprovalsSetting::<Test>::KEY, value);
+}
+
+pub(crate) fn _approve_role_proposal(origin: Option<RuntimeOrigin>) -> DispatchResult {
+    Roles::approve_role_proposal(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        ROLE_PROPOSAL1,
+    )
+}
+
+pub(crate) fn _cancel_role_proposal(origin: Option<RuntimeOrigin>) -> DispatchResult {
+    Roles::cancel_role_proposal(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT2)),
+        ROLE_PROPOSAL1,
+    )
+}
+
+pub(crate) fn _set_content_public_key(account: AccountId) -> DispatchResult {
+    Roles::set_content_public_key(RuntimeOrigin::signed(account), Some([account as u8; 32]))
+}
+
+pub(crate) fn _publish_content_key_envelopes(
+    origin: Option<RuntimeOrigin>,
+    key_version: ContentKeyVersion,
+    members: Vec<AccountId>,
+) -> DispatchResult {
+    Roles::publish_content_key_envelopes(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        SPACE1,
+        key_version,
+        members
+            .into_iter()
+            .map(|member| (member, vec![key_version as u8; 48]))
+            .collect(),
+        10,
+    )
+}
+
+pub(crate) fn _request_to_join_space(origin: Option<RuntimeOrigin>) -> DispatchResult {
+    Roles::request_to_join_space(origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT2)), SPACE1)
+}
+
+pub(crate) fn _approve_join_request(
+    origin: Option<RuntimeOrigin>,
+    role_id: Option<RoleId>,
+) -> DispatchResult {
+    Roles::approve_join_request(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        role_id.unwrap_or(ROLE1),
+        ACCOUNT2,
+    )
+}
+
+pub(crate) fn _cancel_join_request(origin: Option<RuntimeOrigin>) -> DispatchResult {
+    Roles::cancel_join_request(origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT2)), SPACE1)
+}
+
+pub(crate) fn _reject_join_request(origin: Option<RuntimeOrigin>) -> DispatchResult {
+    Roles::reject_join_request(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        SPACE1,
+        ACCOUNT2,
+    )
+}
+
+pub(crate) fn default_invite_voucher() -> InviteVoucher<BlockNumber> {
+    InviteVoucher { role_id: ROLE1, expires_at: 10, nonce: 0 }
+}
+
+/// Sign an invite voucher by a given account, and redeem it by ACCOUNT2.
+pub(crate) fn _redeem_invite_voucher(
+    signer: Option<AccountId>,
+    voucher: Option<InviteVoucher<BlockNumber>>,
+) -> DispatchResult {
+    let signer = signer.unwrap_or(ACCOUNT1);
+    let voucher = voucher.unwrap_or_else(default_invite_voucher);
+    let signature = TestSignature(signer, Roles::invite_voucher_signing_payload(&voucher));
+
+    Roles::redeem_invite_voucher(RuntimeOrigin::signed(ACCOUNT2), signer, voucher, signature)
+}
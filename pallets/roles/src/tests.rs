@@ -1,7 +1,23 @@
 use crate::{mock::*, *};
 
-use frame_support::{assert_noop, assert_ok};
-use subsocial_support::ContentError;
+use frame_support::{
+    assert_noop, assert_ok,
+    pallet_prelude::StorageVersion,
+    traits::{Currency, GetStorageVersion, Hooks, OnRuntimeUpgrade},
+    weights::Weight,
+};
+use pallet_permissions::{
+    PermissionCheckOutcome, PermissionCheckStep, PermissionSource, SpacePermission as SP,
+    SpacePermissions, SpacePermissionsContext,
+};
+use sp_core::H256;
+use sp_runtime::testing::TestSignature;
+
+use crate::weights::WeightInfo;
+use subsocial_support::{
+    traits::{OnSpaceDeleted, SpaceMembersProvider},
+    ContentError,
+};
 
 #[test]
 fn create_role_should_work() {
@@ -539,7 +555,7 @@ fn delete_role_should_fail_with_no_permission() {
 }
 
 #[test]
-fn delete_role_should_fail_with_too_many_users_for_delete_role() {
+fn delete_role_should_work_with_too_many_users_for_delete_role() {
     ExtBuilder::build().execute_with(|| {
         let mut users: Vec<User<AccountId>> = Vec::new();
         for account in 2..23 {
@@ -547,8 +563,23 @@ fn delete_role_should_fail_with_too_many_users_for_delete_role() {
         }
 
         assert_ok!(_create_default_role()); // RoleId 1
-        assert_ok!(_grant_role(None, None, Some(users))); // Grant RoleId 1 to ACCOUNT2-ACCOUNT20
-        assert_noop!(_delete_default_role(), Error::<Test>::TooManyUsersToDeleteRole);
+        assert_ok!(_grant_role(None, None, Some(users.clone()))); // Grant RoleId 1 to ACCOUNT2-ACCOUNT22
+        assert_ok!(_delete_default_role());
+
+        // Role is deleted at once, but still has to be revoked from one user
+        assert!(Roles::role_by_id(ROLE1).is_none());
+        assert!(Roles::role_ids_by_space_id(SPACE1).is_empty());
+        assert!(Roles::role_pending_deletion(ROLE1).is_some());
+        assert_eq!(Roles::users_by_role_id(ROLE1).len(), 1);
+
+        Roles::on_idle(1, Weight::MAX);
+
+        // Check whether storages are cleaned up
+        assert!(Roles::role_pending_deletion(ROLE1).is_none());
+        assert!(Roles::users_by_role_id(ROLE1).is_empty());
+        for user in users {
+            assert!(Roles::role_ids_by_user_in_space(user, SPACE1).is_empty());
+        }
     });
 }
 
@@ -565,3 +596,1573 @@ fn delete_role_should_fail_with_a_few_roles_no_permission() {
         );
     });
 }
+
+#[test]
+fn publish_content_key_envelopes_should_work() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_ok!(_set_content_public_key(ACCOUNT1));
+        assert_ok!(_set_content_public_key(ACCOUNT2));
+
+        // Rotate the first content key
+        assert_ok!(_publish_content_key_envelopes(None, 1, vec![ACCOUNT1, ACCOUNT2]));
+
+        assert_eq!(Roles::content_key_version(SPACE1), 1);
+        let envelope = Roles::content_key_envelope(SPACE1, ACCOUNT2).unwrap();
+        assert_eq!(envelope.key_version, 1);
+        assert_eq!(envelope.encrypted_key.to_vec(), vec![1; 48]);
+    });
+}
+
+#[test]
+fn publish_content_key_envelopes_should_fail_with_invalid_key_version() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_ok!(_set_content_public_key(ACCOUNT2));
+
+        // There is no content key yet, so the only valid version is 1
+        assert_noop!(
+            _publish_content_key_envelopes(None, 0, vec![ACCOUNT2]),
+            Error::<Test>::InvalidContentKeyVersion
+        );
+        assert_noop!(
+            _publish_content_key_envelopes(None, 2, vec![ACCOUNT2]),
+            Error::<Test>::InvalidContentKeyVersion
+        );
+    });
+}
+
+#[test]
+fn publish_content_key_envelopes_should_fail_when_not_a_space_member() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_ok!(_set_content_public_key(ACCOUNT3));
+
+        assert_noop!(
+            _publish_content_key_envelopes(None, 1, vec![ACCOUNT3]),
+            Error::<Test>::NotASpaceMember
+        );
+    });
+}
+
+#[test]
+fn publish_content_key_envelopes_should_fail_when_no_public_key() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_noop!(
+            _publish_content_key_envelopes(None, 1, vec![ACCOUNT2]),
+            Error::<Test>::ContentPublicKeyNotFound
+        );
+    });
+}
+
+#[test]
+fn publish_content_key_envelopes_should_fail_when_no_permission() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_ok!(_set_content_public_key(ACCOUNT2));
+
+        assert_noop!(
+            _publish_content_key_envelopes(
+                Some(RuntimeOrigin::signed(ACCOUNT2)),
+                1,
+                vec![ACCOUNT2]
+            ),
+            Error::<Test>::NoPermissionToManageContentKeys
+        );
+    });
+}
+
+#[test]
+fn content_key_should_be_rotated_when_member_removed() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_ok!(_set_content_public_key(ACCOUNT1));
+        assert_ok!(_set_content_public_key(ACCOUNT2));
+        assert_ok!(_publish_content_key_envelopes(None, 1, vec![ACCOUNT1, ACCOUNT2]));
+
+        // ACCOUNT2 still has ROLE2, so it is still a member
+        assert_ok!(_revoke_role(None, Some(ROLE1), None));
+        assert!(!Roles::is_content_key_rotation_required(SPACE1));
+
+        // ACCOUNT2 is no longer a member of the space
+        assert_ok!(_revoke_role(None, Some(ROLE2), None));
+        assert!(Roles::is_content_key_rotation_required(SPACE1));
+        assert!(Roles::content_key_envelope(SPACE1, ACCOUNT2).is_none());
+
+        // Adding envelopes with the current key version is not allowed until rotation
+        assert_noop!(
+            _publish_content_key_envelopes(None, 1, vec![ACCOUNT1]),
+            Error::<Test>::ContentKeyMustBeRotated
+        );
+
+        assert_ok!(_publish_content_key_envelopes(None, 2, vec![ACCOUNT1]));
+        assert!(!Roles::is_content_key_rotation_required(SPACE1));
+        assert_eq!(Roles::content_key_version(SPACE1), 2);
+    });
+}
+
+#[test]
+fn content_key_rotation_should_fail_without_envelopes_for_remaining_members() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_ok!(_set_content_public_key(ACCOUNT1));
+        assert_ok!(_set_content_public_key(ACCOUNT2));
+        assert_ok!(_publish_content_key_envelopes(None, 1, vec![ACCOUNT1, ACCOUNT2]));
+
+        // ACCOUNT2 is still a member, so it must get an envelope of a rotated key
+        assert_noop!(
+            _publish_content_key_envelopes(None, 2, vec![ACCOUNT1]),
+            Error::<Test>::ContentKeyEnvelopeMissing
+        );
+
+        // Not all of the previous envelopes are declared to be checked
+        assert_noop!(
+            Roles::publish_content_key_envelopes(
+                RuntimeOrigin::signed(ACCOUNT1),
+                SPACE1,
+                2,
+                vec![(ACCOUNT1, vec![2; 48]), (ACCOUNT2, vec![2; 48])],
+                1,
+            ),
+            Error::<Test>::TooManyExistingEnvelopes
+        );
+
+        assert_ok!(_publish_content_key_envelopes(None, 2, vec![ACCOUNT1, ACCOUNT2]));
+        assert_eq!(Roles::content_key_envelope(SPACE1, ACCOUNT2).unwrap().key_version, 2);
+    });
+}
+
+#[test]
+fn content_key_rotation_should_remove_envelopes_of_inactive_members() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_ok!(_set_content_public_key(ACCOUNT1));
+        assert_ok!(_set_content_public_key(ACCOUNT2));
+        assert_ok!(_publish_content_key_envelopes(None, 1, vec![ACCOUNT1, ACCOUNT2]));
+
+        // Both roles of ACCOUNT2 are disabled, so it is no longer an active member
+        let disable = role_update(Some(true), None, None);
+        assert_ok!(_update_role(None, Some(ROLE1), Some(disable.clone())));
+        assert_ok!(_update_role(None, Some(ROLE2), Some(disable)));
+        assert!(!Roles::is_space_member(&ACCOUNT2, SPACE1));
+
+        assert_ok!(_publish_content_key_envelopes(None, 2, vec![ACCOUNT1]));
+        assert!(Roles::content_key_envelope(SPACE1, ACCOUNT2).is_none());
+    });
+}
+
+#[test]
+fn disabling_role_should_remove_content_keys_of_former_members() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_ok!(_set_content_public_key(ACCOUNT2));
+        assert_ok!(_publish_content_key_envelopes(None, 1, vec![ACCOUNT2]));
+
+        // ACCOUNT2 still has ROLE2, so it is still a member
+        let disable = role_update(Some(true), None, None);
+        assert_ok!(_update_role(None, Some(ROLE1), Some(disable.clone())));
+        assert!(Roles::content_key_envelope(SPACE1, ACCOUNT2).is_some());
+        assert!(!Roles::is_content_key_rotation_required(SPACE1));
+
+        // ACCOUNT2 is no longer an active member of the space
+        assert_ok!(_update_role(None, Some(ROLE2), Some(disable)));
+        assert!(Roles::content_key_envelope(SPACE1, ACCOUNT2).is_none());
+        assert!(Roles::is_content_key_rotation_required(SPACE1));
+    });
+}
+
+#[test]
+fn disabling_role_should_remove_content_keys_of_too_many_users_in_on_idle() {
+    ExtBuilder::build().execute_with(|| {
+        let accounts: Vec<AccountId> = (2..23).collect();
+        let users = accounts.iter().copied().map(User::Account).collect();
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_role(None, None, Some(users))); // Grant RoleId 1 to ACCOUNT2-ACCOUNT22
+        for account in accounts.iter() {
+            assert_ok!(_set_content_public_key(*account));
+        }
+        assert_ok!(_publish_content_key_envelopes(None, 1, accounts.clone()));
+
+        assert_ok!(_update_role(None, None, Some(role_update(Some(true), None, None))));
+
+        // Only `MaxUsersToProcessPerDeleteRole` (20) users are checked at once
+        let envelopes_left = accounts
+            .iter()
+            .filter(|account| Roles::content_key_envelope(SPACE1, **account).is_some())
+            .count();
+        assert_eq!(envelopes_left, 1);
+        assert_eq!(DisabledRolesPendingCleanup::<Test>::get(ROLE1), Some(1));
+
+        Roles::on_idle(1, Weight::MAX);
+
+        for account in accounts {
+            assert!(Roles::content_key_envelope(SPACE1, account).is_none());
+        }
+        assert!(DisabledRolesPendingCleanup::<Test>::get(ROLE1).is_none());
+    });
+}
+
+#[test]
+fn is_space_member_should_ignore_expired_role_grants() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_default_role_until(5));
+        assert!(Roles::is_space_member(&ACCOUNT2, SPACE1));
+
+        // The grant has expired, but it is not cleaned up in `on_idle` yet
+        System::set_block_number(5);
+        assert!(!Roles::is_space_member(&ACCOUNT2, SPACE1));
+    });
+}
+
+#[test]
+fn approve_join_request_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_request_to_join_space(None));
+        assert_eq!(
+            Roles::join_request(SPACE1, ACCOUNT2),
+            Some(JoinRequest { created_at: 1, deposit: JOIN_REQUEST_DEPOSIT })
+        );
+        assert_eq!(Balances::reserved_balance(ACCOUNT2), JOIN_REQUEST_DEPOSIT);
+
+        assert_ok!(_approve_join_request(None, None));
+
+        assert!(Roles::join_request(SPACE1, ACCOUNT2).is_none());
+        assert_eq!(Balances::reserved_balance(ACCOUNT2), 0);
+        assert_eq!(Roles::users_by_role_id(ROLE1), vec![User::Account(ACCOUNT2)]);
+        assert_eq!(Roles::role_ids_by_user_in_space(User::Account(ACCOUNT2), SPACE1), vec![ROLE1]);
+    });
+}
+
+#[test]
+fn request_to_join_space_should_fail_when_already_a_member() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_noop!(_request_to_join_space(None), Error::<Test>::AlreadyASpaceMember);
+        assert_noop!(
+            _request_to_join_space(Some(RuntimeOrigin::signed(ACCOUNT1))),
+            Error::<Test>::AlreadyASpaceMember
+        );
+    });
+}
+
+#[test]
+fn request_to_join_space_should_fail_when_request_exists() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_request_to_join_space(None));
+        assert_noop!(_request_to_join_space(None), Error::<Test>::JoinRequestAlreadyExists);
+    });
+}
+
+#[test]
+fn request_to_join_space_should_fail_without_balance_for_deposit() {
+    ExtBuilder::build().execute_with(|| {
+        let account_without_balance = 4;
+        assert_noop!(
+            _request_to_join_space(Some(RuntimeOrigin::signed(account_without_balance))),
+            Error::<Test>::InsufficientBalanceToReserveDeposit
+        );
+    });
+}
+
+#[test]
+fn cancel_join_request_should_unreserve_deposit() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_request_to_join_space(None));
+        assert_ok!(_cancel_join_request(None));
+
+        assert!(Roles::join_request(SPACE1, ACCOUNT2).is_none());
+        assert_eq!(Balances::reserved_balance(ACCOUNT2), 0);
+        assert_noop!(_cancel_join_request(None), Error::<Test>::JoinRequestNotFound);
+    });
+}
+
+#[test]
+fn reject_join_request_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_request_to_join_space(None));
+
+        assert_ok!(_reject_join_request(None));
+        assert_eq!(Balances::reserved_balance(ACCOUNT2), 0);
+
+        assert!(Roles::join_request(SPACE1, ACCOUNT2).is_none());
+        assert_noop!(_approve_join_request(None, None), Error::<Test>::JoinRequestNotFound);
+    });
+}
+
+#[test]
+fn reject_join_request_should_work_with_namespaced_permission() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        // RoleId 2 only lets its' users reject join requests
+        assert_ok!(_create_role(None, None, None, None, Some(vec![REJECT_JOIN_REQUESTS.into()])));
+        assert_ok!(_grant_role(None, Some(ROLE2), Some(vec![User::Account(ACCOUNT3)])));
+        assert_ok!(_request_to_join_space(None));
+
+        // A moderator of join requests can't approve them
+        assert_noop!(
+            _approve_join_request(Some(RuntimeOrigin::signed(ACCOUNT3)), None),
+            Error::<Test>::NoPermissionToManageRoles
+        );
+
+        assert_ok!(_reject_join_request(Some(RuntimeOrigin::signed(ACCOUNT3))));
+        assert!(Roles::join_request(SPACE1, ACCOUNT2).is_none());
+    });
+}
+
+#[test]
+fn approve_and_reject_join_request_should_fail_with_no_permission() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_request_to_join_space(None));
+
+        assert_noop!(
+            _approve_join_request(Some(RuntimeOrigin::signed(ACCOUNT3)), None),
+            Error::<Test>::NoPermissionToManageRoles
+        );
+        assert_noop!(
+            _reject_join_request(Some(RuntimeOrigin::signed(ACCOUNT3))),
+            Error::<Test>::NoPermissionToManageRoles
+        );
+    });
+}
+
+#[test]
+fn redeem_invite_voucher_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_request_to_join_space(None));
+
+        assert_ok!(_redeem_invite_voucher(None, None));
+
+        // A pending join request is not needed anymore
+        assert!(Roles::join_request(SPACE1, ACCOUNT2).is_none());
+        assert_eq!(Roles::users_by_role_id(ROLE1), vec![User::Account(ACCOUNT2)]);
+    });
+}
+
+#[test]
+fn redeem_invite_voucher_should_fail_when_already_used() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_redeem_invite_voucher(None, None));
+
+        assert_noop!(_redeem_invite_voucher(None, None), Error::<Test>::InviteVoucherAlreadyUsed);
+
+        // A voucher with another nonce can be redeemed
+        let voucher = InviteVoucher { nonce: 1, ..default_invite_voucher() };
+        assert_ok!(_redeem_invite_voucher(None, Some(voucher)));
+    });
+}
+
+#[test]
+fn redeem_invite_voucher_should_fail_when_signed_for_another_chain() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        let voucher = default_invite_voucher();
+        let another_genesis_hash = H256::repeat_byte(1);
+        assert_ne!(System::block_hash(0), another_genesis_hash);
+
+        let signature = TestSignature(ACCOUNT1, voucher.signing_payload(&another_genesis_hash));
+        assert_noop!(
+            Roles::redeem_invite_voucher(
+                RuntimeOrigin::signed(ACCOUNT2),
+                ACCOUNT1,
+                voucher,
+                signature
+            ),
+            Error::<Test>::InvalidInviteVoucherSignature
+        );
+    });
+}
+
+#[test]
+fn on_idle_should_remove_expired_used_invite_vouchers() {
+    ExtBuilder::build().execute_with(|| {
+        let voucher = default_invite_voucher();
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_redeem_invite_voucher(None, None));
+        assert_eq!(UsedInviteVouchers::<Test>::iter_prefix(voucher.expires_at).count(), 1);
+
+        // The voucher can still be redeemed before this block, so it must be kept
+        Roles::on_idle(voucher.expires_at - 1, Weight::MAX);
+        assert_eq!(UsedInviteVouchers::<Test>::iter_prefix(voucher.expires_at).count(), 1);
+
+        Roles::on_idle(voucher.expires_at, Weight::MAX);
+        assert_eq!(UsedInviteVouchers::<Test>::iter_prefix(voucher.expires_at).count(), 0);
+        assert_eq!(Roles::next_invite_voucher_expiration_block(), voucher.expires_at + 1);
+    });
+}
+
+#[test]
+fn redeem_invite_voucher_should_fail_when_expired() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        System::set_block_number(10);
+
+        assert_noop!(_redeem_invite_voucher(None, None), Error::<Test>::InviteVoucherExpired);
+    });
+}
+
+#[test]
+fn redeem_invite_voucher_should_fail_with_invalid_signature() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        let voucher = default_invite_voucher();
+        let signature = TestSignature(ACCOUNT3, Roles::invite_voucher_signing_payload(&voucher));
+        assert_noop!(
+            Roles::redeem_invite_voucher(
+                RuntimeOrigin::signed(ACCOUNT2),
+                ACCOUNT1,
+                voucher,
+                signature
+            ),
+            Error::<Test>::InvalidInviteVoucherSignature
+        );
+    });
+}
+
+#[test]
+fn redeem_invite_voucher_should_fail_when_signer_has_no_permission() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        assert_noop!(
+            _redeem_invite_voucher(Some(ACCOUNT3), None),
+            Error::<Test>::NoPermissionToManageRoles
+        );
+    });
+}
+
+#[test]
+fn on_idle_should_delete_expired_role() {
+    ExtBuilder::build().execute_with(|| {
+        let user = User::Account(ACCOUNT2);
+        let expires_at = 1 + ROLE_TTL;
+
+        assert_ok!(_create_expiring_role()); // RoleId 1
+        assert_ok!(_grant_default_role()); // Grant RoleId 1 to ACCOUNT2
+        assert!(RoleIdsExpiringAt::<Test>::contains_key(expires_at, ROLE1));
+
+        // Role is not expired yet
+        Roles::on_idle(expires_at - 1, Weight::MAX);
+        assert!(Roles::role_by_id(ROLE1).is_some());
+
+        Roles::on_idle(expires_at, Weight::MAX);
+
+        // Check whether storages are cleaned up
+        assert!(Roles::role_by_id(ROLE1).is_none());
+        assert!(Roles::users_by_role_id(ROLE1).is_empty());
+        assert!(Roles::role_ids_by_space_id(SPACE1).is_empty());
+        assert!(Roles::role_ids_by_user_in_space(user, SPACE1).is_empty());
+        assert!(!RoleIdsExpiringAt::<Test>::contains_key(expires_at, ROLE1));
+        assert_eq!(Roles::next_role_expiration_block(), expires_at + 1);
+    });
+}
+
+#[test]
+fn on_idle_should_not_delete_expired_role_without_enough_weight() {
+    ExtBuilder::build().execute_with(|| {
+        let expires_at = 1 + ROLE_TTL;
+
+        assert_ok!(_create_expiring_role()); // RoleId 1
+        assert_ok!(_grant_default_role());
+
+        assert_eq!(Roles::on_idle(expires_at, Weight::zero()), Weight::zero());
+        assert!(Roles::role_by_id(ROLE1).is_some());
+        assert!(RoleIdsExpiringAt::<Test>::contains_key(expires_at, ROLE1));
+    });
+}
+
+#[test]
+fn on_idle_should_delete_expired_role_with_many_users_in_a_few_steps() {
+    ExtBuilder::build().execute_with(|| {
+        let expires_at = 1 + ROLE_TTL;
+        let max_users = MaxUsersToProcessPerDeleteRole::get() as u64;
+        let users: Vec<_> = (10..10 + max_users + 5).map(User::Account).collect();
+
+        assert_ok!(_create_expiring_role()); // RoleId 1
+        assert_ok!(_grant_role(None, None, Some(users)));
+
+        // Enough weight to revoke a role from `MaxUsersToProcessPerDeleteRole` users only
+        let step_weight = <() as WeightInfo>::delete_expired_role(max_users as u32);
+
+        Roles::on_idle(expires_at, step_weight);
+        assert!(Roles::role_by_id(ROLE1).is_some());
+        assert_eq!(Roles::users_by_role_id(ROLE1).len(), 5);
+
+        Roles::on_idle(expires_at, step_weight);
+        assert!(Roles::role_by_id(ROLE1).is_none());
+        assert!(Roles::users_by_role_id(ROLE1).is_empty());
+        assert!(!RoleIdsExpiringAt::<Test>::contains_key(expires_at, ROLE1));
+    });
+}
+
+#[test]
+fn on_idle_should_clean_up_deleted_space() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        let user = User::Account(ACCOUNT2);
+
+        assert_ok!(_set_content_public_key(ACCOUNT1));
+        assert_ok!(_set_content_public_key(ACCOUNT2));
+        assert_ok!(_publish_content_key_envelopes(None, 1, vec![ACCOUNT1, ACCOUNT2]));
+        assert_ok!(_request_to_join_space(Some(RuntimeOrigin::signed(ACCOUNT3))));
+
+        Roles::on_space_deleted(&ACCOUNT1, SPACE1);
+
+        // Nothing is cleaned up until the space is processed in `on_idle`
+        assert!(Roles::is_space_pending_cleanup(SPACE1));
+        assert_eq!(Roles::role_ids_by_space_id(SPACE1).len(), 2);
+
+        Roles::on_idle(2, Weight::MAX);
+
+        assert!(!Roles::is_space_pending_cleanup(SPACE1));
+        assert!(Roles::role_ids_by_space_id(SPACE1).is_empty());
+        assert!(Roles::role_by_id(ROLE1).is_none());
+        assert!(Roles::role_by_id(ROLE2).is_none());
+        assert!(Roles::users_by_role_id(ROLE1).is_empty());
+        assert!(Roles::role_ids_by_user_in_space(user, SPACE1).is_empty());
+        assert!(Roles::content_key_envelope(SPACE1, ACCOUNT1).is_none());
+        assert!(Roles::content_key_envelope(SPACE1, ACCOUNT2).is_none());
+        assert!(Roles::join_request(SPACE1, ACCOUNT3).is_none());
+        assert_eq!(Roles::content_key_version(SPACE1), 0);
+        assert!(!Roles::is_content_key_rotation_required(SPACE1));
+    });
+}
+
+#[test]
+fn on_idle_should_clean_up_deleted_space_with_many_users_in_a_few_steps() {
+    ExtBuilder::build().execute_with(|| {
+        let max_users = MaxUsersToProcessPerDeleteRole::get() as u64;
+        let users: Vec<_> = (10..10 + max_users + 5).map(User::Account).collect();
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_role(None, None, Some(users)));
+
+        Roles::on_space_deleted(&ACCOUNT1, SPACE1);
+
+        // Enough weight to revoke a role from `MaxUsersToProcessPerDeleteRole` users only
+        let step_weight = <() as WeightInfo>::delete_expired_role(max_users as u32)
+            .saturating_add(<Test as frame_system::Config>::DbWeight::get().reads(2));
+
+        Roles::on_idle(2, step_weight);
+        assert!(Roles::role_by_id(ROLE1).is_some());
+        assert_eq!(Roles::users_by_role_id(ROLE1).len(), 5);
+
+        Roles::on_idle(3, Weight::MAX);
+        assert!(Roles::role_by_id(ROLE1).is_none());
+        assert!(!Roles::is_space_pending_cleanup(SPACE1));
+    });
+}
+
+#[test]
+fn delete_role_should_remove_role_expiration() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_expiring_role()); // RoleId 1
+        assert_ok!(_delete_default_role());
+
+        assert!(!RoleIdsExpiringAt::<Test>::contains_key(1 + ROLE_TTL, ROLE1));
+    });
+}
+
+#[test]
+fn grant_role_should_work_until_expiration() {
+    ExtBuilder::build().execute_with(|| {
+        let user = User::Account(ACCOUNT2);
+        let expires_at = 10;
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_default_role_until(expires_at)); // Grant RoleId 1 to ACCOUNT2
+
+        assert_eq!(Roles::role_grant_expires_at(ROLE1, &user), Some(expires_at));
+        assert!(Roles::is_role_grant_active(ROLE1, &user));
+        assert_eq!(
+            Roles::active_role_ids_with_permission(
+                user.clone(),
+                SPACE1,
+                SpacePermission::ManageRoles
+            ),
+            vec![ROLE1]
+        );
+
+        System::set_block_number(expires_at);
+
+        // Expired grant is ignored, even before it is pruned
+        assert!(!Roles::is_role_grant_active(ROLE1, &user));
+        assert!(Roles::active_role_ids_with_permission(user, SPACE1, SpacePermission::ManageRoles)
+            .is_empty());
+    });
+}
+
+#[test]
+fn grant_role_should_make_temporary_grant_permanent() {
+    ExtBuilder::build().execute_with(|| {
+        let user = User::Account(ACCOUNT2);
+        let expires_at = 10;
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_default_role_until(expires_at));
+        assert_ok!(_grant_default_role());
+
+        assert!(Roles::role_grant_expires_at(ROLE1, &user).is_none());
+        assert!(!RoleGrantsExpiringAt::<Test>::contains_key(expires_at, (ROLE1, user.clone())));
+        System::assert_has_event(
+            Event::RoleGrantExpirationUpdated {
+                account: ACCOUNT1,
+                role_id: ROLE1,
+                user,
+                expires_at: None,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn grant_role_should_not_emit_expiration_update_when_it_is_unchanged() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_default_role_until(10));
+        assert_ok!(_grant_default_role_until(10));
+
+        assert!(!System::events().into_iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::Roles(Event::RoleGrantExpirationUpdated { .. })
+        )));
+    });
+}
+
+#[test]
+fn grant_role_should_fail_with_invalid_grant_expiration() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        assert_noop!(_grant_default_role_until(1), Error::<Test>::InvalidGrantExpiration);
+    });
+}
+
+#[test]
+fn revoke_role_should_remove_grant_expiration() {
+    ExtBuilder::build().execute_with(|| {
+        let user = User::Account(ACCOUNT2);
+        let expires_at = 10;
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_default_role_until(expires_at));
+        assert_ok!(_revoke_default_role());
+
+        assert!(Roles::role_grant_expires_at(ROLE1, &user).is_none());
+        assert!(!RoleGrantsExpiringAt::<Test>::contains_key(expires_at, (ROLE1, user)));
+    });
+}
+
+#[test]
+fn on_idle_should_revoke_expired_role_grant() {
+    ExtBuilder::build().execute_with(|| {
+        let user = User::Account(ACCOUNT2);
+        let expires_at = 10;
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_default_role_until(expires_at));
+
+        Roles::on_idle(expires_at - 1, Weight::MAX);
+        assert_eq!(Roles::users_by_role_id(ROLE1), vec![user.clone()]);
+
+        Roles::on_idle(expires_at, Weight::MAX);
+
+        // Check whether storages are cleaned up
+        assert!(Roles::role_by_id(ROLE1).is_some());
+        assert!(Roles::users_by_role_id(ROLE1).is_empty());
+        assert!(Roles::role_ids_by_user_in_space(&user, SPACE1).is_empty());
+        assert!(Roles::role_grant_expires_at(ROLE1, &user).is_none());
+        assert!(!RoleGrantsExpiringAt::<Test>::contains_key(expires_at, (ROLE1, user)));
+        assert_eq!(Roles::next_role_grant_expiration_block(), expires_at + 1);
+    });
+}
+
+#[test]
+fn set_role_rank_should_work() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_set_role_rank(None, None, 10));
+
+        assert_eq!(Roles::role_rank(ROLE1), 10);
+    });
+}
+
+#[test]
+fn set_role_rank_should_fail_when_new_rank_is_not_below_manager_rank() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        // ACCOUNT2 manages roles via RoleId 2
+        assert_ok!(_set_role_rank(None, Some(ROLE2), 5));
+
+        assert_noop!(
+            _set_role_rank(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE1), 5),
+            Error::<Test>::RoleRankTooHigh
+        );
+        assert_ok!(_set_role_rank(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE1), 4));
+        assert_eq!(Roles::role_rank(ROLE1), 4);
+    });
+}
+
+#[test]
+fn update_role_should_fail_when_role_is_not_below_manager_rank() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_ok!(_set_role_rank(None, Some(ROLE1), 5));
+        assert_ok!(_set_role_rank(None, Some(ROLE2), 5));
+
+        assert_noop!(
+            _update_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE1), None),
+            Error::<Test>::RoleRankTooHigh
+        );
+        assert_noop!(
+            _grant_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2), None),
+            Error::<Test>::RoleRankTooHigh
+        );
+    });
+}
+
+#[test]
+fn unranked_manager_should_not_manage_any_roles() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        // ACCOUNT2 manages roles via RoleId 2, which is not ranked anymore,
+        // while the space still has a ranked role
+        assert_ok!(_set_role_rank(None, Some(ROLE1), 1));
+        assert_ok!(_set_role_rank(None, Some(ROLE2), 0));
+
+        assert_noop!(
+            _revoke_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE1), None),
+            Error::<Test>::RoleRankTooHigh
+        );
+        assert_noop!(
+            _create_role(Some(RuntimeOrigin::signed(ACCOUNT2)), None, None, None, None),
+            Error::<Test>::RoleRankTooHigh
+        );
+    });
+}
+
+#[test]
+fn manager_should_manage_unranked_roles_when_space_has_no_ranked_roles() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        // ACCOUNT2 manages roles via RoleId 2, which is the only ranked role
+        assert_ok!(_set_role_rank(None, Some(ROLE2), 0));
+        assert_eq!(Roles::ranked_roles_count(SPACE1), 0);
+
+        assert_ok!(_create_role(Some(RuntimeOrigin::signed(ACCOUNT2)), None, None, None, None));
+        assert_ok!(_revoke_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE1), None));
+
+        // Only the space owner can introduce ranks
+        assert_noop!(
+            _set_role_rank(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE1), 1),
+            Error::<Test>::RoleRankTooHigh
+        );
+    });
+}
+
+#[test]
+fn ranked_roles_count_should_follow_role_ranks() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_eq!(Roles::ranked_roles_count(SPACE1), 1);
+
+        assert_ok!(_set_role_rank(None, Some(ROLE1), 3));
+        assert_ok!(_set_role_rank(None, Some(ROLE1), 2));
+        assert_eq!(Roles::ranked_roles_count(SPACE1), 2);
+
+        assert_ok!(_delete_role(None, Some(ROLE1)));
+        assert_eq!(Roles::ranked_roles_count(SPACE1), 1);
+
+        assert_ok!(_set_role_rank(None, Some(ROLE2), 0));
+        assert_eq!(Roles::ranked_roles_count(SPACE1), 0);
+        assert!(!RankedRolesCountBySpaceId::<Test>::contains_key(SPACE1));
+    });
+}
+
+#[test]
+fn create_role_should_work_with_rank() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_ok!(_set_role_rank(None, Some(ROLE2), 5));
+
+        assert_ok!(Roles::create_role(
+            RuntimeOrigin::signed(ACCOUNT2),
+            SPACE1,
+            None,
+            default_role_content_ipfs(),
+            permission_set_default(),
+            4,
+        )); // RoleId 3
+
+        assert_eq!(Roles::role_rank(ROLE3), 4);
+    });
+}
+
+#[test]
+fn create_role_should_fail_when_rank_is_not_below_manager_rank() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_ok!(_set_role_rank(None, Some(ROLE2), 5));
+
+        assert_noop!(
+            Roles::create_role(
+                RuntimeOrigin::signed(ACCOUNT2),
+                SPACE1,
+                None,
+                default_role_content_ipfs(),
+                permission_set_default(),
+                5,
+            ),
+            Error::<Test>::RoleRankTooHigh
+        );
+    });
+}
+
+#[test]
+fn create_role_should_fail_with_permissions_not_held_by_manager() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_noop!(
+            _create_role(
+                Some(RuntimeOrigin::signed(ACCOUNT2)),
+                None,
+                None,
+                None,
+                Some(vec![SpacePermission::UpdateSpace])
+            ),
+            Error::<Test>::CannotGivePermissionsNotHeld
+        );
+    });
+}
+
+#[test]
+fn refresh_conditional_role_should_grant_and_revoke_role_by_balance() {
+    ExtBuilder::build().execute_with(|| {
+        let user = User::Account(ACCOUNT3);
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_set_role_condition(None, None, Some(RoleCondition::MinBalance(50))));
+
+        Balances::make_free_balance_be(&ACCOUNT3, 100);
+        assert_ok!(_refresh_conditional_role(ACCOUNT3));
+        assert_eq!(Roles::users_by_role_id(ROLE1), vec![user.clone()]);
+        assert_eq!(Roles::role_ids_by_user_in_space(&user, SPACE1), vec![ROLE1]);
+
+        Balances::make_free_balance_be(&ACCOUNT3, 10);
+        assert_ok!(_refresh_conditional_role(ACCOUNT3));
+        assert!(Roles::users_by_role_id(ROLE1).is_empty());
+        assert!(Roles::role_ids_by_user_in_space(&user, SPACE1).is_empty());
+        assert!(!ConditionalRoleGrants::<Test>::contains_key(ROLE1, &user));
+    });
+}
+
+#[test]
+fn refresh_conditional_role_should_not_revoke_role_granted_by_manager() {
+    ExtBuilder::build().execute_with(|| {
+        let user = User::Account(ACCOUNT3);
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_set_role_condition(None, None, Some(RoleCondition::MinBalance(50))));
+        assert_ok!(_grant_role(None, None, Some(vec![user.clone()])));
+
+        Balances::make_free_balance_be(&ACCOUNT3, 10);
+        assert_noop!(_refresh_conditional_role(ACCOUNT3), Error::<Test>::NothingToRefresh);
+        assert_eq!(Roles::users_by_role_id(ROLE1), vec![user]);
+    });
+}
+
+#[test]
+fn refresh_conditional_role_should_not_revoke_role_granted_again_by_manager() {
+    ExtBuilder::build().execute_with(|| {
+        let user = User::Account(ACCOUNT3);
+
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_set_role_condition(None, None, Some(RoleCondition::MinBalance(50))));
+
+        Balances::make_free_balance_be(&ACCOUNT3, 100);
+        assert_ok!(_refresh_conditional_role(ACCOUNT3));
+        assert!(ConditionalRoleGrants::<Test>::contains_key(ROLE1, &user));
+
+        // The role is granted by a manager too, so it does not depend on the condition anymore
+        assert_ok!(_grant_role(None, None, Some(vec![user.clone()])));
+        assert!(!ConditionalRoleGrants::<Test>::contains_key(ROLE1, &user));
+
+        Balances::make_free_balance_be(&ACCOUNT3, 10);
+        assert_noop!(_refresh_conditional_role(ACCOUNT3), Error::<Test>::NothingToRefresh);
+        assert_eq!(Roles::users_by_role_id(ROLE1), vec![user]);
+    });
+}
+
+#[test]
+fn set_role_condition_should_fail_with_permissions_not_held_by_manager() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        assert_ok!(_create_role(None, None, None, None, Some(vec![SP::UpdateSpace]))); // RoleId 3
+
+        assert_noop!(
+            _set_role_condition(
+                Some(RuntimeOrigin::signed(ACCOUNT2)),
+                Some(ROLE3),
+                Some(RoleCondition::OwnsDomain)
+            ),
+            Error::<Test>::CannotGivePermissionsNotHeld
+        );
+    });
+}
+
+#[test]
+fn refresh_conditional_role_should_grant_role_to_domain_owner() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_set_role_condition(None, None, Some(RoleCondition::OwnsDomain)));
+
+        assert_noop!(_refresh_conditional_role(ACCOUNT3), Error::<Test>::NothingToRefresh);
+
+        DomainOwners::set(vec![ACCOUNT3]);
+        assert_ok!(_refresh_conditional_role(ACCOUNT3));
+        assert_eq!(Roles::users_by_role_id(ROLE1), vec![User::Account(ACCOUNT3)]);
+    });
+}
+
+#[test]
+fn refresh_conditional_role_should_grant_role_to_long_time_follower() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_set_role_condition(None, None, Some(RoleCondition::FollowsSpaceFor(10))));
+
+        // All accounts follow a space since block 1 in mock
+        assert_noop!(_refresh_conditional_role(ACCOUNT3), Error::<Test>::NothingToRefresh);
+
+        System::set_block_number(11);
+        assert_ok!(_refresh_conditional_role(ACCOUNT3));
+        assert_eq!(Roles::users_by_role_id(ROLE1), vec![User::Account(ACCOUNT3)]);
+    });
+}
+
+#[test]
+fn refresh_conditional_role_should_fail_when_role_has_no_condition() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        assert_noop!(_refresh_conditional_role(ACCOUNT3), Error::<Test>::RoleHasNoCondition);
+    });
+}
+
+#[test]
+fn set_role_condition_should_fail_with_no_permission() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        assert_noop!(
+            _set_role_condition(
+                Some(RuntimeOrigin::signed(ACCOUNT2)),
+                None,
+                Some(RoleCondition::OwnsDomain)
+            ),
+            Error::<Test>::NoPermissionToManageRoles
+        );
+    });
+}
+
+#[test]
+fn revoke_role_should_create_proposal_when_approvals_required() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        let user = User::Account(ACCOUNT3);
+
+        assert_ok!(_revoke_role(
+            Some(RuntimeOrigin::signed(ACCOUNT2)),
+            Some(ROLE2),
+            Some(vec![user.clone()])
+        ));
+
+        // The role is not revoked until the proposal is approved
+        assert_eq!(Roles::users_by_role_id(ROLE2), vec![user.clone()]);
+
+        let proposal = Roles::role_proposal_by_id(ROLE_PROPOSAL1).unwrap();
+        assert_eq!(proposal.proposer, ACCOUNT2);
+        assert_eq!(proposal.role_id, ROLE2);
+        assert_eq!(proposal.approvals, vec![ACCOUNT2]);
+
+        assert_ok!(_approve_role_proposal(None));
+
+        assert!(Roles::users_by_role_id(ROLE2).is_empty());
+        assert!(Roles::role_ids_by_user_in_space(&user, SPACE1).is_empty());
+        assert!(Roles::role_proposal_by_id(ROLE_PROPOSAL1).is_none());
+    });
+}
+
+#[test]
+fn delete_role_should_create_proposal_when_approvals_required() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        assert_ok!(_delete_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2)));
+        assert!(Roles::role_by_id(ROLE2).is_some());
+
+        assert_ok!(_approve_role_proposal(None));
+        assert!(Roles::role_by_id(ROLE2).is_none());
+        assert!(Roles::users_by_role_id(ROLE2).is_empty());
+    });
+}
+
+#[test]
+fn grant_role_should_create_proposal_only_for_managing_roles() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        let user = User::Account(ACCOUNT3);
+
+        // RoleId 2 does not have ManageRoles permission, so it is granted at once
+        assert_ok!(_revoke_role(None, Some(ROLE2), Some(vec![user.clone()])));
+        assert_ok!(_grant_role(
+            Some(RuntimeOrigin::signed(ACCOUNT2)),
+            Some(ROLE2),
+            Some(vec![user.clone()])
+        ));
+        assert_eq!(Roles::users_by_role_id(ROLE2), vec![user.clone()]);
+        assert!(Roles::role_proposal_by_id(ROLE_PROPOSAL1).is_none());
+
+        // RoleId 3 has ManageRoles permission and ranks below the role of ACCOUNT2
+        assert_ok!(_create_default_role());
+        assert_ok!(_grant_role(
+            Some(RuntimeOrigin::signed(ACCOUNT2)),
+            Some(ROLE3),
+            Some(vec![user.clone()])
+        ));
+        assert!(Roles::users_by_role_id(ROLE3).is_empty());
+
+        assert_ok!(_approve_role_proposal(None));
+        assert_eq!(Roles::users_by_role_id(ROLE3), vec![user]);
+    });
+}
+
+#[test]
+fn update_role_should_create_proposal_when_it_disables_role() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        let disable = role_update(Some(true), None, None);
+        assert_ok!(_update_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2), Some(disable)));
+        assert!(!Roles::role_by_id(ROLE2).unwrap().disabled);
+
+        assert_ok!(_approve_role_proposal(None));
+        assert!(Roles::role_by_id(ROLE2).unwrap().disabled);
+    });
+}
+
+#[test]
+fn update_role_should_create_proposal_when_it_takes_permissions_away() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        let permissions = vec![SP::ManageRoles, SP::UpdateOwnPosts];
+        assert_ok!(_create_role(None, None, None, None, Some(permissions))); // RoleId 3
+
+        let strip = role_update(None, None, Some(vec![SP::UpdateOwnPosts].into_iter().collect()));
+        assert_ok!(_update_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE3), Some(strip)));
+        assert!(Roles::role_by_id(ROLE3).unwrap().permissions.contains(&SP::ManageRoles));
+
+        assert_ok!(_approve_role_proposal(None));
+        assert!(!Roles::role_by_id(ROLE3).unwrap().permissions.contains(&SP::ManageRoles));
+    });
+}
+
+#[test]
+fn update_role_should_update_content_at_once_when_approvals_required() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        let update = role_update(None, Some(updated_role_content_ipfs()), None);
+        assert_ok!(_update_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2), Some(update)));
+
+        assert_eq!(Roles::role_by_id(ROLE2).unwrap().content, updated_role_content_ipfs());
+        assert!(Roles::role_proposal_by_id(ROLE_PROPOSAL1).is_none());
+    });
+}
+
+#[test]
+fn set_role_condition_should_create_proposal_for_managing_roles() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 3
+
+        let condition = RoleCondition::MinBalance(50);
+        assert_ok!(_set_role_condition(
+            Some(RuntimeOrigin::signed(ACCOUNT2)),
+            Some(ROLE3),
+            Some(condition.clone())
+        ));
+        assert!(Roles::role_condition(ROLE3).is_none());
+
+        assert_ok!(_approve_role_proposal(None));
+        assert_eq!(Roles::role_condition(ROLE3), Some(condition));
+    });
+}
+
+#[test]
+fn approve_join_request_should_create_proposal_for_managing_roles() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        let requester = 4;
+        let user = User::Account(requester);
+
+        assert_ok!(_create_default_role()); // RoleId 3
+        Balances::make_free_balance_be(&requester, 100);
+        assert_ok!(_request_to_join_space(Some(RuntimeOrigin::signed(requester))));
+
+        assert_ok!(Roles::approve_join_request(RuntimeOrigin::signed(ACCOUNT2), ROLE3, requester));
+        assert!(Roles::users_by_role_id(ROLE3).is_empty());
+
+        assert_ok!(_approve_role_proposal(None));
+        assert_eq!(Roles::users_by_role_id(ROLE3), vec![user]);
+    });
+}
+
+#[test]
+fn refresh_conditional_role_should_create_unapproved_proposal_for_managing_roles() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        let claimer = 4;
+        let user = User::Account(claimer);
+
+        assert_ok!(_create_default_role()); // RoleId 3
+        assert_ok!(_set_role_condition(None, Some(ROLE3), Some(RoleCondition::MinBalance(50))));
+        Balances::make_free_balance_be(&claimer, 100);
+
+        assert_ok!(Roles::refresh_conditional_role(
+            RuntimeOrigin::signed(ACCOUNT3),
+            ROLE3,
+            claimer
+        ));
+        assert!(Roles::users_by_role_id(ROLE3).is_empty());
+
+        let proposal = Roles::role_proposal_by_id(ROLE_PROPOSAL1).unwrap();
+        assert_eq!(proposal.proposer, ACCOUNT3);
+        assert!(proposal.approvals.is_empty());
+
+        // The caller's approval is not counted, so two role managers must approve it
+        assert_ok!(_approve_role_proposal(None));
+        assert!(Roles::users_by_role_id(ROLE3).is_empty());
+
+        assert_ok!(_approve_role_proposal(Some(RuntimeOrigin::signed(ACCOUNT2))));
+        assert_eq!(Roles::users_by_role_id(ROLE3), vec![user.clone()]);
+        assert!(ConditionalRoleGrants::<Test>::contains_key(ROLE3, &user));
+    });
+}
+
+#[test]
+fn role_changes_by_space_owner_should_not_require_approvals() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        assert_ok!(_revoke_role(None, Some(ROLE2), Some(vec![User::Account(ACCOUNT3)])));
+        assert!(Roles::users_by_role_id(ROLE2).is_empty());
+
+        assert_ok!(_delete_role(None, Some(ROLE2)));
+        assert!(Roles::role_by_id(ROLE2).is_none());
+        assert!(Roles::role_proposal_by_id(ROLE_PROPOSAL1).is_none());
+    });
+}
+
+#[test]
+fn approve_role_proposal_should_fail_when_already_approved() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        assert_ok!(_delete_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2)));
+
+        assert_noop!(
+            _approve_role_proposal(Some(RuntimeOrigin::signed(ACCOUNT2))),
+            Error::<Test>::RoleProposalAlreadyApproved
+        );
+    });
+}
+
+#[test]
+fn approve_role_proposal_should_fail_with_no_permission() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        assert_ok!(_delete_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2)));
+
+        assert_noop!(
+            _approve_role_proposal(Some(RuntimeOrigin::signed(ACCOUNT3))),
+            Error::<Test>::NoPermissionToManageRoles
+        );
+    });
+}
+
+#[test]
+fn approve_role_proposal_should_fail_when_expired() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        assert_ok!(_delete_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2)));
+
+        System::set_block_number(11);
+        assert_noop!(_approve_role_proposal(None), Error::<Test>::RoleProposalExpired);
+    });
+}
+
+#[test]
+fn cancel_role_proposal_should_work() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        assert_ok!(_delete_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2)));
+
+        assert_noop!(
+            _cancel_role_proposal(Some(RuntimeOrigin::signed(ACCOUNT3))),
+            Error::<Test>::NotARoleProposer
+        );
+
+        assert_ok!(_cancel_role_proposal(None));
+        assert!(Roles::role_proposal_by_id(ROLE_PROPOSAL1).is_none());
+        assert!(Roles::role_by_id(ROLE2).is_some());
+    });
+}
+
+#[test]
+fn cancel_role_proposal_should_work_for_anyone_when_expired() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        assert_ok!(_delete_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2)));
+
+        System::set_block_number(11);
+        assert_ok!(_cancel_role_proposal(Some(RuntimeOrigin::signed(ACCOUNT3))));
+        assert!(Roles::role_proposal_by_id(ROLE_PROPOSAL1).is_none());
+    });
+}
+
+#[test]
+fn role_proposal_should_reserve_deposit_until_executed() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        let user = User::Account(ACCOUNT3);
+        assert_ok!(_revoke_role(
+            Some(RuntimeOrigin::signed(ACCOUNT2)),
+            Some(ROLE2),
+            Some(vec![user.clone()])
+        ));
+
+        let proposal = Roles::role_proposal_by_id(ROLE_PROPOSAL1).unwrap();
+        assert_eq!(proposal.depositor, ACCOUNT2);
+        assert_eq!(proposal.deposit, ROLE_PROPOSAL_DEPOSIT);
+        assert_eq!(Balances::reserved_balance(ACCOUNT2), ROLE_PROPOSAL_DEPOSIT);
+
+        assert_ok!(_approve_role_proposal(None));
+        assert_eq!(Balances::reserved_balance(ACCOUNT2), 0);
+
+        // The change is applied on behalf of the role manager, whose approval completes it
+        System::assert_has_event(
+            Event::RoleRevoked { account: ACCOUNT1, role_id: ROLE2, users: vec![user] }.into(),
+        );
+    });
+}
+
+#[test]
+fn cancel_role_proposal_should_unreserve_deposit() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        assert_ok!(_delete_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2)));
+        assert_eq!(Balances::reserved_balance(ACCOUNT2), ROLE_PROPOSAL_DEPOSIT);
+
+        assert_ok!(_cancel_role_proposal(None));
+        assert_eq!(Balances::reserved_balance(ACCOUNT2), 0);
+        assert!(!RoleProposalsExpiringAt::<Test>::contains_key(11, ROLE_PROPOSAL1));
+    });
+}
+
+#[test]
+fn role_proposal_should_fail_when_same_change_is_already_proposed() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        assert_ok!(_delete_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2)));
+
+        assert_noop!(
+            _delete_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2)),
+            Error::<Test>::RoleProposalAlreadyExists
+        );
+
+        // An expired proposal is replaced with a new one
+        System::set_block_number(11);
+        assert_ok!(_delete_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2)));
+        assert!(Roles::role_proposal_by_id(ROLE_PROPOSAL1).is_none());
+        assert!(Roles::role_proposal_by_id(ROLE_PROPOSAL1 + 1).is_some());
+        assert_eq!(Balances::reserved_balance(ACCOUNT2), ROLE_PROPOSAL_DEPOSIT);
+    });
+}
+
+#[test]
+fn expired_role_proposals_should_be_removed_in_on_idle() {
+    ExtBuilder::build_with_required_role_approvals().execute_with(|| {
+        assert_ok!(_delete_role(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(ROLE2)));
+
+        Roles::on_idle(10, Weight::MAX);
+        assert!(Roles::role_proposal_by_id(ROLE_PROPOSAL1).is_some());
+
+        Roles::on_idle(11, Weight::MAX);
+        assert!(Roles::role_proposal_by_id(ROLE_PROPOSAL1).is_none());
+        assert!(!RoleProposalsExpiringAt::<Test>::contains_key(11, ROLE_PROPOSAL1));
+        assert_eq!(Balances::reserved_balance(ACCOUNT2), 0);
+        assert_eq!(Roles::next_role_proposal_expiration_block(), 12);
+        System::assert_has_event(Event::RoleProposalExpired { proposal_id: ROLE_PROPOSAL1 }.into());
+    });
+}
+
+#[test]
+fn space_permission_set_should_behave_like_an_ordered_set() {
+    let mut permissions: SpacePermissionSet =
+        vec![SP::UpdateSpace, SP::ManageRoles, SP::SpendTreasury].into_iter().collect();
+
+    assert_eq!(permissions.len(), 3);
+    assert!(permissions.contains(&SP::SpendTreasury));
+    assert!(!permissions.insert(SP::ManageRoles));
+    assert!(permissions.remove(&SP::UpdateSpace));
+    assert!(!permissions.contains(&SP::UpdateSpace));
+    assert_eq!(permissions.iter().collect::<Vec<_>>(), vec![SP::ManageRoles, SP::SpendTreasury]);
+
+    let other: SpacePermissionSet = vec![SP::ManageRoles].into_iter().collect();
+    assert_eq!(permissions.difference(&other).iter().collect::<Vec<_>>(), vec![SP::SpendTreasury]);
+    assert_eq!(other.union(&permissions), permissions);
+}
+
+#[test]
+fn migration_to_v1_should_convert_role_permissions_to_bitsets() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        let role = Roles::role_by_id(ROLE1).unwrap();
+
+        // Store the role as it was before v1, i.e. with permissions as an ordered set.
+        let old_role = (
+            role.created.clone(),
+            role.id,
+            role.space_id,
+            role.disabled,
+            role.expires_at,
+            role.content.clone(),
+            role.permissions.iter().collect::<BTreeSet<_>>(),
+        );
+        frame_support::storage::unhashed::put_raw(
+            &RoleById::<Test>::hashed_key_for(ROLE1),
+            &old_role.encode(),
+        );
+        StorageVersion::new(0).put::<Roles>();
+
+        migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(Roles::role_by_id(ROLE1), Some(role));
+        assert_eq!(Roles::on_chain_storage_version(), Roles::current_storage_version());
+    });
+}
+
+#[test]
+fn migration_to_v3_should_convert_role_permission_bitsets() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2().execute_with(|| {
+        let role = Roles::role_by_id(ROLE1).unwrap();
+
+        // Store the role as it was before v3, i.e. with permissions as a bitset only.
+        let old_role = (
+            role.created.clone(),
+            role.id,
+            role.space_id,
+            role.disabled,
+            role.expires_at,
+            role.content.clone(),
+            role.permissions
+                .iter()
+                .fold(0u64, |bits, permission| bits | 1 << permission.encode()[0]),
+        );
+        frame_support::storage::unhashed::put_raw(
+            &RoleById::<Test>::hashed_key_for(ROLE1),
+            &old_role.encode(),
+        );
+        StorageVersion::new(2).put::<Roles>();
+
+        migration::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+        assert_eq!(Roles::role_by_id(ROLE1), Some(role));
+        assert_eq!(Roles::on_chain_storage_version(), 3);
+    });
+}
+
+#[test]
+fn migration_to_v2_should_index_expiring_roles() {
+    ExtBuilder::build().execute_with(|| {
+        let expires_at = 1 + ROLE_TTL;
+
+        assert_ok!(_create_expiring_role()); // RoleId 1
+        assert_ok!(_create_expiring_role()); // RoleId 2
+        assert_ok!(_create_default_role()); // RoleId 3
+
+        // Roles created before v2 were not indexed.
+        RoleIdsExpiringAt::<Test>::remove(expires_at, ROLE1);
+        RoleIdsExpiringAt::<Test>::remove(expires_at, ROLE2);
+        StorageVersion::new(1).put::<Roles>();
+
+        // The second role expired before the upgrade, while its' block is already cleaned up.
+        let mut role = Roles::role_by_id(ROLE2).unwrap();
+        role.expires_at = Some(expires_at - 5);
+        RoleById::<Test>::insert(ROLE2, role);
+        System::set_block_number(expires_at - 2);
+        NextRoleExpirationBlock::<Test>::put(expires_at - 1);
+
+        migration::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+        assert!(RoleIdsExpiringAt::<Test>::contains_key(expires_at, ROLE1));
+        assert!(RoleIdsExpiringAt::<Test>::contains_key(expires_at - 1, ROLE2));
+        assert_eq!(RoleIdsExpiringAt::<Test>::iter().count(), 2);
+        assert_eq!(Roles::on_chain_storage_version(), 2);
+
+        Roles::on_idle(expires_at, Weight::MAX);
+        assert!(Roles::role_by_id(ROLE1).is_none());
+        assert!(Roles::role_by_id(ROLE2).is_none());
+        assert!(Roles::role_by_id(ROLE3).is_some());
+    });
+}
+
+#[test]
+fn create_role_should_work_with_namespaced_permissions() {
+    ExtBuilder::build().execute_with(|| {
+        let permission = namespaced_permission(0);
+        assert_ok!(_create_role(None, None, None, None, Some(vec![permission]))); // RoleId 1
+        assert_ok!(_grant_default_role()); // Grant RoleId 1 to ACCOUNT2
+
+        assert!(Roles::role_by_id(ROLE1).unwrap().permissions.contains(&permission));
+        assert!(!Roles::role_by_id(ROLE1)
+            .unwrap()
+            .permissions
+            .contains(&namespaced_permission(1)));
+        assert_eq!(
+            Roles::active_role_ids_with_permission(User::Account(ACCOUNT2), SPACE1, permission),
+            vec![ROLE1]
+        );
+    });
+}
+
+#[test]
+fn create_role_should_fail_with_undeclared_namespaced_permission() {
+    ExtBuilder::build().execute_with(|| {
+        assert_noop!(
+            _create_role(None, None, None, None, Some(vec![namespaced_permission(2)])),
+            pallet_permissions::Error::<Test>::UnknownNamespacedPermission
+        );
+    });
+}
+
+#[test]
+fn update_role_should_fail_with_undeclared_namespaced_permission() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+
+        let permissions = vec![SP::ManageRoles, namespaced_permission(2)].into_iter().collect();
+        assert_noop!(
+            _update_role(None, None, Some(self::role_update(None, None, Some(permissions)))),
+            pallet_permissions::Error::<Test>::UnknownNamespacedPermission
+        );
+    });
+}
+
+#[test]
+fn space_permissions_should_resolve_namespaced_permissions() {
+    ExtBuilder::build().execute_with(|| {
+        let permission = namespaced_permission(1);
+        let ctx = |space_perms| SpacePermissionsContext {
+            space_id: SPACE1,
+            is_space_owner: false,
+            is_space_follower: true,
+            space_perms: Some(space_perms),
+        };
+
+        let follower_can = SpacePermissions {
+            follower: Some(vec![permission].into_iter().collect()),
+            ..Default::default()
+        };
+        assert_eq!(
+            pallet_permissions::Pallet::<Test>::has_user_a_space_permission(
+                ctx(follower_can),
+                permission
+            ),
+            Some(true)
+        );
+
+        let nobody_can = SpacePermissions {
+            none: Some(vec![permission].into_iter().collect()),
+            ..Default::default()
+        };
+        assert_eq!(
+            pallet_permissions::Pallet::<Test>::has_user_a_space_permission(
+                ctx(nobody_can),
+                permission
+            ),
+            Some(false)
+        );
+    });
+}
+
+fn check_step(
+    source: PermissionSource<RoleId>,
+    outcome: PermissionCheckOutcome,
+) -> PermissionCheckStep<RoleId> {
+    PermissionCheckStep { source, outcome }
+}
+
+#[test]
+fn trace_account_space_permission_should_explain_granted_role() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_default_role()); // Grant RoleId 1 to ACCOUNT2
+
+        let trace =
+            Roles::trace_account_space_permission(ACCOUNT2, SPACE1, SP::ManageRoles).unwrap();
+        assert!(trace.granted);
+        assert_eq!(
+            trace.steps,
+            vec![
+                check_step(PermissionSource::SpacePrivacy, PermissionCheckOutcome::Passed),
+                check_step(PermissionSource::Moderation, PermissionCheckOutcome::Passed),
+                check_step(PermissionSource::Nobody, PermissionCheckOutcome::NotIncluded),
+                check_step(PermissionSource::Everyone, PermissionCheckOutcome::NotIncluded),
+                check_step(PermissionSource::Follower, PermissionCheckOutcome::NotIncluded),
+                check_step(PermissionSource::SpaceOwner, PermissionCheckOutcome::NotApplicable),
+                check_step(PermissionSource::Role(ROLE1), PermissionCheckOutcome::Granted),
+            ]
+        );
+    });
+}
+
+#[test]
+fn trace_account_space_permission_should_explain_disabled_role() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_default_role()); // Grant RoleId 1 to ACCOUNT2
+        assert_ok!(_update_role(None, None, Some(self::role_update(Some(true), None, None))));
+
+        let trace =
+            Roles::trace_account_space_permission(ACCOUNT2, SPACE1, SP::ManageRoles).unwrap();
+        assert!(!trace.granted);
+        assert_eq!(
+            trace.steps.last(),
+            Some(&check_step(PermissionSource::Role(ROLE1), PermissionCheckOutcome::RoleDisabled))
+        );
+    });
+}
+
+#[test]
+fn trace_account_space_permission_should_explain_space_owner() {
+    ExtBuilder::build().execute_with(|| {
+        let trace =
+            Roles::trace_account_space_permission(ACCOUNT1, SPACE1, SP::UpdateSpace).unwrap();
+        assert!(trace.granted);
+        assert_eq!(
+            trace.steps.last(),
+            Some(&check_step(PermissionSource::SpaceOwner, PermissionCheckOutcome::Granted))
+        );
+
+        assert!(Roles::trace_account_space_permission(ACCOUNT1, SPACE2, SP::UpdateSpace).is_err());
+    });
+}
+
+#[test]
+fn trace_account_space_permission_should_stop_when_space_is_not_accessible() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_default_role()); // RoleId 1
+        assert_ok!(_grant_default_role()); // Grant RoleId 1 to ACCOUNT2
+        SpaceOutsiders::set(vec![ACCOUNT2]);
+
+        let trace =
+            Roles::trace_account_space_permission(ACCOUNT2, SPACE1, SP::ManageRoles).unwrap();
+        assert!(!trace.granted);
+        assert_eq!(
+            trace.steps,
+            vec![check_step(PermissionSource::SpacePrivacy, PermissionCheckOutcome::Forbidden)]
+        );
+    });
+}
+
+#[test]
+fn trace_space_permission_should_stop_at_forbidden_permission() {
+    ExtBuilder::build().execute_with(|| {
+        let ctx = SpacePermissionsContext {
+            space_id: SPACE1,
+            is_space_owner: true,
+            is_space_follower: true,
+            space_perms: Some(SpacePermissions {
+                none: Some(vec![SP::UpdateSpace].into_iter().collect()),
+                ..Default::default()
+            }),
+        };
+
+        assert_eq!(
+            pallet_permissions::Pallet::<Test>::trace_space_permission(ctx, SP::UpdateSpace),
+            (
+                Some(false),
+                vec![check_step(PermissionSource::Nobody, PermissionCheckOutcome::Forbidden)]
+            )
+        );
+    });
+}
@@ -4,6 +4,27 @@ pub type RoleId = u64;
 
 pub const FIRST_ROLE_ID: u64 = 1;
 
+/// A rank of a role within its space. Managers can manage only roles that rank below
+/// their highest managing role. Roles are unranked (`0`) by default.
+pub type RoleRank = u32;
+
+/// A namespace of space permissions, that are declared by this pallet.
+pub const ROLES_PERMISSIONS_NAMESPACE: PermissionNamespace = *b"roles/ns";
+
+/// Lets a user reject requests to join a space without `ManageRoles` permission,
+/// e.g. to moderate spam requests.
+pub const REJECT_JOIN_REQUESTS: NamespacedPermission =
+    NamespacedPermission::new(ROLES_PERMISSIONS_NAMESPACE, 0);
+
+/// A public key that a member uses to receive content keys of members-only spaces.
+pub type X25519PublicKey = [u8; 32];
+
+pub type ContentKeyVersion = u32;
+
+pub const MAX_ENCRYPTED_CONTENT_KEY_LEN: u32 = 512;
+
+pub type EncryptedContentKey = BoundedVec<u8, ConstU32<MAX_ENCRYPTED_CONTENT_KEY_LEN>>;
+
 /// Information about a role's permissions, its' containing space, and its' content.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[scale_info(skip_type_params(T))]
@@ -35,9 +56,143 @@ pub struct Role<T: Config> {
     pub permissions: SpacePermissionSet,
 }
 
+pub type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// A condition, that an account must meet to claim a conditional role.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum RoleCondition<Balance, BlockNumber> {
+    /// An account has at least this free balance.
+    MinBalance(Balance),
+    /// An account owns at least one domain.
+    OwnsDomain,
+    /// An account has followed the role's space for at least this many blocks.
+    FollowsSpaceFor(BlockNumber),
+}
+
+pub type RoleConditionOf<T> = RoleCondition<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+pub type RoleProposalId = u64;
+
+pub const FIRST_ROLE_PROPOSAL_ID: u64 = 1;
+
+/// A sensitive change of a role, that may require approvals of several role managers.
+/// See `RoleApprovalsSetting`.
+///
+/// `GrantRoleByCondition` and `RevokeRoleByCondition` are proposed by
+/// `refresh_conditional_role`, and are checked against the role's condition again once approved.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum RoleChange<AccountId, BlockNumber, Balance> {
+    DeleteRole,
+    GrantRole { users: Vec<User<AccountId>>, expires_at: Option<BlockNumber> },
+    RevokeRole { users: Vec<User<AccountId>> },
+    UpdateRole { update: RoleUpdate },
+    SetRoleCondition { condition: Option<RoleCondition<Balance, BlockNumber>> },
+    GrantRoleByCondition { account: AccountId },
+    RevokeRoleByCondition { account: AccountId },
+}
+
+pub type RoleChangeOf<T> = RoleChange<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::BlockNumber,
+    BalanceOf<T>,
+>;
+
+/// A proposed role change, that is applied once it is approved by enough role managers.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct RoleProposal<T: Config> {
+    pub id: RoleProposalId,
+    pub proposer: T::AccountId,
+    pub role_id: RoleId,
+    pub change: RoleChangeOf<T>,
+
+    /// Role managers who have approved this proposal, including its' proposer,
+    /// unless the proposal is created by `refresh_conditional_role`.
+    pub approvals: Vec<T::AccountId>,
+
+    /// A block number starting from which this proposal cannot be approved anymore.
+    pub expires_at: T::BlockNumber,
+
+    /// An account, that the deposit of this proposal is reserved from. It is the proposer,
+    /// unless the proposal is created by redeeming an invite voucher.
+    pub depositor: T::AccountId,
+
+    /// The amount held on deposit, until this proposal is executed, cancelled
+    /// or removed after it expires.
+    pub deposit: BalanceOf<T>,
+}
+
+/// A space setting that defines how many role managers must approve sensitive role changes
+/// in a given space: deleting, disabling or revoking a role, taking permissions away from it,
+/// and granting `ManageRoles` permission, either directly or via a role condition.
+/// If it is less than two (by default), such changes are applied at once.
+/// Changes made by the space owner are always applied at once.
+pub struct RoleApprovalsSetting<T>(PhantomData<T>);
+
+impl<T: Config> SpaceSetting for RoleApprovalsSetting<T> {
+    const KEY: SpaceSettingKey = *b"roles/minApprovs";
+
+    type Value = u32;
+
+    fn default_value() -> Self::Value {
+        0
+    }
+}
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct RoleUpdate {
     pub disabled: Option<bool>,
     pub content: Option<Content>,
     pub permissions: Option<SpacePermissionSet>,
 }
+
+/// A space content key encrypted with a member's X25519 public key.
+/// Encryption and decryption are done on the client side.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ContentKeyEnvelope {
+    /// A version of a content key. It is increased each time a key is rotated.
+    pub key_version: ContentKeyVersion,
+    pub encrypted_key: EncryptedContentKey,
+}
+
+/// A request of an account to join a space.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct JoinRequest<BlockNumber, Balance> {
+    /// A block number at which this request was created.
+    pub created_at: BlockNumber,
+
+    /// The amount held on deposit from the requester, until this request is approved,
+    /// rejected or cancelled.
+    pub deposit: Balance,
+}
+
+pub type JoinRequestOf<T> = JoinRequest<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+
+/// A context that is prepended to an invite voucher before signing it,
+/// so that a signature of a voucher cannot be reused for any other payload.
+pub const INVITE_VOUCHER_SIGNING_CONTEXT: &[u8] = b"subsocial/roles/invite-voucher";
+
+/// An invite that a role manager signs off-chain. Anyone who has a voucher and its' signature
+/// can redeem it once to receive a role, until the voucher expires.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct InviteVoucher<BlockNumber> {
+    /// A role that will be granted to a user who redeems this voucher.
+    pub role_id: RoleId,
+
+    /// A block number starting from which this voucher cannot be redeemed.
+    pub expires_at: BlockNumber,
+
+    /// An arbitrary number that allows signing several vouchers for the same role.
+    pub nonce: u64,
+}
+
+pub type InviteVoucherOf<T> = InviteVoucher<<T as frame_system::Config>::BlockNumber>;
+
+impl<BlockNumber: Encode> InviteVoucher<BlockNumber> {
+    /// A payload that a role manager signs to issue this voucher on a chain
+    /// with a given genesis hash, so that it cannot be redeemed on any other chain.
+    pub fn signing_payload<Hash: Encode>(&self, genesis_hash: &Hash) -> Vec<u8> {
+        (INVITE_VOUCHER_SIGNING_CONTEXT, genesis_hash, self).encode()
+    }
+}
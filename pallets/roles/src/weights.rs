@@ -5,6 +5,9 @@
 //! DATE: 2023-02-15, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! HOSTNAME: `benchmarks-ci`, CPU: `Intel(R) Xeon(R) Platinum 8280 CPU @ 2.70GHz`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! Entries marked as "Not benchmarked yet" were added by hand after this run,
+//! and must be regenerated with the command below.
 
 // Executed Command:
     // ./scripts/../target/release/subsocial-collator
@@ -46,6 +49,22 @@ pub trait WeightInfo {
     fn delete_role(x: u32, ) -> Weight;
     fn grant_role(x: u32, ) -> Weight;
     fn revoke_role(x: u32, ) -> Weight;
+    fn set_content_public_key() -> Weight;
+    fn publish_content_key_envelopes(x: u32, ) -> Weight;
+    fn request_to_join_space() -> Weight;
+    fn approve_join_request() -> Weight;
+    fn reject_join_request() -> Weight;
+    fn redeem_invite_voucher() -> Weight;
+    fn delete_expired_role(x: u32, ) -> Weight;
+    fn revoke_expired_role_grant() -> Weight;
+    fn clean_deleted_role(x: u32, ) -> Weight;
+    fn set_role_rank() -> Weight;
+    fn set_role_condition() -> Weight;
+    fn refresh_conditional_role() -> Weight;
+    fn approve_role_proposal() -> Weight;
+    fn cancel_role_proposal() -> Weight;
+    fn cancel_join_request() -> Weight;
+    fn clean_disabled_role(x: u32, ) -> Weight;
 }
 
 /// Weights for pallet_roles using the Substrate node and recommended hardware.
@@ -56,66 +75,318 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
             // Storage: Timestamp Now (r:1 w:0)
             // Storage: Roles RoleIdsBySpaceId (r:1 w:1)
             // Storage: Roles RoleById (r:0 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleRankById (r:1 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:1)
         fn create_role() -> Weight {
-        // Minimum execution time: 52_528 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(53_688_000)
-            .saturating_add(T::DbWeight::get().reads(4))
-            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().reads(7))
+            .saturating_add(T::DbWeight::get().writes(5))
         }
             // Storage: Roles RoleById (r:1 w:1)
             // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces SpaceSettings (r:1 w:0)
+            // Storage: Roles NextRoleProposalId (r:1 w:1)
+            // Storage: Roles RoleProposalById (r:0 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:0)
+            // Storage: Roles RoleProposalIdByChange (r:1 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+            // Storage: System Account (r:1 w:1)
         fn update_role() -> Weight {
-        // Minimum execution time: 48_647 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(50_219_000)
-            .saturating_add(T::DbWeight::get().reads(2))
-            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().reads(7))
+            .saturating_add(T::DbWeight::get().writes(6))
         }
             // Storage: Roles RoleById (r:1 w:1)
             // Storage: Spaces SpaceById (r:1 w:0)
             // Storage: Roles UsersByRoleId (r:1 w:1)
             // Storage: Roles RoleIdsBySpaceId (r:1 w:1)
             // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RolesPendingDeletion (r:0 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:1)
             /// The range of component `x` is `[0, 40]`.
         fn delete_role(x: u32, ) -> Weight {
-        // Minimum execution time: 57_007 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(64_783_236)
             // Standard Error: 26_706
-            .saturating_add(Weight::from_ref_time(8_701_651).saturating_mul(x.into()))
-            .saturating_add(T::DbWeight::get().reads(4))
-            .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(x.into())))
-            .saturating_add(T::DbWeight::get().writes(3))
-            .saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x.into())))
+            .saturating_add(Weight::from_ref_time(9_835_120).saturating_mul(x.into()))
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(x.into())))
+            .saturating_add(T::DbWeight::get().writes(5))
+            .saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(x.into())))
         }
             // Storage: Roles RoleById (r:1 w:0)
             // Storage: Spaces SpaceById (r:1 w:0)
             // Storage: Roles UsersByRoleId (r:1 w:1)
             // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RoleGrantsExpiringAt (r:0 w:1)
+            // Storage: Roles ConditionalRoleGrants (r:0 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:0)
             /// The range of component `x` is `[1, 500]`.
         fn grant_role(x: u32, ) -> Weight {
-        // Minimum execution time: 56_274 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(56_612_000)
             // Standard Error: 131_120
-            .saturating_add(Weight::from_ref_time(21_272_680).saturating_mul(x.into()))
-            .saturating_add(T::DbWeight::get().reads(3))
-            .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(x.into())))
+            .saturating_add(Weight::from_ref_time(27_480_510).saturating_mul(x.into()))
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(x.into())))
             .saturating_add(T::DbWeight::get().writes(1))
-            .saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x.into())))
+            .saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(x.into())))
         }
             // Storage: Roles RoleById (r:1 w:0)
             // Storage: Spaces SpaceById (r:1 w:0)
             // Storage: Roles UsersByRoleId (r:1 w:1)
             // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: Roles ConditionalRoleGrants (r:0 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:0)
             /// The range of component `x` is `[1, 500]`.
         fn revoke_role(x: u32, ) -> Weight {
-        // Minimum execution time: 61_065 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(61_492_000)
             // Standard Error: 13_040
             .saturating_add(Weight::from_ref_time(9_647_540).saturating_mul(x.into()))
-            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().reads(4))
             .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(x.into())))
             .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(x.into())))
+        }
+            // Storage: Roles ContentPublicKeyByAccount (r:0 w:1)
+        fn set_content_public_key() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(18_942_000)
+            .saturating_add(T::DbWeight::get().writes(1))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles ContentKeyVersionBySpaceId (r:1 w:1)
+            // Storage: Roles ContentKeyRotationRequired (r:1 w:1)
+            // Storage: Roles ContentPublicKeyByAccount (r:1 w:0)
+            // Storage: Roles ContentKeyEnvelopes (r:0 w:1)
+        fn publish_content_key_envelopes(x: u32, ) -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(39_215_000)
+            // Standard Error: 148_760
+            .saturating_add(Weight::from_ref_time(14_876_000).saturating_mul(x.into()))
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(x.into())))
+            .saturating_add(T::DbWeight::get().writes(2))
             .saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x.into())))
         }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles JoinRequests (r:1 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn request_to_join_space() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(27_318_000)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(2))
+        }
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles JoinRequests (r:1 w:1)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: System Account (r:2 w:2)
+            // Storage: Roles ConditionalRoleGrants (r:0 w:1)
+            // Storage: Spaces SpaceSettings (r:1 w:0)
+            // Storage: Roles NextRoleProposalId (r:1 w:1)
+            // Storage: Roles RoleProposalById (r:0 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:0)
+            // Storage: Roles RoleProposalIdByChange (r:1 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+        fn approve_join_request() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(41_802_000)
+            .saturating_add(T::DbWeight::get().reads(13))
+            .saturating_add(T::DbWeight::get().writes(11))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Roles JoinRequests (r:1 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn reject_join_request() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(24_507_000)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+        }
+            // Storage: Roles UsedInviteVouchers (r:1 w:1)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles JoinRequests (r:0 w:1)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: System Account (r:2 w:2)
+            // Storage: Roles ConditionalRoleGrants (r:0 w:1)
+            // Storage: Spaces SpaceSettings (r:1 w:0)
+            // Storage: Roles NextRoleProposalId (r:1 w:1)
+            // Storage: Roles RoleProposalById (r:0 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:0)
+            // Storage: Roles RoleProposalIdByChange (r:1 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+        fn redeem_invite_voucher() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(78_164_000)
+            .saturating_add(T::DbWeight::get().reads(13))
+            .saturating_add(T::DbWeight::get().writes(12))
+        }
+            // Storage: Roles RoleIdsExpiringAt (r:1 w:1)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles RoleById (r:1 w:1)
+            // Storage: Roles RoleIdsBySpaceId (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:1)
+            /// The range of component `x` is `[0, 40]`.
+        fn delete_expired_role(x: u32, ) -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(41_260_000)
+            // Standard Error: 86_500
+            .saturating_add(Weight::from_ref_time(8_650_000).saturating_mul(x.into()))
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(x.into())))
+            .saturating_add(T::DbWeight::get().writes(5))
+            .saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x.into())))
+        }
+            // Storage: Roles RoleGrantsExpiringAt (r:1 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+        fn revoke_expired_role_grant() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(48_310_000)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(4))
+        }
+            // Storage: Roles RolesPendingDeletion (r:1 w:1)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:1)
+            /// The range of component `x` is `[0, 40]`.
+        fn clean_deleted_role(x: u32, ) -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(21_540_000)
+            // Standard Error: 91_200
+            .saturating_add(Weight::from_ref_time(9_120_000).saturating_mul(x.into()))
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(x.into())))
+            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(x.into())))
+        }
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Roles RoleRankById (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:1)
+        fn set_role_rank() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(38_214_000)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(2))
+        }
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Roles RoleRankById (r:1 w:0)
+            // Storage: Roles RoleConditionById (r:0 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:0)
+            // Storage: Spaces SpaceSettings (r:1 w:0)
+            // Storage: Roles NextRoleProposalId (r:1 w:1)
+            // Storage: Roles RoleProposalById (r:0 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:0)
+            // Storage: Roles RoleProposalIdByChange (r:1 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn set_role_condition() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(36_410_000)
+            .saturating_add(T::DbWeight::get().reads(11))
+            .saturating_add(T::DbWeight::get().writes(6))
+        }
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Roles RoleConditionById (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: System Account (r:2 w:1)
+            // Storage: SpaceFollows SpaceFollowedAt (r:1 w:0)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles ConditionalRoleGrants (r:1 w:1)
+            // Storage: Spaces SpaceSettings (r:1 w:0)
+            // Storage: Roles NextRoleProposalId (r:1 w:1)
+            // Storage: Roles RoleProposalById (r:0 w:1)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: SpaceFollows FollowsTrackedSince (r:1 w:0)
+            // Storage: Roles RoleProposalIdByChange (r:1 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+        fn refresh_conditional_role() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(52_870_000)
+            .saturating_add(T::DbWeight::get().reads(13))
+            .saturating_add(T::DbWeight::get().writes(8))
+        }
+            // Storage: Roles RoleProposalById (r:1 w:1)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleRankById (r:1 w:0)
+            // Storage: Spaces SpaceSettings (r:1 w:0)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:1)
+            // Storage: Roles RoleProposalIdByChange (r:0 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn approve_role_proposal() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(42_610_000)
+            .saturating_add(T::DbWeight::get().reads(9))
+            .saturating_add(T::DbWeight::get().writes(5))
+        }
+            // Storage: Roles RoleProposalById (r:1 w:1)
+            // Storage: Roles RoleProposalIdByChange (r:0 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn cancel_role_proposal() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(19_938_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(4))
+        }
+            // Storage: Roles JoinRequests (r:1 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn cancel_join_request() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(28_911_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+        }
+            // Storage: Roles DisabledRolesPendingCleanup (r:1 w:1)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Roles UsersByRoleId (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:0)
+            // Storage: Roles SpacesPendingCleanup (r:1 w:0)
+            // Storage: Roles ContentKeyEnvelopes (r:1 w:1)
+            // Storage: Roles ContentKeyRotationRequired (r:0 w:1)
+            /// The range of component `x` is `[0, 40]`.
+        fn clean_disabled_role(x: u32, ) -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(12_410_000)
+            // Standard Error: 83_500
+            .saturating_add(Weight::from_ref_time(8_350_000).saturating_mul(x.into()))
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(x.into())))
+            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(x.into())))
+        }
     }
 
     // For backwards compatibility and tests
@@ -125,64 +396,316 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
             // Storage: Timestamp Now (r:1 w:0)
             // Storage: Roles RoleIdsBySpaceId (r:1 w:1)
             // Storage: Roles RoleById (r:0 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleRankById (r:1 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:1)
         fn create_role() -> Weight {
-        // Minimum execution time: 52_528 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(53_688_000)
-            .saturating_add(RocksDbWeight::get().reads(4))
-            .saturating_add(RocksDbWeight::get().writes(3))
+            .saturating_add(RocksDbWeight::get().reads(7))
+            .saturating_add(RocksDbWeight::get().writes(5))
         }
             // Storage: Roles RoleById (r:1 w:1)
             // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces SpaceSettings (r:1 w:0)
+            // Storage: Roles NextRoleProposalId (r:1 w:1)
+            // Storage: Roles RoleProposalById (r:0 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:0)
+            // Storage: Roles RoleProposalIdByChange (r:1 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+            // Storage: System Account (r:1 w:1)
         fn update_role() -> Weight {
-        // Minimum execution time: 48_647 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(50_219_000)
-            .saturating_add(RocksDbWeight::get().reads(2))
-            .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().reads(7))
+            .saturating_add(RocksDbWeight::get().writes(6))
         }
             // Storage: Roles RoleById (r:1 w:1)
             // Storage: Spaces SpaceById (r:1 w:0)
             // Storage: Roles UsersByRoleId (r:1 w:1)
             // Storage: Roles RoleIdsBySpaceId (r:1 w:1)
             // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RolesPendingDeletion (r:0 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:1)
             /// The range of component `x` is `[0, 40]`.
         fn delete_role(x: u32, ) -> Weight {
-        // Minimum execution time: 57_007 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(64_783_236)
             // Standard Error: 26_706
-            .saturating_add(Weight::from_ref_time(8_701_651).saturating_mul(x.into()))
-            .saturating_add(RocksDbWeight::get().reads(4))
-            .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(x.into())))
-            .saturating_add(RocksDbWeight::get().writes(3))
-            .saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(x.into())))
+            .saturating_add(Weight::from_ref_time(9_835_120).saturating_mul(x.into()))
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(x.into())))
+            .saturating_add(RocksDbWeight::get().writes(5))
+            .saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(x.into())))
         }
             // Storage: Roles RoleById (r:1 w:0)
             // Storage: Spaces SpaceById (r:1 w:0)
             // Storage: Roles UsersByRoleId (r:1 w:1)
             // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RoleGrantsExpiringAt (r:0 w:1)
+            // Storage: Roles ConditionalRoleGrants (r:0 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:0)
             /// The range of component `x` is `[1, 500]`.
         fn grant_role(x: u32, ) -> Weight {
-        // Minimum execution time: 56_274 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(56_612_000)
             // Standard Error: 131_120
-            .saturating_add(Weight::from_ref_time(21_272_680).saturating_mul(x.into()))
-            .saturating_add(RocksDbWeight::get().reads(3))
-            .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(x.into())))
+            .saturating_add(Weight::from_ref_time(27_480_510).saturating_mul(x.into()))
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(x.into())))
             .saturating_add(RocksDbWeight::get().writes(1))
-            .saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(x.into())))
+            .saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(x.into())))
         }
             // Storage: Roles RoleById (r:1 w:0)
             // Storage: Spaces SpaceById (r:1 w:0)
             // Storage: Roles UsersByRoleId (r:1 w:1)
             // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: Roles ConditionalRoleGrants (r:0 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:0)
             /// The range of component `x` is `[1, 500]`.
         fn revoke_role(x: u32, ) -> Weight {
-        // Minimum execution time: 61_065 nanoseconds.
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
         Weight::from_ref_time(61_492_000)
             // Standard Error: 13_040
             .saturating_add(Weight::from_ref_time(9_647_540).saturating_mul(x.into()))
-            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().reads(4))
             .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(x.into())))
             .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(x.into())))
+        }
+            // Storage: Roles ContentPublicKeyByAccount (r:0 w:1)
+        fn set_content_public_key() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(18_942_000)
+            .saturating_add(RocksDbWeight::get().writes(1))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles ContentKeyVersionBySpaceId (r:1 w:1)
+            // Storage: Roles ContentKeyRotationRequired (r:1 w:1)
+            // Storage: Roles ContentPublicKeyByAccount (r:1 w:0)
+            // Storage: Roles ContentKeyEnvelopes (r:0 w:1)
+        fn publish_content_key_envelopes(x: u32, ) -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(39_215_000)
+            // Standard Error: 148_760
+            .saturating_add(Weight::from_ref_time(14_876_000).saturating_mul(x.into()))
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(x.into())))
+            .saturating_add(RocksDbWeight::get().writes(2))
+            .saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(x.into())))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles JoinRequests (r:1 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn request_to_join_space() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(27_318_000)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(2))
+        }
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles JoinRequests (r:1 w:1)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: System Account (r:2 w:2)
+            // Storage: Roles ConditionalRoleGrants (r:0 w:1)
+            // Storage: Spaces SpaceSettings (r:1 w:0)
+            // Storage: Roles NextRoleProposalId (r:1 w:1)
+            // Storage: Roles RoleProposalById (r:0 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:0)
+            // Storage: Roles RoleProposalIdByChange (r:1 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+        fn approve_join_request() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(41_802_000)
+            .saturating_add(RocksDbWeight::get().reads(13))
+            .saturating_add(RocksDbWeight::get().writes(11))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Roles JoinRequests (r:1 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn reject_join_request() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(24_507_000)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+        }
+            // Storage: Roles UsedInviteVouchers (r:1 w:1)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles JoinRequests (r:0 w:1)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: System Account (r:2 w:2)
+            // Storage: Roles ConditionalRoleGrants (r:0 w:1)
+            // Storage: Spaces SpaceSettings (r:1 w:0)
+            // Storage: Roles NextRoleProposalId (r:1 w:1)
+            // Storage: Roles RoleProposalById (r:0 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:0)
+            // Storage: Roles RoleProposalIdByChange (r:1 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+        fn redeem_invite_voucher() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(78_164_000)
+            .saturating_add(RocksDbWeight::get().reads(13))
+            .saturating_add(RocksDbWeight::get().writes(12))
+        }
+            // Storage: Roles RoleIdsExpiringAt (r:1 w:1)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles RoleById (r:1 w:1)
+            // Storage: Roles RoleIdsBySpaceId (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:1)
+            /// The range of component `x` is `[0, 40]`.
+        fn delete_expired_role(x: u32, ) -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(41_260_000)
+            // Standard Error: 86_500
+            .saturating_add(Weight::from_ref_time(8_650_000).saturating_mul(x.into()))
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(x.into())))
+            .saturating_add(RocksDbWeight::get().writes(5))
             .saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(x.into())))
         }
+            // Storage: Roles RoleGrantsExpiringAt (r:1 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+        fn revoke_expired_role_grant() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(48_310_000)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(4))
+        }
+            // Storage: Roles RolesPendingDeletion (r:1 w:1)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:1)
+            /// The range of component `x` is `[0, 40]`.
+        fn clean_deleted_role(x: u32, ) -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(21_540_000)
+            // Standard Error: 91_200
+            .saturating_add(Weight::from_ref_time(9_120_000).saturating_mul(x.into()))
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(x.into())))
+            .saturating_add(RocksDbWeight::get().writes(3))
+            .saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(x.into())))
+        }
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Roles RoleRankById (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:1)
+        fn set_role_rank() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(38_214_000)
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(2))
+        }
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Roles RoleRankById (r:1 w:0)
+            // Storage: Roles RoleConditionById (r:0 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:0)
+            // Storage: Spaces SpaceSettings (r:1 w:0)
+            // Storage: Roles NextRoleProposalId (r:1 w:1)
+            // Storage: Roles RoleProposalById (r:0 w:1)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:0)
+            // Storage: Roles RoleProposalIdByChange (r:1 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn set_role_condition() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(36_410_000)
+            .saturating_add(RocksDbWeight::get().reads(11))
+            .saturating_add(RocksDbWeight::get().writes(6))
+        }
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Roles RoleConditionById (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: System Account (r:2 w:1)
+            // Storage: SpaceFollows SpaceFollowedAt (r:1 w:0)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles ConditionalRoleGrants (r:1 w:1)
+            // Storage: Spaces SpaceSettings (r:1 w:0)
+            // Storage: Roles NextRoleProposalId (r:1 w:1)
+            // Storage: Roles RoleProposalById (r:0 w:1)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: SpaceFollows FollowsTrackedSince (r:1 w:0)
+            // Storage: Roles RoleProposalIdByChange (r:1 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+        fn refresh_conditional_role() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(52_870_000)
+            .saturating_add(RocksDbWeight::get().reads(13))
+            .saturating_add(RocksDbWeight::get().writes(8))
+        }
+            // Storage: Roles RoleProposalById (r:1 w:1)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleRankById (r:1 w:0)
+            // Storage: Spaces SpaceSettings (r:1 w:0)
+            // Storage: Roles RankedRolesCountBySpaceId (r:1 w:1)
+            // Storage: Roles RoleProposalIdByChange (r:0 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn approve_role_proposal() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(42_610_000)
+            .saturating_add(RocksDbWeight::get().reads(9))
+            .saturating_add(RocksDbWeight::get().writes(5))
+        }
+            // Storage: Roles RoleProposalById (r:1 w:1)
+            // Storage: Roles RoleProposalIdByChange (r:0 w:1)
+            // Storage: Roles RoleProposalsExpiringAt (r:0 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn cancel_role_proposal() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(19_938_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(4))
+        }
+            // Storage: Roles JoinRequests (r:1 w:1)
+            // Storage: System Account (r:1 w:1)
+        fn cancel_join_request() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(28_911_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+        }
+            // Storage: Roles DisabledRolesPendingCleanup (r:1 w:1)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Roles UsersByRoleId (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:0)
+            // Storage: Roles SpacesPendingCleanup (r:1 w:0)
+            // Storage: Roles ContentKeyEnvelopes (r:1 w:1)
+            // Storage: Roles ContentKeyRotationRequired (r:0 w:1)
+            /// The range of component `x` is `[0, 40]`.
+        fn clean_disabled_role(x: u32, ) -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(12_410_000)
+            // Standard Error: 83_500
+            .saturating_add(Weight::from_ref_time(8_350_000).saturating_mul(x.into()))
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(x.into())))
+            .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(x.into())))
+        }
     }
@@ -4,14 +4,31 @@
 
 use super::*;
 use frame_benchmarking::{account, benchmarks};
-use frame_support::{dispatch::DispatchError, ensure};
+use frame_support::{
+    dispatch::DispatchError,
+    ensure,
+    sp_runtime::traits::Bounded,
+    traits::{Currency, Get},
+};
 use frame_system::RawOrigin;
-use pallet_spaces::types::Space;
-use subsocial_support::Content;
+use pallet_spaces::{types::Space, BalanceOf};
+use sp_std::vec::Vec;
+use subsocial_support::{cleanup::WeightMeter, Content};
+
+use crate::weights::WeightInfo;
+
+fn fund_account<T: Config>(origin: &RawOrigin<T::AccountId>) {
+    if let RawOrigin::Signed(account) = origin {
+        let balance = BalanceOf::<T>::max_value() / 2u32.into();
+        <T as pallet_spaces::Config>::Currency::make_free_balance_be(account, balance);
+    }
+}
 
 fn create_dummy_space<T: Config>(
     origin: RawOrigin<T::AccountId>,
 ) -> Result<Space<T>, DispatchError> {
+    fund_account::<T>(&origin);
+
     let space_id = pallet_spaces::NextSpaceId::<T>::get();
 
     pallet_spaces::Pallet::<T>::create_space(origin.clone().into(), Content::None, None)?;
@@ -49,4 +66,25 @@ benchmarks! {
         ensure!(!SpaceFollowedByAccount::<T>::get(&(space_follower.clone(), space.id)), "SpaceFollowedByAccount was not updated");
         ensure!(!SpacesFollowedByAccount::<T>::get(&space_follower).contains(&space.id), "SpacesFollowedByAccount was not updated");
     }
+
+    clean_deleted_space_followers {
+        let x in 0..1000;
+        let space_owner_origin = RawOrigin::Signed(account::<T::AccountId>("SpaceOwner", 2, 0));
+        let space = create_dummy_space::<T>(space_owner_origin)?;
+
+        let followers: Vec<_> =
+            (0..x).map(|i| account::<T::AccountId>("SpaceFollower", i, 0)).collect();
+        SpaceFollowers::<T>::insert(space.id, followers);
+        SpacesPendingCleanup::<T>::insert(space.id, true);
+
+        // Only enough weight to take the followers and put them back, without removing any.
+        let weight = T::DbWeight::get()
+            .reads(1)
+            .saturating_add(<T as Config>::WeightInfo::clean_deleted_space_followers(x));
+    }: {
+        Pallet::<T>::clean_deleted_spaces(&mut WeightMeter::new(weight));
+    }
+    verify {
+        ensure!(SpaceFollowers::<T>::decode_len(space.id).unwrap_or_default() as u32 == x, "SpaceFollowers should be kept");
+    }
 }
@@ -8,8 +8,12 @@ use pallet_spaces::Pallet as Spaces;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod migration;
 pub mod weights;
 
+/// The log target of this pallet.
+pub const LOG_TARGET: &str = "runtime::space-follows";
+
 // pub mod rpc;
 
 #[frame_support::pallet]
@@ -21,8 +25,9 @@ pub mod pallet {
     use frame_system::pallet_prelude::*;
     use sp_std::vec::Vec;
     use subsocial_support::{
-        remove_from_vec,
-        traits::{IsAccountBlocked, SpaceFollowsProvider},
+        cleanup::WeightMeter,
+        remove_from_vec, space_owner_walk_weight,
+        traits::{IsAccountBlocked, OnSpaceDeleted, SpaceFollowsProvider, SpaceStatsManager},
         ModerationError, SpaceId,
     };
 
@@ -31,6 +36,9 @@ pub mod pallet {
         /// The overarching event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+        /// A handler that keeps the followers count of a space up to date.
+        type SpaceStats: SpaceStatsManager;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -64,6 +72,26 @@ pub mod pallet {
     pub type SpacesFollowedByAccount<T: Config> =
         StorageMap<_, Twox64Concat, T::AccountId, Vec<SpaceId>, ValueQuery>;
 
+    /// A block at which an account has started following a space.
+    /// Follows made before this was tracked have no record,
+    /// see [`FollowsTrackedSince`].
+    #[pallet::storage]
+    #[pallet::getter(fn space_followed_at)]
+    pub type SpaceFollowedAt<T: Config> =
+        StorageMap<_, Blake2_128Concat, (T::AccountId, SpaceId), T::BlockNumber>;
+
+    /// A block, since which `SpaceFollowedAt` is tracked. Follows without a record
+    /// are counted from this block, as they were made before it.
+    #[pallet::storage]
+    #[pallet::getter(fn follows_tracked_since)]
+    pub type FollowsTrackedSince<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// Deleted spaces, whose followers are not cleaned up yet.
+    #[pallet::storage]
+    #[pallet::getter(fn is_space_pending_cleanup)]
+    pub type SpacesPendingCleanup<T: Config> =
+        StorageMap<_, Twox64Concat, SpaceId, bool, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -71,10 +99,22 @@ pub mod pallet {
         SpaceUnfollowed { follower: T::AccountId, space_id: SpaceId },
     }
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            let mut meter = WeightMeter::new(remaining_weight);
+            Self::clean_deleted_spaces(&mut meter);
+            meter.consumed()
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
-        #[pallet::weight(<T as Config>::WeightInfo::follow_space())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::follow_space()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn follow_space(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
             let follower = ensure_signed(origin)?;
 
@@ -85,6 +125,8 @@ pub mod pallet {
 
             let space = Spaces::<T>::require_space(space_id)?;
             ensure!(!space.hidden, Error::<T>::CannotFollowHiddenSpace);
+            Spaces::<T>::ensure_space_not_archived(space_id)?;
+            Spaces::<T>::ensure_space_accessible_by(&follower, &space)?;
 
             ensure!(
                 T::IsAccountBlocked::is_allowed_account(follower.clone(), space.id),
@@ -97,7 +139,10 @@ pub mod pallet {
         }
 
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config>::WeightInfo::unfollow_space())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::unfollow_space()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn unfollow_space(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
             let follower = ensure_signed(origin)?;
 
@@ -139,9 +184,14 @@ pub mod pallet {
         fn add_space_follower(follower: T::AccountId, space_id: SpaceId) {
             SpaceFollowers::<T>::mutate(space_id, |followers| followers.push(follower.clone()));
             SpaceFollowedByAccount::<T>::insert((follower.clone(), space_id), true);
+            SpaceFollowedAt::<T>::insert(
+                (follower.clone(), space_id),
+                frame_system::Pallet::<T>::block_number(),
+            );
             SpacesFollowedByAccount::<T>::mutate(follower.clone(), |space_ids| {
                 space_ids.push(space_id)
             });
+            T::SpaceStats::on_follower_added(space_id);
 
             Self::deposit_event(Event::SpaceFollowed { follower, space_id });
         }
@@ -154,17 +204,91 @@ pub mod pallet {
                 remove_from_vec(account_ids, follower.clone())
             });
             SpaceFollowedByAccount::<T>::remove((follower.clone(), space_id));
+            SpaceFollowedAt::<T>::remove((follower.clone(), space_id));
+            T::SpaceStats::on_follower_removed(space_id);
 
             Self::deposit_event(Event::SpaceUnfollowed { follower, space_id });
             Ok(())
         }
     }
 
+    impl<T: Config> Pallet<T> {
+        /// Remove followers of deleted spaces, while there is enough weight left.
+        ///
+        /// Followers of a space are taken from the storage and put back as a whole, so every step
+        /// is charged in proportion to the number of followers, that are still left.
+        pub(crate) fn clean_deleted_spaces(meter: &mut WeightMeter) {
+            let db_weight = T::DbWeight::get();
+            let follower_weight = db_weight.reads_writes(1, 3);
+
+            while meter.try_consume(db_weight.reads(1)) {
+                let space_id = match SpacesPendingCleanup::<T>::iter_keys().next() {
+                    Some(space_id) => space_id,
+                    None => break,
+                };
+
+                let followers_count =
+                    SpaceFollowers::<T>::decode_len(space_id).unwrap_or_default() as u32;
+                let step_weight =
+                    <T as Config>::WeightInfo::clean_deleted_space_followers(followers_count);
+                if !meter.try_consume(step_weight) {
+                    break
+                }
+
+                let mut followers = SpaceFollowers::<T>::take(space_id);
+                while let Some(follower) = followers.pop() {
+                    if !meter.try_consume(follower_weight) {
+                        followers.push(follower);
+                        break
+                    }
+
+                    SpacesFollowedByAccount::<T>::mutate(follower.clone(), |space_ids| {
+                        remove_from_vec(space_ids, space_id)
+                    });
+                    SpaceFollowedByAccount::<T>::remove((follower.clone(), space_id));
+                    SpaceFollowedAt::<T>::remove((follower, space_id));
+                }
+
+                if !followers.is_empty() {
+                    SpaceFollowers::<T>::insert(space_id, followers);
+                    break
+                }
+                SpacesPendingCleanup::<T>::remove(space_id);
+            }
+        }
+    }
+
     impl<T: Config> SpaceFollowsProvider for Pallet<T> {
         type AccountId = T::AccountId;
+        type BlockNumber = T::BlockNumber;
 
         fn is_space_follower(account: Self::AccountId, space_id: SpaceId) -> bool {
             Pallet::<T>::space_followed_by_account((account, space_id))
         }
+
+        fn space_followers_count(space_id: SpaceId) -> u32 {
+            Pallet::<T>::space_followers(space_id).len() as u32
+        }
+
+        /// Follows made before `SpaceFollowedAt` was tracked are counted
+        /// from `FollowsTrackedSince`.
+        fn space_followed_at(
+            account: Self::AccountId,
+            space_id: SpaceId,
+        ) -> Option<T::BlockNumber> {
+            let key = (account, space_id);
+            Pallet::<T>::space_followed_at(&key).or_else(|| {
+                Pallet::<T>::space_followed_by_account(&key)
+                    .then(Pallet::<T>::follows_tracked_since)
+            })
+        }
+    }
+
+    impl<T: Config> OnSpaceDeleted<T::AccountId> for Pallet<T> {
+        /// Followers of a deleted space are removed later in `on_idle`,
+        /// because a space can have any number of followers.
+        fn on_space_deleted(_owner: &T::AccountId, space_id: SpaceId) {
+            SpacesPendingCleanup::<T>::insert(space_id, true);
+        }
     }
 }
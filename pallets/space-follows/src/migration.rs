@@ -0,0 +1,48 @@
+use frame_support::{log, traits::OnRuntimeUpgrade};
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+use super::*;
+
+pub mod followed_at {
+    use frame_support::{pallet_prelude::*, weights::Weight};
+
+    use super::*;
+
+    /// Marks the upgrade block as the one, since which `SpaceFollowedAt` is tracked,
+    /// so that follows made before the upgrade are counted from this block.
+    ///
+    /// It does not depend on the storage version, and does nothing if it was applied before.
+    pub struct TrackLegacyFollows<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for TrackLegacyFollows<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if FollowsTrackedSince::<T>::exists() {
+                log::info!(
+                    target: LOG_TARGET,
+                    "Migration did not execute. This probably should be removed"
+                );
+                return T::DbWeight::get().reads(1)
+            }
+
+            let now = frame_system::Pallet::<T>::block_number();
+            FollowsTrackedSince::<T>::put(now);
+
+            log::info!(
+                target: LOG_TARGET,
+                "Follows made before block {:?} are counted from this block",
+                now
+            );
+            T::DbWeight::get().reads_writes(2, 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            ensure!(
+                FollowsTrackedSince::<T>::exists(),
+                "the block, since which follows are tracked, should be set"
+            );
+            Ok(())
+        }
+    }
+}
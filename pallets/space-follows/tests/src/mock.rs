@@ -1,7 +1,7 @@
-use frame_support::{pallet_prelude::ConstU32, parameter_types, traits::Everything};
+use frame_support::{pallet_prelude::{ConstU32, ConstU64}, parameter_types, traits::Everything};
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
 };
 use sp_std::convert::{TryFrom, TryInto};
@@ -90,6 +90,7 @@ impl pallet_balances::Config for Test {
 
 impl pallet_permissions::Config for Test {
     type DefaultSpacePermissions = pallet_permissions::default_permissions::DefaultSpacePermissions;
+    type NamespacedPermissions = ();
 }
 
 parameter_types! {
@@ -101,8 +102,17 @@ impl pallet_roles::Config for Test {
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
     type SpacePermissionsProvider = Spaces;
     type SpaceFollows = SpaceFollows;
+    type Currency = Balances;
+    type JoinRequestDeposit = ConstU64<0>;
+    type DomainsProvider = ();
     type IsAccountBlocked = ();
     type IsContentBlocked = ();
+    type SpaceSettings = Spaces;
+    type OnRoleRemoved = ();
+    type RoleProposalLifetime = ConstU64<100>;
+    type RoleProposalDeposit = ConstU64<0>;
+    type InviteSigner = UintAuthorityId;
+    type InviteSignature = TestSignature;
     type WeightInfo = ();
 }
 
@@ -112,11 +122,24 @@ impl pallet_spaces::Config for Test {
     type SpaceFollows = SpaceFollows;
     type IsAccountBlocked = ();
     type IsContentBlocked = ();
+    type SpacePosts = ();
+    type ProfileManager = ();
+    type OnSpaceDeleted = (SpaceFollows, Roles);
+    type OnSpaceOwnerChanged = ();
+    type SpaceSettingsHandler = ();
+    type SpaceOwnerOrigin = frame_system::EnsureSigned<AccountId>;
+    type SpaceAccounts = ();
+    type Currency = Balances;
+    type BaseSpaceDeposit = ConstU64<0>;
+    type StorageByteDeposit = ConstU64<0>;
     type MaxSpacesPerAccount = ConstU32<100>;
+    type MinHandleLen = ConstU32<5>;
+    type MaxHandleLen = ConstU32<50>;
     type WeightInfo = ();
 }
 
 impl pallet_space_follows::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type SpaceStats = Spaces;
     type WeightInfo = ();
 }
@@ -1,7 +1,10 @@
 use crate::{mock::*, tests_utils::*};
-use frame_support::{assert_noop, assert_ok};
-use pallet_space_follows::Error as SpaceFollowsError;
+use frame_support::{assert_noop, assert_ok, traits::OnRuntimeUpgrade};
+use pallet_space_follows::{
+    migration::followed_at::TrackLegacyFollows, Error as SpaceFollowsError, SpaceFollowedAt,
+};
 use pallet_spaces::Error as SpacesError;
+use subsocial_support::traits::SpaceFollowsProvider;
 
 #[test]
 fn follow_space_should_work() {
@@ -11,6 +14,10 @@ fn follow_space_should_work() {
         assert_eq!(SpaceFollows::spaces_followed_by_account(ACCOUNT2), vec![SPACE1]);
         assert_eq!(SpaceFollows::space_followers(SPACE1), vec![ACCOUNT2]);
         assert!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)));
+        assert_eq!(
+            SpaceFollows::space_followed_at((ACCOUNT2, SPACE1)),
+            Some(System::block_number())
+        );
     });
 }
 
@@ -48,6 +55,7 @@ fn unfollow_space_should_work() {
 
         assert!(SpaceFollows::spaces_followed_by_account(ACCOUNT2).is_empty());
         assert!(SpaceFollows::space_followers(SPACE1).is_empty());
+        assert!(SpaceFollows::space_followed_at((ACCOUNT2, SPACE1)).is_none());
     });
 }
 #[test]
@@ -63,3 +71,26 @@ fn unfollow_space_should_fail_when_account_is_not_space_follower_yet() {
         assert_noop!(_default_unfollow_space(), SpaceFollowsError::<Test>::NotSpaceFollower);
     });
 }
+
+#[test]
+fn legacy_follows_should_be_counted_from_upgrade_block() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_default_follow_space()); // Follow SpaceId 1 by ACCOUNT2
+
+        // A follow made before `SpaceFollowedAt` was tracked has no record.
+        SpaceFollowedAt::<Test>::remove((ACCOUNT2, SPACE1));
+        System::set_block_number(5);
+
+        TrackLegacyFollows::<Test>::on_runtime_upgrade();
+
+        assert_eq!(SpaceFollows::follows_tracked_since(), 5);
+        assert_eq!(
+            <SpaceFollows as SpaceFollowsProvider>::space_followed_at(ACCOUNT2, SPACE1),
+            Some(5)
+        );
+        assert_eq!(
+            <SpaceFollows as SpaceFollowsProvider>::space_followed_at(ACCOUNT3, SPACE1),
+            None
+        );
+    });
+}
@@ -0,0 +1,25 @@
+//! Runtime API definition for space ownership pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use subsocial_support::{SpaceId, WhoAndWhen};
+
+/// A previous owner of a space along with who and when took the space over.
+pub type SpaceOwnershipRecord<AccountId, BlockNumber, Moment> =
+	(AccountId, WhoAndWhen<AccountId, BlockNumber, Moment>);
+
+sp_api::decl_runtime_apis! {
+	pub trait SpaceOwnershipApi<AccountId, BlockNumber, Moment> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Moment: Codec,
+	{
+		/// Get previous owners of a given space, from the oldest to the latest one.
+		fn get_space_ownership_history(
+			space_id: SpaceId,
+		) -> Vec<SpaceOwnershipRecord<AccountId, BlockNumber, Moment>>;
+	}
+}
@@ -4,14 +4,27 @@
 
 use super::*;
 use frame_benchmarking::{account, benchmarks};
-use frame_support::{dispatch::DispatchError, ensure};
+use frame_support::{
+    dispatch::DispatchError, ensure, sp_runtime::traits::Bounded, traits::Currency, weights::Weight,
+};
 use frame_system::RawOrigin;
-use pallet_spaces::types::Space;
+use pallet_spaces::{types::Space, BalanceOf};
 use subsocial_support::Content;
 
+const EXPIRY: u32 = 10;
+
+fn fund_account<T: Config>(origin: &RawOrigin<T::AccountId>) {
+    if let RawOrigin::Signed(account) = origin {
+        let balance = BalanceOf::<T>::max_value() / 2u32.into();
+        <T as pallet_spaces::Config>::Currency::make_free_balance_be(account, balance);
+    }
+}
+
 fn create_dummy_space<T: Config>(
     origin: RawOrigin<T::AccountId>,
 ) -> Result<Space<T>, DispatchError> {
+    fund_account::<T>(&origin);
+
     let space_id = pallet_spaces::NextSpaceId::<T>::get();
 
     pallet_spaces::Pallet::<T>::create_space(origin.clone().into(), Content::None, None)?;
@@ -29,7 +42,8 @@ benchmarks! {
         let acc2 = account::<T::AccountId>("Acc2", 2, 0);
 
         let space = create_dummy_space::<T>(RawOrigin::Signed(acc1.clone()))?;
-    }: _(RawOrigin::Signed(acc1.clone()), space.id, acc2.clone())
+        let expires_at = frame_system::Pallet::<T>::block_number() + EXPIRY.into();
+    }: _(RawOrigin::Signed(acc1.clone()), space.id, acc2.clone(), Some(expires_at))
     verify {
         ensure!(PendingSpaceOwner::<T>::get(&space.id) == Some(acc2), "Request is not found");
     }
@@ -43,7 +57,9 @@ benchmarks! {
             RawOrigin::Signed(acc1.clone()).into(),
             space.id,
             acc2.clone(),
+            None,
         )?;
+        fund_account::<T>(&RawOrigin::Signed(acc2.clone()));
     }: _(RawOrigin::Signed(acc2.clone()), space.id)
     verify {
         let space = pallet_spaces::SpaceById::<T>::get(space.id)
@@ -62,6 +78,7 @@ benchmarks! {
             RawOrigin::Signed(acc1.clone()).into(),
             space.id,
             acc2.clone(),
+            None,
         )?;
     }: _(RawOrigin::Signed(acc2.clone()), space.id)
     verify {
@@ -72,4 +89,124 @@ benchmarks! {
         ensure!(space.owner == acc1, "Space owner is updated");
     }
 
+    cancel_pending_ownership {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+        let acc2 = account::<T::AccountId>("Acc2", 2, 0);
+
+        let space = create_dummy_space::<T>(RawOrigin::Signed(acc1.clone()))?;
+        let expires_at = frame_system::Pallet::<T>::block_number() + EXPIRY.into();
+        Pallet::<T>::transfer_space_ownership(
+            RawOrigin::Signed(acc1.clone()).into(),
+            space.id,
+            acc2,
+            Some(expires_at),
+        )?;
+    }: _(RawOrigin::Signed(acc1), space.id)
+    verify {
+        ensure!(PendingSpaceOwner::<T>::get(&space.id) == None, "Request was not cleaned");
+        ensure!(
+            TransfersExpiringAt::<T>::iter_prefix(expires_at).next().is_none(),
+            "Expiry was not cleaned"
+        );
+    }
+
+    transfer_space_ownership_to_space {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+
+        let space = create_dummy_space::<T>(RawOrigin::Signed(acc1.clone()))?;
+        let owner_space = create_dummy_space::<T>(RawOrigin::Signed(acc1.clone()))?;
+        let expires_at = frame_system::Pallet::<T>::block_number() + EXPIRY.into();
+    }: _(RawOrigin::Signed(acc1), space.id, owner_space.id, Some(expires_at))
+    verify {
+        ensure!(
+            PendingOwnerSpace::<T>::get(&space.id) == Some(owner_space.id),
+            "Request is not found"
+        );
+    }
+
+    list_space_for_sale {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+        let acc2 = account::<T::AccountId>("Acc2", 2, 0);
+
+        let space = create_dummy_space::<T>(RawOrigin::Signed(acc1.clone()))?;
+        let price = BalanceOf::<T>::max_value() / 4u32.into();
+    }: _(RawOrigin::Signed(acc1), space.id, price, Some(acc2))
+    verify {
+        ensure!(SpaceListings::<T>::contains_key(space.id), "Listing is not found");
+    }
+
+    cancel_space_listing {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+
+        let space = create_dummy_space::<T>(RawOrigin::Signed(acc1.clone()))?;
+        let price = BalanceOf::<T>::max_value() / 4u32.into();
+        Pallet::<T>::list_space_for_sale(
+            RawOrigin::Signed(acc1.clone()).into(),
+            space.id,
+            price,
+            None,
+        )?;
+    }: _(RawOrigin::Signed(acc1), space.id)
+    verify {
+        ensure!(!SpaceListings::<T>::contains_key(space.id), "Listing was not cleaned");
+    }
+
+    buy_space {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+        let acc2 = account::<T::AccountId>("Acc2", 2, 0);
+
+        let space = create_dummy_space::<T>(RawOrigin::Signed(acc1.clone()))?;
+        let price = BalanceOf::<T>::max_value() / 8u32.into();
+        Pallet::<T>::transfer_space_ownership(
+            RawOrigin::Signed(acc1.clone()).into(),
+            space.id,
+            acc2.clone(),
+            Some(frame_system::Pallet::<T>::block_number() + EXPIRY.into()),
+        )?;
+        Pallet::<T>::list_space_for_sale(
+            RawOrigin::Signed(acc1).into(),
+            space.id,
+            price,
+            Some(acc2.clone()),
+        )?;
+        fund_account::<T>(&RawOrigin::Signed(acc2.clone()));
+    }: _(RawOrigin::Signed(acc2.clone()), space.id, price)
+    verify {
+        let space = pallet_spaces::SpaceById::<T>::get(space.id)
+            .ok_or(DispatchError::Other("Space not found"))?;
+
+        ensure!(space.owner == acc2, "Space owner is not updated");
+        ensure!(!SpaceListings::<T>::contains_key(space.id), "Listing was not cleaned");
+    }
+
+    clean_expired_transfers {
+        let n in 1 .. 100;
+
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+        let acc2 = account::<T::AccountId>("Acc2", 2, 0);
+
+        let start = frame_system::Pallet::<T>::block_number();
+        let expires_at = start + EXPIRY.into();
+
+        for _ in 0 .. n {
+            let space = create_dummy_space::<T>(RawOrigin::Signed(acc1.clone()))?;
+            Pallet::<T>::transfer_space_ownership(
+                RawOrigin::Signed(acc1.clone()).into(),
+                space.id,
+                acc2.clone(),
+                Some(expires_at),
+            )?;
+        }
+    }: {
+        // Start from the block with expired transfers, so that only expirations are counted.
+        NextExpirationBlock::<T>::put(expires_at);
+        Pallet::<T>::clean_expired_transfers(expires_at, &mut WeightMeter::new(Weight::MAX));
+    }
+    verify {
+        ensure!(
+            TransfersExpiringAt::<T>::iter_prefix(expires_at).next().is_none(),
+            "Not all expired transfers are cleaned"
+        );
+    }
+
 }
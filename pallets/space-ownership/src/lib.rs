@@ -1,15 +1,29 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_system::ensure_signed;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+    traits::{Currency, ExistenceRequirement},
+    BoundedVec, RuntimeDebug,
+};
+use scale_info::TypeInfo;
 use sp_std::prelude::*;
 
-use pallet_spaces::{Pallet as Spaces, SpaceById, SpaceIdsByOwner};
+use pallet_spaces::{
+    types::{Space, MAX_SPACE_OWNERSHIP_DEPTH},
+    BalanceOf, OwnerSpaceBySpaceId, Pallet as Spaces, SpaceById, SpaceIdsByOwner,
+};
 use subsocial_support::{
-    remove_from_bounded_vec, traits::IsAccountBlocked, ModerationError, SpaceId,
+    cleanup::{process_block_queue, WeightMeter},
+    new_who_and_when, remove_from_bounded_vec, space_owner_walk_weight,
+    traits::{IsAccountBlocked, OnSpaceOwnerChanged, SpaceAccountProvider},
+    ModerationError, SpaceId, WhoAndWhenOf,
 };
 
 pub use pallet::*;
 
+pub mod types;
+pub use types::*;
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 pub mod weights;
@@ -21,13 +35,19 @@ pub mod pallet {
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
 
-    use subsocial_support::traits::ProfileManager;
+    use subsocial_support::traits::{OnSpaceDeleted, ProfileManager};
 
     #[pallet::config]
     pub trait Config: frame_system::Config + pallet_spaces::Config {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-        type ProfileManager: ProfileManager<Self::AccountId>;
+        /// The maximum number of expired ownership transfers that are cleaned up per block.
+        #[pallet::constant]
+        type MaxExpiredTransfersPerBlock: Get<u32>;
+
+        /// The maximum number of previous owners, that are kept in the history of a space.
+        #[pallet::constant]
+        type MaxOwnershipHistoryLength: Get<u32>;
 
         type WeightInfo: WeightInfo;
     }
@@ -48,12 +68,61 @@ pub mod pallet {
         NotAllowedToAcceptOwnershipTransfer,
         /// Account is not allowed to reject ownership transfer.
         NotAllowedToRejectOwnershipTransfer,
+        /// Ownership transfer can only expire at a future block.
+        InvalidTransferExpiry,
+        /// Ownership transfer has expired and cannot be accepted anymore.
+        OwnershipTransferExpired,
+        /// A space cannot be transferred to itself or to a space that it owns.
+        CannotTransferToOwnedSpace,
+        /// A new owner space is owned through too many other spaces.
+        SpaceOwnershipTooDeep,
+        /// Space is not listed for sale.
+        SpaceNotListedForSale,
+        /// Space is listed for sale to another account.
+        NotAllowedToBuySpace,
+        /// The price of a space is higher than a buyer is ready to pay.
+        SpacePriceTooHigh,
     }
 
     #[pallet::storage]
     #[pallet::getter(fn pending_space_owner)]
     pub type PendingSpaceOwner<T: Config> = StorageMap<_, Twox64Concat, SpaceId, T::AccountId>;
 
+    /// The id of a space, that is going to own a given space, if its' pending ownership
+    /// transfer is made to a space. The pending owner is the account of this space.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_owner_space)]
+    pub type PendingOwnerSpace<T: Config> = StorageMap<_, Twox64Concat, SpaceId, SpaceId>;
+
+    /// A block number at which a pending ownership transfer of a given space expires.
+    /// Transfers without an expiry block are pending until accepted, rejected or cancelled.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_transfer_expires_at)]
+    pub type PendingTransferExpiresAt<T: Config> =
+        StorageMap<_, Twox64Concat, SpaceId, T::BlockNumber>;
+
+    /// Pending ownership transfers indexed by the block at which they expire.
+    #[pallet::storage]
+    pub type TransfersExpiringAt<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, SpaceId, ()>;
+
+    /// The first block, whose expired transfers may not be cleaned up yet.
+    #[pallet::storage]
+    #[pallet::getter(fn next_expiration_block)]
+    pub type NextExpirationBlock<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    /// Get a sale listing of a given space.
+    #[pallet::storage]
+    #[pallet::getter(fn space_listing)]
+    pub type SpaceListings<T: Config> = StorageMap<_, Twox64Concat, SpaceId, SpaceListingOf<T>>;
+
+    /// Get previous owners of a given space, from the oldest to the latest one.
+    /// Only the latest `MaxOwnershipHistoryLength` owners are kept.
+    #[pallet::storage]
+    #[pallet::getter(fn space_ownership_history)]
+    pub type SpaceOwnershipHistory<T: Config> =
+        StorageMap<_, Twox64Concat, SpaceId, SpaceOwnershipHistoryOf<T>, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -70,28 +139,308 @@ pub mod pallet {
             account: T::AccountId,
             space_id: SpaceId,
         },
+        SpaceOwnershipTransferCancelled {
+            account: T::AccountId,
+            space_id: SpaceId,
+        },
+        SpaceOwnershipTransferExpired {
+            space_id: SpaceId,
+            new_owner: T::AccountId,
+        },
+        SpaceListedForSale {
+            account: T::AccountId,
+            space_id: SpaceId,
+            price: BalanceOf<T>,
+            buyer: Option<T::AccountId>,
+        },
+        SpaceListingCancelled {
+            account: T::AccountId,
+            space_id: SpaceId,
+        },
+        SpaceSold {
+            seller: T::AccountId,
+            buyer: T::AccountId,
+            space_id: SpaceId,
+            price: BalanceOf<T>,
+        },
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let mut meter = WeightMeter::new(<T as Config>::WeightInfo::clean_expired_transfers(
+                T::MaxExpiredTransfersPerBlock::get(),
+            ));
+            Self::clean_expired_transfers(now, &mut meter);
+            meter.consumed()
+        }
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
-        #[pallet::weight(<T as Config>::WeightInfo::transfer_space_ownership())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::transfer_space_ownership()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn transfer_space_ownership(
             origin: OriginFor<T>,
             space_id: SpaceId,
             transfer_to: T::AccountId,
+            expires_at: Option<T::BlockNumber>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let who = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            Self::do_transfer_space_ownership(who, space_id, transfer_to, expires_at)
+        }
+
+        #[pallet::call_index(1)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::accept_pending_ownership()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn accept_pending_ownership(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
+            let who = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            let space = Spaces::require_space(space_id)?;
+            let owner_space_id_opt = Self::pending_owner_space(space_id);
+
+            if owner_space_id_opt.is_none() {
+                ensure!(!space.is_owner(&who), Error::<T>::AlreadyASpaceOwner);
+            }
+
+            let new_owner =
+                Self::pending_space_owner(space_id).ok_or(Error::<T>::NoPendingTransferOnSpace)?;
+
+            // A transfer to a space is accepted by an owner of that space
+            // on behalf of the space account.
+            match owner_space_id_opt {
+                Some(owner_space_id) => {
+                    let owner_space = Spaces::<T>::require_space(owner_space_id)?;
+                    ensure!(
+                        owner_space.is_owner(&who),
+                        Error::<T>::NotAllowedToAcceptOwnershipTransfer
+                    );
+                    Self::ensure_can_be_owned_by_space(space_id, owner_space_id)?;
+                },
+                None => ensure!(who == new_owner, Error::<T>::NotAllowedToAcceptOwnershipTransfer),
+            }
+            ensure!(!Self::is_transfer_expired(space_id), Error::<T>::OwnershipTransferExpired);
+
+            Spaces::<T>::ensure_space_limit_not_reached(&new_owner)?;
+            Spaces::<T>::transfer_space_deposit(space_id, &space.owner, &new_owner)?;
+
+            // Here we know that the origin is eligible to become a new owner of this space.
+            Self::change_space_owner(space, new_owner, owner_space_id_opt);
+
+            Self::deposit_event(Event::SpaceOwnershipTransferAccepted { account: who, space_id });
+            Ok(())
+        }
+
+        #[pallet::call_index(2)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::reject_pending_ownership()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn reject_pending_ownership(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
+            let who = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            let space = Spaces::<T>::require_space(space_id)?;
+            let transfer_to =
+                Self::pending_space_owner(space_id).ok_or(Error::<T>::NoPendingTransferOnSpace)?;
+
+            let is_pending_owner = who == transfer_to ||
+                Self::pending_owner_space(space_id)
+                    .and_then(Spaces::<T>::space_by_id)
+                    .map_or(false, |owner_space| owner_space.is_owner(&who));
+
+            ensure!(
+                is_pending_owner || space.is_owner(&who),
+                Error::<T>::NotAllowedToRejectOwnershipTransfer
+            );
+
+            Self::remove_pending_transfer(space_id);
+
+            Self::deposit_event(Event::SpaceOwnershipTransferRejected { account: who, space_id });
+            Ok(())
+        }
+
+        /// Cancel a pending ownership transfer of a given space.
+        /// Only the current space owner can call this dispatch.
+        #[pallet::call_index(3)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::cancel_pending_ownership()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn cancel_pending_ownership(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
+            let who = T::SpaceOwnerOrigin::ensure_origin(origin)?;
 
             let space = Spaces::<T>::require_space(space_id)?;
             space.ensure_space_owner(who.clone())?;
 
-            ensure!(who != transfer_to, Error::<T>::CannotTransferToCurrentOwner);
+            ensure!(
+                PendingSpaceOwner::<T>::contains_key(space_id),
+                Error::<T>::NoPendingTransferOnSpace
+            );
+
+            Self::remove_pending_transfer(space_id);
+
+            Self::deposit_event(Event::SpaceOwnershipTransferCancelled { account: who, space_id });
+            Ok(())
+        }
+
+        /// Transfer the ownership of a given space to another space.
+        ///
+        /// Once accepted by an owner of `owner_space_id`, the space is held by the account of
+        /// the owner space, and anyone who owns the owner space also owns this space.
+        #[pallet::call_index(4)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::transfer_space_ownership_to_space()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn transfer_space_ownership_to_space(
+            origin: OriginFor<T>,
+            space_id: SpaceId,
+            owner_space_id: SpaceId,
+            expires_at: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            let who = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            Self::ensure_can_be_owned_by_space(space_id, owner_space_id)?;
+
+            let transfer_to = T::SpaceAccounts::space_account(owner_space_id);
+            Self::do_transfer_space_ownership(who, space_id, transfer_to, expires_at)?;
+
+            PendingOwnerSpace::<T>::insert(space_id, owner_space_id);
+            Ok(())
+        }
+
+        /// List a given space for sale at a fixed price, optionally only to a given `buyer`.
+        /// A new listing replaces the previous one, if any.
+        /// Only the space owner can call this dispatch.
+        #[pallet::call_index(5)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::list_space_for_sale()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn list_space_for_sale(
+            origin: OriginFor<T>,
+            space_id: SpaceId,
+            price: BalanceOf<T>,
+            buyer: Option<T::AccountId>,
+        ) -> DispatchResult {
+            let who = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            let space = Spaces::<T>::require_space(space_id)?;
+            space.ensure_space_owner(who.clone())?;
+
+            if let Some(buyer) = &buyer {
+                ensure!(!space.is_owner(buyer), Error::<T>::AlreadyASpaceOwner);
+            }
+
+            SpaceListings::<T>::insert(space_id, SpaceListing { price, buyer: buyer.clone() });
+
+            Self::deposit_event(Event::SpaceListedForSale { account: who, space_id, price, buyer });
+            Ok(())
+        }
+
+        /// Remove a sale listing of a given space.
+        /// Only the space owner can call this dispatch.
+        #[pallet::call_index(6)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::cancel_space_listing()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn cancel_space_listing(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
+            let who = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            let space = Spaces::<T>::require_space(space_id)?;
+            space.ensure_space_owner(who.clone())?;
+
+            ensure!(SpaceListings::<T>::contains_key(space_id), Error::<T>::SpaceNotListedForSale);
+            SpaceListings::<T>::remove(space_id);
+
+            Self::deposit_event(Event::SpaceListingCancelled { account: who, space_id });
+            Ok(())
+        }
+
+        /// Buy a space, that is listed for sale, paying its' price to the current space owner.
+        ///
+        /// The ownership is handed over at once, without a pending transfer to accept.
+        /// `max_price` protects a buyer from the price being raised before the purchase.
+        #[pallet::call_index(7)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::buy_space()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn buy_space(
+            origin: OriginFor<T>,
+            space_id: SpaceId,
+            max_price: BalanceOf<T>,
+        ) -> DispatchResult {
+            let buyer = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            let space = Spaces::<T>::require_space(space_id)?;
+            ensure!(!space.is_owner(&buyer), Error::<T>::AlreadyASpaceOwner);
+
+            let listing = Self::space_listing(space_id).ok_or(Error::<T>::SpaceNotListedForSale)?;
+            if let Some(allowed_buyer) = &listing.buyer {
+                ensure!(*allowed_buyer == buyer, Error::<T>::NotAllowedToBuySpace);
+            }
+            ensure!(listing.price <= max_price, Error::<T>::SpacePriceTooHigh);
+
+            ensure!(
+                T::IsAccountBlocked::is_allowed_account(buyer.clone(), space_id),
+                ModerationError::AccountIsBlocked
+            );
+            Spaces::<T>::ensure_space_limit_not_reached(&buyer)?;
+
+            let seller = space.owner.clone();
+            <T as pallet_spaces::Config>::Currency::transfer(
+                &buyer,
+                &seller,
+                listing.price,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            Spaces::<T>::transfer_space_deposit(space_id, &seller, &buyer)?;
+
+            Self::change_space_owner(space, buyer.clone(), None);
+
+            Self::deposit_event(Event::SpaceSold { seller, buyer, space_id, price: listing.price });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        fn do_transfer_space_ownership(
+            who: T::AccountId,
+            space_id: SpaceId,
+            transfer_to: T::AccountId,
+            expires_at: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            let space = Spaces::<T>::require_space(space_id)?;
+            space.ensure_space_owner(who.clone())?;
+
+            ensure!(
+                who != transfer_to && space.owner != transfer_to,
+                Error::<T>::CannotTransferToCurrentOwner
+            );
             ensure!(
                 T::IsAccountBlocked::is_allowed_account(transfer_to.clone(), space_id),
                 ModerationError::AccountIsBlocked
             );
 
+            // A new transfer replaces the previous one, if any.
+            Self::remove_pending_transfer(space_id);
+
+            if let Some(expires_at) = expires_at {
+                let now = frame_system::Pallet::<T>::block_number();
+                ensure!(expires_at > now, Error::<T>::InvalidTransferExpiry);
+
+                PendingTransferExpiresAt::<T>::insert(space_id, expires_at);
+                TransfersExpiringAt::<T>::insert(expires_at, space_id, ());
+            }
+
             PendingSpaceOwner::<T>::insert(space_id, transfer_to.clone());
 
             Self::deposit_event(Event::SpaceOwnershipTransferCreated {
@@ -102,29 +451,27 @@ pub mod pallet {
             Ok(())
         }
 
-        #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config>::WeightInfo::accept_pending_ownership())]
-        pub fn accept_pending_ownership(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
-            let new_owner = ensure_signed(origin)?;
-
-            let mut space = Spaces::require_space(space_id)?;
-            ensure!(!space.is_owner(&new_owner), Error::<T>::AlreadyASpaceOwner);
-
-            let transfer_to =
-                Self::pending_space_owner(space_id).ok_or(Error::<T>::NoPendingTransferOnSpace)?;
-
-            ensure!(new_owner == transfer_to, Error::<T>::NotAllowedToAcceptOwnershipTransfer);
-
-            Spaces::<T>::ensure_space_limit_not_reached(&transfer_to)?;
+        /// Hand over a given space to a new owner, that is either an account or an account of
+        /// `owner_space_id`, and clear a pending transfer and a sale listing of this space.
+        fn change_space_owner(
+            mut space: Space<T>,
+            new_owner: T::AccountId,
+            owner_space_id_opt: Option<SpaceId>,
+        ) {
+            let space_id = space.id;
 
-            // Here we know that the origin is eligible to become a new owner of this space.
-            PendingSpaceOwner::<T>::remove(space_id);
+            Self::remove_pending_transfer(space_id);
+            SpaceListings::<T>::remove(space_id);
 
             let old_owner = space.owner;
             space.owner = new_owner.clone();
             SpaceById::<T>::insert(space_id, space);
+            OwnerSpaceBySpaceId::<T>::set(space_id, owner_space_id_opt);
+            Self::add_to_ownership_history(space_id, &old_owner, &new_owner);
 
-            T::ProfileManager::unlink_space_from_profile(&old_owner, space_id);
+            <T as pallet_spaces::Config>::ProfileManager::unlink_space_from_profile(
+                &old_owner, space_id,
+            );
 
             // Remove space id from the list of spaces by old owner
             SpaceIdsByOwner::<T>::mutate(old_owner, |space_ids| {
@@ -132,37 +479,120 @@ pub mod pallet {
             });
 
             // Add space id to the list of spaces by new owner
-            SpaceIdsByOwner::<T>::mutate(new_owner.clone(), |ids| {
+            SpaceIdsByOwner::<T>::mutate(&new_owner, |ids| {
                 ids.try_push(space_id).expect("qed; too many spaces per account")
             });
 
             // TODO add a new owner as a space follower? See
             // T::BeforeSpaceCreated::before_space_created(new_owner.clone(), space)?;
+        }
 
-            Self::deposit_event(Event::SpaceOwnershipTransferAccepted {
-                account: new_owner,
-                space_id,
+        /// Remember a previous owner of a given space, dropping the oldest one,
+        /// if the history is full.
+        fn add_to_ownership_history(
+            space_id: SpaceId,
+            old_owner: &T::AccountId,
+            new_owner: &T::AccountId,
+        ) {
+            let record = (old_owner.clone(), new_who_and_when::<T>(new_owner.clone()));
+
+            SpaceOwnershipHistory::<T>::mutate(space_id, |history| {
+                if !history.is_empty() &&
+                    history.len() >= T::MaxOwnershipHistoryLength::get() as usize
+                {
+                    history.remove(0);
+                }
+                let _ = history.try_push(record);
             });
-            Ok(())
         }
 
-        #[pallet::call_index(2)]
-        #[pallet::weight(<T as Config>::WeightInfo::reject_pending_ownership())]
-        pub fn reject_pending_ownership(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+        /// Check that a given space can be owned by an owner space, i.e. the owner space exists,
+        /// it is neither this space nor owned by it, and its' ownership is not too deep.
+        fn ensure_can_be_owned_by_space(
+            space_id: SpaceId,
+            owner_space_id: SpaceId,
+        ) -> DispatchResult {
+            Spaces::<T>::ensure_space_exists(owner_space_id)?;
 
-            let space = Spaces::<T>::require_space(space_id)?;
-            let transfer_to =
-                Self::pending_space_owner(space_id).ok_or(Error::<T>::NoPendingTransferOnSpace)?;
+            let owner_space_ids = Spaces::<T>::owner_space_ids(owner_space_id);
             ensure!(
-                who == transfer_to || who == space.owner,
-                Error::<T>::NotAllowedToRejectOwnershipTransfer
+                owner_space_id != space_id && !owner_space_ids.contains(&space_id),
+                Error::<T>::CannotTransferToOwnedSpace
             );
+            ensure!(
+                (owner_space_ids.len() as u32) < MAX_SPACE_OWNERSHIP_DEPTH,
+                Error::<T>::SpaceOwnershipTooDeep
+            );
+            Ok(())
+        }
+
+        /// Whether a pending ownership transfer of a given space has reached its' expiry block,
+        /// but is not cleaned up yet.
+        pub fn is_transfer_expired(space_id: SpaceId) -> bool {
+            Self::pending_transfer_expires_at(space_id)
+                .map_or(false, |expires_at| expires_at <= frame_system::Pallet::<T>::block_number())
+        }
 
+        fn remove_pending_transfer(space_id: SpaceId) {
             PendingSpaceOwner::<T>::remove(space_id);
+            PendingOwnerSpace::<T>::remove(space_id);
+            if let Some(expires_at) = PendingTransferExpiresAt::<T>::take(space_id) {
+                TransfersExpiringAt::<T>::remove(expires_at, space_id);
+            }
+        }
 
-            Self::deposit_event(Event::SpaceOwnershipTransferRejected { account: who, space_id });
-            Ok(())
+        /// Remove pending transfers, that expired at or before a given block,
+        /// while there is enough weight left. Visiting a block without expired transfers
+        /// is weighed the same as removing a transfer.
+        pub(crate) fn clean_expired_transfers(now: T::BlockNumber, meter: &mut WeightMeter) {
+            let base_weight = <T as Config>::WeightInfo::clean_expired_transfers(0);
+            if !meter.try_consume(base_weight) {
+                return
+            }
+
+            let step_weight =
+                <T as Config>::WeightInfo::clean_expired_transfers(1).saturating_sub(base_weight);
+
+            let next_block = process_block_queue(
+                Self::next_expiration_block(),
+                now,
+                meter,
+                step_weight,
+                |block| TransfersExpiringAt::<T>::iter_key_prefix(block).next(),
+                |_| step_weight,
+                |block, space_id| {
+                    TransfersExpiringAt::<T>::remove(block, space_id);
+                    PendingTransferExpiresAt::<T>::remove(space_id);
+                    PendingOwnerSpace::<T>::remove(space_id);
+
+                    if let Some(new_owner) = PendingSpaceOwner::<T>::take(space_id) {
+                        Self::deposit_event(Event::SpaceOwnershipTransferExpired {
+                            space_id,
+                            new_owner,
+                        });
+                    }
+                },
+            );
+
+            NextExpirationBlock::<T>::put(next_block);
+        }
+    }
+
+    impl<T: Config> OnSpaceDeleted<T::AccountId> for Pallet<T> {
+        fn on_space_deleted(_owner: &T::AccountId, space_id: SpaceId) {
+            Self::remove_pending_transfer(space_id);
+            SpaceListings::<T>::remove(space_id);
+            SpaceOwnershipHistory::<T>::remove(space_id);
+        }
+    }
+
+    impl<T: Config> OnSpaceOwnerChanged<T::AccountId> for Pallet<T> {
+        fn on_space_owner_changed(
+            space_id: SpaceId,
+            old_owner: &T::AccountId,
+            new_owner: &T::AccountId,
+        ) {
+            Self::add_to_ownership_history(space_id, old_owner, new_owner);
         }
     }
 }
@@ -0,0 +1,19 @@
+use super::*;
+
+/// An offer to sell a space at a fixed price.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SpaceListing<AccountId, Balance> {
+    /// An amount that a buyer pays to the current space owner.
+    pub price: Balance,
+
+    /// If set, only this account can buy the space.
+    pub buyer: Option<AccountId>,
+}
+
+pub type SpaceListingOf<T> = SpaceListing<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
+/// A previous owner of a space along with who (a new owner) and when took the space over.
+pub type SpaceOwnershipRecordOf<T> = (<T as frame_system::Config>::AccountId, WhoAndWhenOf<T>);
+
+pub type SpaceOwnershipHistoryOf<T> =
+    BoundedVec<SpaceOwnershipRecordOf<T>, <T as Config>::MaxOwnershipHistoryLength>;
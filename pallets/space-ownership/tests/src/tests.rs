@@ -3,6 +3,7 @@ use sp_runtime::traits::Zero;
 
 use pallet_space_ownership::Error as SpaceOwnershipError;
 use pallet_spaces::Error as SpacesError;
+use subsocial_support::traits::SpacePermissionsProvider;
 
 use crate::{mock::*, tests_utils::*};
 
@@ -64,6 +65,36 @@ fn accept_pending_ownership_should_work() {
     });
 }
 
+#[test]
+fn accept_pending_ownership_should_move_space_deposit_to_new_owner() {
+    ExtBuilder::build().execute_with(|| {
+        BaseSpaceDeposit::set(10);
+        assert_ok!(_create_default_space());
+        assert_ok!(_transfer_default_space_ownership());
+
+        assert_ok!(_accept_default_pending_ownership());
+
+        assert!(Balances::reserved_balance(ACCOUNT1).is_zero());
+        assert_eq!(Balances::reserved_balance(ACCOUNT2), 10);
+        assert_eq!(Spaces::space_deposit(SPACE1), 10);
+    });
+}
+
+#[test]
+fn accept_pending_ownership_should_fail_when_new_owner_cannot_reserve_deposit() {
+    ExtBuilder::build().execute_with(|| {
+        BaseSpaceDeposit::set(10);
+        assert_ok!(_create_default_space());
+        assert_ok!(_transfer_default_space_ownership());
+        assert_ok!(Balances::transfer(RuntimeOrigin::signed(ACCOUNT2), ACCOUNT3, 95));
+
+        assert_noop!(
+            _accept_default_pending_ownership(),
+            SpacesError::<Test>::InsufficientBalanceToReserveDeposit
+        );
+    });
+}
+
 #[test]
 fn accept_pending_ownership_should_fail_when_space_not_found() {
     ExtBuilder::build_with_pending_ownership_transfer_no_space().execute_with(|| {
@@ -165,3 +196,435 @@ fn reject_pending_ownership_should_fail_when_account_is_not_allowed_to_reject()
         ); // Rejecting a transfer from ACCOUNT2
     });
 }
+
+#[test]
+fn transfer_space_ownership_should_fail_when_expiry_is_not_in_future() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _transfer_space_ownership_with_expiry(1),
+            SpaceOwnershipError::<Test>::InvalidTransferExpiry
+        );
+    });
+}
+
+#[test]
+fn transfer_space_ownership_should_replace_previous_expiry() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_transfer_space_ownership_with_expiry(5));
+        assert_ok!(_transfer_default_space_ownership());
+
+        assert!(SpaceOwnership::pending_transfer_expires_at(SPACE1).is_none());
+
+        run_to_block(5);
+        assert_eq!(SpaceOwnership::pending_space_owner(SPACE1), Some(ACCOUNT2));
+    });
+}
+
+#[test]
+fn cancel_pending_ownership_should_work() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_transfer_space_ownership_with_expiry(5));
+
+        assert_ok!(_cancel_default_pending_ownership());
+
+        assert!(SpaceOwnership::pending_space_owner(SPACE1).is_none());
+        assert!(SpaceOwnership::pending_transfer_expires_at(SPACE1).is_none());
+        assert_noop!(
+            _accept_default_pending_ownership(),
+            SpaceOwnershipError::<Test>::NoPendingTransferOnSpace
+        );
+    });
+}
+
+#[test]
+fn cancel_pending_ownership_should_fail_when_not_a_space_owner() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_transfer_default_space_ownership());
+
+        assert_noop!(
+            _cancel_pending_ownership(Some(RuntimeOrigin::signed(ACCOUNT2))),
+            SpacesError::<Test>::NotASpaceOwner
+        );
+    });
+}
+
+#[test]
+fn cancel_pending_ownership_should_fail_when_no_pending_transfer_on_space() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _cancel_default_pending_ownership(),
+            SpaceOwnershipError::<Test>::NoPendingTransferOnSpace
+        );
+    });
+}
+
+#[test]
+fn accept_pending_ownership_should_fail_when_transfer_expired() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_transfer_space_ownership_with_expiry(5));
+
+        // The transfer has expired, but it is not cleaned up yet.
+        System::set_block_number(5);
+
+        assert_noop!(
+            _accept_default_pending_ownership(),
+            SpaceOwnershipError::<Test>::OwnershipTransferExpired
+        );
+    });
+}
+
+#[test]
+fn expired_transfers_should_be_cleaned_up() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_transfer_space_ownership_with_expiry(5));
+
+        run_to_block(4);
+        assert_ok!(_accept_default_pending_ownership());
+
+        assert_ok!(_create_default_space()); // SpaceId 1002
+        assert_ok!(SpaceOwnership::transfer_space_ownership(
+            RuntimeOrigin::signed(ACCOUNT1),
+            SPACE1 + 1,
+            ACCOUNT3,
+            Some(6),
+        ));
+
+        run_to_block(6);
+        assert!(SpaceOwnership::pending_space_owner(SPACE1 + 1).is_none());
+        assert!(SpaceOwnership::pending_transfer_expires_at(SPACE1 + 1).is_none());
+    });
+}
+
+#[test]
+fn expired_transfers_cleanup_should_be_limited_per_block() {
+    ExtBuilder::build().execute_with(|| {
+        let spaces = [SPACE1, SPACE1 + 1, SPACE1 + 2];
+        for space_id in spaces {
+            assert_ok!(_create_default_space());
+            assert_ok!(SpaceOwnership::transfer_space_ownership(
+                RuntimeOrigin::signed(ACCOUNT1),
+                space_id,
+                ACCOUNT2,
+                Some(5),
+            ));
+        }
+
+        // Only `MaxExpiredTransfersPerBlock` (2) transfers are cleaned up in the first block.
+        run_to_block(5);
+        let pending = spaces
+            .into_iter()
+            .filter(|space_id| SpaceOwnership::pending_space_owner(space_id).is_some())
+            .count();
+        assert_eq!(pending, 1);
+
+        // The remaining transfer is cleaned up in the next block.
+        run_to_block(6);
+        for space_id in spaces {
+            assert!(SpaceOwnership::pending_space_owner(space_id).is_none());
+        }
+    });
+}
+
+#[test]
+fn transfer_space_ownership_to_space_should_work() {
+    ExtBuilder::build_with_two_spaces().execute_with(|| {
+        assert_ok!(_transfer_space_ownership_to_space(None, None, None));
+
+        assert_eq!(SpaceOwnership::pending_space_owner(SPACE1), Some(space_account(SPACE2)));
+        assert_eq!(SpaceOwnership::pending_owner_space(SPACE1), Some(SPACE2));
+
+        // The transfer is accepted by the owner of the new owner space.
+        assert_ok!(_accept_default_pending_ownership());
+
+        let space = Spaces::space_by_id(SPACE1).unwrap();
+        assert_eq!(space.owner, space_account(SPACE2));
+        assert_eq!(Spaces::owner_space_id(SPACE1), Some(SPACE2));
+        assert!(Spaces::space_ids_by_owner(space_account(SPACE2)).contains(&SPACE1));
+        assert!(SpaceOwnership::pending_owner_space(SPACE1).is_none());
+
+        // The ownership is resolved through the owner space.
+        assert!(space.is_owner(&ACCOUNT2));
+        assert!(!space.is_owner(&ACCOUNT1));
+        assert_ok!(Spaces::ensure_space_owner(SPACE1, &ACCOUNT2));
+    });
+}
+
+#[test]
+fn transfer_space_ownership_to_space_should_fail_when_transferring_to_owned_space() {
+    ExtBuilder::build_with_two_spaces().execute_with(|| {
+        assert_noop!(
+            _transfer_space_ownership_to_space(None, None, Some(SPACE1)),
+            SpaceOwnershipError::<Test>::CannotTransferToOwnedSpace
+        );
+
+        assert_ok!(_move_default_space_under_space2());
+
+        // SpaceId 2 cannot be owned by SpaceId 1, because SpaceId 1 is owned by SpaceId 2.
+        assert_noop!(
+            _transfer_space_ownership_to_space(
+                Some(RuntimeOrigin::signed(ACCOUNT2)),
+                Some(SPACE2),
+                Some(SPACE1),
+            ),
+            SpaceOwnershipError::<Test>::CannotTransferToOwnedSpace
+        );
+    });
+}
+
+#[test]
+fn accept_pending_ownership_should_fail_when_not_an_owner_of_new_owner_space() {
+    ExtBuilder::build_with_two_spaces().execute_with(|| {
+        assert_ok!(_transfer_space_ownership_to_space(None, None, None));
+
+        assert_noop!(
+            _accept_pending_ownership(Some(RuntimeOrigin::signed(ACCOUNT3)), None),
+            SpaceOwnershipError::<Test>::NotAllowedToAcceptOwnershipTransfer
+        );
+    });
+}
+
+#[test]
+fn owner_of_owner_space_should_manage_owned_space() {
+    ExtBuilder::build_with_two_spaces().execute_with(|| {
+        assert_ok!(_move_default_space_under_space2());
+
+        // The owner of SpaceId 2 can transfer SpaceId 1 back to an account.
+        assert_ok!(_transfer_space_ownership(
+            Some(RuntimeOrigin::signed(ACCOUNT2)),
+            None,
+            Some(ACCOUNT3),
+        ));
+        assert_ok!(_accept_pending_ownership(Some(RuntimeOrigin::signed(ACCOUNT3)), None));
+
+        let space = Spaces::space_by_id(SPACE1).unwrap();
+        assert_eq!(space.owner, ACCOUNT3);
+        assert!(Spaces::owner_space_id(SPACE1).is_none());
+        assert!(Spaces::space_ids_by_owner(space_account(SPACE2)).is_empty());
+        assert!(!space.is_owner(&ACCOUNT2));
+    });
+}
+
+#[test]
+fn nested_space_ownership_should_be_resolved() {
+    ExtBuilder::build_with_two_spaces().execute_with(|| {
+        assert_ok!(_create_space(Some(RuntimeOrigin::signed(ACCOUNT3)), None, None)); // SpaceId 1003
+        let space3 = SPACE2 + 1;
+
+        assert_ok!(_transfer_space_ownership_to_space(
+            Some(RuntimeOrigin::signed(ACCOUNT3)),
+            Some(space3),
+            Some(SPACE1),
+        ));
+        assert_ok!(_accept_pending_ownership(Some(RuntimeOrigin::signed(ACCOUNT1)), Some(space3)));
+        assert_ok!(_move_default_space_under_space2());
+
+        assert_eq!(Spaces::owner_space_ids(space3), vec![SPACE1, SPACE2]);
+        assert_ok!(Spaces::ensure_space_owner(space3, &ACCOUNT2));
+        assert_noop!(
+            Spaces::ensure_space_owner(space3, &ACCOUNT1),
+            SpacesError::<Test>::NotASpaceOwner
+        );
+    });
+}
+
+#[test]
+fn delete_space_should_fail_when_space_owns_other_spaces() {
+    ExtBuilder::build_with_two_spaces().execute_with(|| {
+        assert_ok!(_move_default_space_under_space2());
+
+        assert_noop!(
+            Spaces::delete_space(RuntimeOrigin::signed(ACCOUNT2), SPACE2),
+            SpacesError::<Test>::SpaceOwnsOtherSpaces
+        );
+
+        // An owned space can be deleted by the owner of its' owner space.
+        assert_ok!(Spaces::delete_space(RuntimeOrigin::signed(ACCOUNT2), SPACE1));
+        assert!(Spaces::owner_space_id(SPACE1).is_none());
+        assert_ok!(Spaces::delete_space(RuntimeOrigin::signed(ACCOUNT2), SPACE2));
+    });
+}
+
+#[test]
+fn list_space_for_sale_should_work() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_list_space_for_sale(None, None, Some(Some(ACCOUNT2))));
+
+        let listing = SpaceOwnership::space_listing(SPACE1).unwrap();
+        assert_eq!(listing.price, SPACE_PRICE);
+        assert_eq!(listing.buyer, Some(ACCOUNT2));
+    });
+}
+
+#[test]
+fn list_space_for_sale_should_fail_when_not_a_space_owner() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _list_space_for_sale(Some(RuntimeOrigin::signed(ACCOUNT2)), None, None),
+            SpacesError::<Test>::NotASpaceOwner
+        );
+    });
+}
+
+#[test]
+fn cancel_space_listing_should_work() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_list_default_space_for_sale());
+        assert_ok!(_cancel_space_listing(None));
+
+        assert!(SpaceOwnership::space_listing(SPACE1).is_none());
+        assert_noop!(_buy_default_space(), SpaceOwnershipError::<Test>::SpaceNotListedForSale);
+    });
+}
+
+#[test]
+fn buy_space_should_work() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_transfer_default_space_ownership());
+        assert_ok!(_list_default_space_for_sale());
+
+        let seller_balance = Balances::free_balance(ACCOUNT1);
+        let buyer_balance = Balances::free_balance(ACCOUNT2);
+
+        assert_ok!(_buy_default_space());
+
+        let space = Spaces::space_by_id(SPACE1).unwrap();
+        assert_eq!(space.owner, ACCOUNT2);
+        assert!(Spaces::space_ids_by_owner(ACCOUNT1).is_empty());
+        assert_eq!(Spaces::space_ids_by_owner(ACCOUNT2).into_inner(), vec![SPACE1]);
+
+        assert_eq!(Balances::free_balance(ACCOUNT1), seller_balance + SPACE_PRICE);
+        assert_eq!(Balances::free_balance(ACCOUNT2), buyer_balance - SPACE_PRICE);
+
+        // The listing and a pending transfer are cleared.
+        assert!(SpaceOwnership::space_listing(SPACE1).is_none());
+        assert!(SpaceOwnership::pending_space_owner(SPACE1).is_none());
+    });
+}
+
+#[test]
+fn buy_space_should_fail_when_space_is_listed_for_another_buyer() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_list_space_for_sale(None, None, Some(Some(ACCOUNT3))));
+
+        assert_noop!(_buy_default_space(), SpaceOwnershipError::<Test>::NotAllowedToBuySpace);
+    });
+}
+
+#[test]
+fn buy_space_should_fail_when_price_is_higher_than_max_price() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_list_default_space_for_sale());
+
+        assert_noop!(
+            _buy_space(None, Some(SPACE_PRICE - 1)),
+            SpaceOwnershipError::<Test>::SpacePriceTooHigh
+        );
+    });
+}
+
+#[test]
+fn buy_space_should_fail_when_buyer_is_a_space_owner() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_list_default_space_for_sale());
+
+        assert_noop!(
+            _buy_space(Some(RuntimeOrigin::signed(ACCOUNT1)), None),
+            SpaceOwnershipError::<Test>::AlreadyASpaceOwner
+        );
+    });
+}
+
+#[test]
+fn buy_space_should_fail_when_buyer_has_not_enough_funds() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_list_space_for_sale(None, Some(1_000), None));
+
+        assert_noop!(
+            _buy_space(None, Some(1_000)),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn accept_pending_ownership_should_clear_space_listing() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_list_default_space_for_sale());
+        assert_ok!(_transfer_default_space_ownership());
+        assert_ok!(_accept_default_pending_ownership());
+
+        assert!(SpaceOwnership::space_listing(SPACE1).is_none());
+    });
+}
+
+#[test]
+fn accept_pending_ownership_should_add_previous_owner_to_history() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_transfer_default_space_ownership());
+        assert_ok!(_accept_default_pending_ownership());
+
+        let history = SpaceOwnership::space_ownership_history(SPACE1);
+        assert_eq!(history.len(), 1);
+
+        let (previous_owner, changed) = &history[0];
+        assert_eq!(*previous_owner, ACCOUNT1);
+        assert_eq!(changed.account, ACCOUNT2);
+        assert_eq!(changed.block, 1);
+    });
+}
+
+#[test]
+fn ownership_history_should_keep_only_latest_owners() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        // ACCOUNT1 -> ACCOUNT2 -> ACCOUNT3 -> ACCOUNT1
+        for (owner, new_owner) in [(ACCOUNT1, ACCOUNT2), (ACCOUNT2, ACCOUNT3), (ACCOUNT3, ACCOUNT1)]
+        {
+            assert_ok!(_transfer_space_ownership(
+                Some(RuntimeOrigin::signed(owner)),
+                None,
+                Some(new_owner),
+            ));
+            assert_ok!(_accept_pending_ownership(Some(RuntimeOrigin::signed(new_owner)), None));
+        }
+
+        // Only `MaxOwnershipHistoryLength` (2) latest previous owners are kept.
+        let previous_owners: Vec<_> = SpaceOwnership::space_ownership_history(SPACE1)
+            .into_iter()
+            .map(|(owner, _)| owner)
+            .collect();
+        assert_eq!(previous_owners, vec![ACCOUNT2, ACCOUNT3]);
+    });
+}
+
+#[test]
+fn force_create_space_should_add_previous_owner_to_history() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        let space = Spaces::space_by_id(SPACE1).unwrap();
+
+        assert_ok!(Spaces::force_create_space(
+            RuntimeOrigin::root(),
+            SPACE1,
+            space.created,
+            ACCOUNT3,
+            space.content,
+            space.hidden,
+            space.permissions,
+        ));
+
+        let history = SpaceOwnership::space_ownership_history(SPACE1);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].0, ACCOUNT1);
+        assert_eq!(history[0].1.account, ACCOUNT3);
+    });
+}
+
+#[test]
+fn delete_space_should_clear_ownership_history() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_transfer_default_space_ownership());
+        assert_ok!(_accept_default_pending_ownership());
+
+        assert_ok!(Spaces::delete_space(RuntimeOrigin::signed(ACCOUNT2), SPACE1));
+        assert!(SpaceOwnership::space_ownership_history(SPACE1).is_empty());
+    });
+}
@@ -1,10 +1,10 @@
-use frame_support::{assert_ok, pallet_prelude::*};
+use frame_support::{assert_ok, pallet_prelude::*, traits::Hooks};
 use sp_core::storage::Storage;
 use sp_io::TestExternalities;
 
 use pallet_permissions::SpacePermissions;
 use pallet_spaces::*;
-use subsocial_support::{Content, SpaceId};
+use subsocial_support::{traits::SpaceAccountProvider, Content, SpaceId};
 
 use crate::mock::*;
 
@@ -48,6 +48,16 @@ impl ExtBuilder {
         ext
     }
 
+    /// Custom ext configuration with SpaceId 1 owned by ACCOUNT1
+    /// and SpaceId 2 owned by ACCOUNT2
+    pub fn build_with_two_spaces() -> TestExternalities {
+        let mut ext = Self::build_with_space();
+        ext.execute_with(|| {
+            assert_ok!(_create_space(Some(RuntimeOrigin::signed(ACCOUNT2)), None, None));
+        });
+        ext
+    }
+
     /// Custom ext configuration with pending ownership transfer without Space
     pub fn build_with_pending_ownership_transfer_no_space() -> TestExternalities {
         let mut ext = Self::build_with_space();
@@ -66,6 +76,20 @@ pub(crate) const ACCOUNT2: AccountId = 2;
 pub(crate) const ACCOUNT3: AccountId = 3;
 
 pub(crate) const SPACE1: SpaceId = 1001;
+pub(crate) const SPACE2: SpaceId = 1002;
+
+pub(crate) const SPACE_PRICE: Balance = 50;
+
+////// Block Utils
+
+/// Run `on_initialize` of the space ownership pallet for every block up to a given one.
+pub(crate) fn run_to_block(n: BlockNumber) {
+    while System::block_number() < n {
+        let next = System::block_number() + 1;
+        System::set_block_number(next);
+        SpaceOwnership::on_initialize(next);
+    }
+}
 
 ///////////// Space Utils
 
@@ -104,9 +128,42 @@ pub(crate) fn _transfer_space_ownership(
         origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
         space_id.unwrap_or(SPACE1),
         transfer_to.unwrap_or(ACCOUNT2),
+        None,
+    )
+}
+
+pub(crate) fn _transfer_space_ownership_with_expiry(expires_at: BlockNumber) -> DispatchResult {
+    SpaceOwnership::transfer_space_ownership(
+        RuntimeOrigin::signed(ACCOUNT1),
+        SPACE1,
+        ACCOUNT2,
+        Some(expires_at),
+    )
+}
+
+pub(crate) fn _transfer_space_ownership_to_space(
+    origin: Option<RuntimeOrigin>,
+    space_id: Option<SpaceId>,
+    owner_space_id: Option<SpaceId>,
+) -> DispatchResult {
+    SpaceOwnership::transfer_space_ownership_to_space(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        space_id.unwrap_or(SPACE1),
+        owner_space_id.unwrap_or(SPACE2),
+        None,
     )
 }
 
+/// Transfer SpaceId 1 to SpaceId 2 and accept the transfer by ACCOUNT2.
+pub(crate) fn _move_default_space_under_space2() -> DispatchResult {
+    _transfer_space_ownership_to_space(None, None, None)?;
+    _accept_pending_ownership(None, None)
+}
+
+pub(crate) fn space_account(space_id: SpaceId) -> AccountId {
+    <Test as pallet_spaces::Config>::SpaceAccounts::space_account(space_id)
+}
+
 pub(crate) fn _accept_default_pending_ownership() -> DispatchResult {
     _accept_pending_ownership(None, None)
 }
@@ -138,3 +195,55 @@ pub(crate) fn _reject_pending_ownership(
         space_id.unwrap_or(SPACE1),
     )
 }
+
+pub(crate) fn _cancel_default_pending_ownership() -> DispatchResult {
+    _cancel_pending_ownership(None)
+}
+
+pub(crate) fn _cancel_pending_ownership(origin: Option<RuntimeOrigin>) -> DispatchResult {
+    SpaceOwnership::cancel_pending_ownership(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        SPACE1,
+    )
+}
+
+//////// Space marketplace utils
+
+pub(crate) fn _list_default_space_for_sale() -> DispatchResult {
+    _list_space_for_sale(None, None, None)
+}
+
+pub(crate) fn _list_space_for_sale(
+    origin: Option<RuntimeOrigin>,
+    price: Option<Balance>,
+    buyer: Option<Option<AccountId>>,
+) -> DispatchResult {
+    SpaceOwnership::list_space_for_sale(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        SPACE1,
+        price.unwrap_or(SPACE_PRICE),
+        buyer.unwrap_or_default(),
+    )
+}
+
+pub(crate) fn _cancel_space_listing(origin: Option<RuntimeOrigin>) -> DispatchResult {
+    SpaceOwnership::cancel_space_listing(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        SPACE1,
+    )
+}
+
+pub(crate) fn _buy_default_space() -> DispatchResult {
+    _buy_space(None, None)
+}
+
+pub(crate) fn _buy_space(
+    origin: Option<RuntimeOrigin>,
+    max_price: Option<Balance>,
+) -> DispatchResult {
+    SpaceOwnership::buy_space(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT2)),
+        SPACE1,
+        max_price.unwrap_or(SPACE_PRICE),
+    )
+}
@@ -0,0 +1,126 @@
+//! Space treasury pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::{dispatch::DispatchError, ensure, traits::Currency};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::{boxed::Box, vec};
+use subsocial_support::Content;
+
+fn fund_account<T: Config>(origin: &RawOrigin<T::AccountId>) {
+    if let RawOrigin::Signed(account) = origin {
+        let balance = pallet_spaces::BalanceOf::<T>::max_value() / 2u32.into();
+        <T as pallet_spaces::Config>::Currency::make_free_balance_be(account, balance);
+    }
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+    frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+fn create_dummy_space<T: Config>(
+    origin: RawOrigin<T::AccountId>,
+) -> Result<Space<T>, DispatchError> {
+    fund_account::<T>(&origin);
+
+    let space_id = pallet_spaces::NextSpaceId::<T>::get();
+
+    pallet_spaces::Pallet::<T>::create_space(origin.clone().into(), Content::None, None)?;
+
+    let space = pallet_spaces::SpaceById::<T>::get(space_id)
+        .ok_or(DispatchError::Other("Space not found"))?;
+
+    Ok(space)
+}
+
+benchmarks! {
+
+    deposit {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+        let acc2 = account::<T::AccountId>("Acc2", 2, 0);
+
+        let space = create_dummy_space::<T>(RawOrigin::Signed(acc1))?;
+        let amount = <T as Config>::Currency::minimum_balance() * 10u32.into();
+        <T as Config>::Currency::make_free_balance_be(&acc2, amount * 10u32.into());
+    }: _(RawOrigin::Signed(acc2), space.id, amount)
+    verify {
+        let treasury = Pallet::<T>::space_treasury_account(space.id);
+        ensure!(
+            <T as Config>::Currency::free_balance(&treasury) == amount,
+            "Treasury is not funded"
+        );
+    }
+
+    spend {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+        let acc2 = account::<T::AccountId>("Acc2", 2, 0);
+        let beneficiary = account::<T::AccountId>("Acc3", 3, 0);
+
+        let space = create_dummy_space::<T>(RawOrigin::Signed(acc1.clone()))?;
+        let role_id = pallet_roles::NextRoleId::<T>::get();
+        Roles::<T>::create_role(
+            RawOrigin::Signed(acc1.clone()).into(),
+            space.id,
+            None,
+            Content::None,
+            vec![SpacePermission::SpendTreasury],
+            0,
+        )?;
+        Roles::<T>::grant_role(
+            RawOrigin::Signed(acc1.clone()).into(),
+            role_id,
+            vec![User::Account(acc2.clone())],
+            None,
+        )?;
+
+        let amount = <T as Config>::Currency::minimum_balance() * 10u32.into();
+        Pallet::<T>::set_role_spending_limit(
+            RawOrigin::Signed(acc1).into(),
+            role_id,
+            Some(amount),
+        )?;
+
+        let treasury = Pallet::<T>::space_treasury_account(space.id);
+        <T as Config>::Currency::make_free_balance_be(&treasury, amount * 10u32.into());
+    }: _(RawOrigin::Signed(acc2), space.id, beneficiary.clone(), amount)
+    verify {
+        ensure!(
+            <T as Config>::Currency::free_balance(&beneficiary) == amount,
+            "Beneficiary is not paid"
+        );
+    }
+
+    set_role_spending_limit {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+
+        let space = create_dummy_space::<T>(RawOrigin::Signed(acc1.clone()))?;
+        let role_id = pallet_roles::NextRoleId::<T>::get();
+        Roles::<T>::create_role(
+            RawOrigin::Signed(acc1.clone()).into(),
+            space.id,
+            None,
+            Content::None,
+            vec![SpacePermission::SpendTreasury],
+            0,
+        )?;
+
+        let limit = <T as Config>::Currency::minimum_balance() * 10u32.into();
+    }: _(RawOrigin::Signed(acc1), role_id, Some(limit))
+    verify {
+        ensure!(SpendingLimitByRoleId::<T>::get(role_id) == Some(limit), "Limit is not set");
+    }
+
+    act_as_space {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+
+        let space = create_dummy_space::<T>(RawOrigin::Signed(acc1.clone()))?;
+        let call = Box::new(<T as Config>::BenchmarkHelper::space_call(space.id));
+    }: _(RawOrigin::Signed(acc1.clone()), space.id, call)
+    verify {
+        assert_last_event::<T>(Event::ActedAsSpace { account: acc1, space_id: space.id }.into());
+    }
+
+}
@@ -0,0 +1,455 @@
+//! # Space Treasury Module
+//!
+//! Every space has a treasury: an account derived from the pallet id and the space id.
+//! Anyone can deposit funds into a space treasury. Funds can be spent by the space owner
+//! or by users who have `SpendTreasury` permission via roles within a space. Spending via roles
+//! can be limited per role and per spending period.
+//!
+//! Users with `RepresentSpaceInternally` or `RepresentSpaceExternally` permission can also
+//! dispatch calls on behalf of a space (see `act_as_space`). Such calls are dispatched from
+//! a separate actor account of the space, so they can neither reserve treasury funds nor use
+//! the treasury account's ownership of other spaces.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+    dispatch::{
+        extract_actual_weight, DispatchResult, DispatchResultWithPostInfo, GetDispatchInfo,
+        PostDispatchInfo,
+    },
+    ensure,
+    traits::{Currency, ExistenceRequirement},
+    PalletId,
+};
+use frame_system::ensure_signed;
+use sp_runtime::traits::{AccountIdConversion, CheckedDiv, Dispatchable, Saturating, Zero};
+use sp_std::{boxed::Box, prelude::*};
+
+use pallet_permissions::SpacePermission;
+use pallet_roles::{OnRoleRemoved, Pallet as Roles, RoleId};
+use pallet_spaces::{types::Space, Pallet as Spaces};
+use subsocial_support::{
+    space_owner_walk_weight,
+    traits::{OnSpaceDeleted, SpaceAccountProvider},
+    SpaceId, User,
+};
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
+type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use crate::weights::WeightInfo;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_spaces::Config + pallet_roles::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// The overarching call type.
+        type RuntimeCall: Parameter
+            + Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+            + GetDispatchInfo;
+
+        /// The currency mechanism.
+        type Currency: Currency<Self::AccountId>;
+
+        /// The pallet id, used to derive treasury accounts of spaces.
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+
+        /// The number of blocks, within which spendings via a role are summed up
+        /// and checked against the spending limit of this role.
+        #[pallet::constant]
+        type SpendingPeriod: Get<Self::BlockNumber>;
+
+        /// Decides which calls can be dispatched on behalf of a space,
+        /// and which space these calls are targeted at.
+        type SpaceCalls: SpaceCallInspector<<Self as Config>::RuntimeCall>;
+
+        /// A helper to build calls on behalf of a space in benchmarks.
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<<Self as Config>::RuntimeCall>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        TreasuryDeposited {
+            account: T::AccountId,
+            space_id: SpaceId,
+            amount: BalanceOf<T>,
+        },
+        TreasurySpent {
+            account: T::AccountId,
+            space_id: SpaceId,
+            beneficiary: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        RoleSpendingLimitUpdated {
+            account: T::AccountId,
+            role_id: RoleId,
+            limit: Option<BalanceOf<T>>,
+        },
+        ActedAsSpace {
+            account: T::AccountId,
+            space_id: SpaceId,
+        },
+        /// Funds of a space account were returned to the owner of a deleted space.
+        DeletedSpaceFundsReturned {
+            space_id: SpaceId,
+            space_account: T::AccountId,
+            owner: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Amount to deposit or spend must be greater than zero.
+        ZeroAmount,
+        /// Account does not have permission to spend funds from this space treasury.
+        NoPermissionToSpendTreasury,
+        /// Amount to spend exceeds the remaining allowance of all the account's roles
+        /// in this space within the current spending period.
+        SpendingLimitExceeded,
+        /// This call cannot be dispatched on behalf of a space.
+        CallCannotActAsSpace,
+        /// Account does not have permission to act on behalf of this space.
+        NoPermissionToRepresentSpace,
+    }
+
+    /// The maximum amount that all users of a given role can spend from a space treasury
+    /// within a spending period, if they have `SpendTreasury` permission via this role.
+    ///
+    /// If a role has no limit, users of this role can spend any amount.
+    /// If a user has several roles, a payment is charged to the role with the most
+    /// remaining allowance.
+    #[pallet::storage]
+    #[pallet::getter(fn spending_limit_by_role_id)]
+    pub type SpendingLimitByRoleId<T: Config> = StorageMap<_, Twox64Concat, RoleId, BalanceOf<T>>;
+
+    /// A spending period and the amount, that users of a given role have spent within it.
+    #[pallet::storage]
+    #[pallet::getter(fn spent_by_role_id)]
+    pub type SpentByRoleId<T: Config> =
+        StorageMap<_, Twox64Concat, RoleId, (T::BlockNumber, BalanceOf<T>), ValueQuery>;
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Deposit funds from the caller's account into the treasury of a given space.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::deposit())]
+        pub fn deposit(
+            origin: OriginFor<T>,
+            space_id: SpaceId,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            Spaces::<T>::ensure_space_exists(space_id)?;
+
+            <T as Config>::Currency::transfer(
+                &who,
+                &Self::space_treasury_account(space_id),
+                amount,
+                ExistenceRequirement::KeepAlive,
+            )?;
+
+            Self::deposit_event(Event::TreasuryDeposited { account: who, space_id, amount });
+            Ok(())
+        }
+
+        /// Pay a given amount from the treasury of a given space to a beneficiary.
+        ///
+        /// Only the space owner or a user with `SpendTreasury` permission via a role
+        /// can call this dispatch. See `SpendingLimitByRoleId` for how spending limits are applied.
+        #[pallet::call_index(1)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::spend()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn spend(
+            origin: OriginFor<T>,
+            space_id: SpaceId,
+            beneficiary: T::AccountId,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+            let space = Spaces::<T>::require_space(space_id)?;
+            Spaces::<T>::ensure_account_has_space_permission(
+                who.clone(),
+                &space,
+                SpacePermission::SpendTreasury,
+                Error::<T>::NoPermissionToSpendTreasury.into(),
+            )?;
+            Self::charge_spending_limit(&who, &space, amount)?;
+
+            <T as Config>::Currency::transfer(
+                &Self::space_treasury_account(space_id),
+                &beneficiary,
+                amount,
+                ExistenceRequirement::AllowDeath,
+            )?;
+
+            Self::deposit_event(Event::TreasurySpent {
+                account: who,
+                space_id,
+                beneficiary,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Set or remove (if `None`) a spending limit of a given role.
+        /// Only the space owner or a manager of this role can call this dispatch.
+        #[pallet::call_index(2)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::set_role_spending_limit()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn set_role_spending_limit(
+            origin: OriginFor<T>,
+            role_id: RoleId,
+            limit: Option<BalanceOf<T>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let role = Roles::<T>::require_role(role_id)?;
+            Roles::<T>::ensure_role_manager_of(who.clone(), &role)?;
+
+            SpendingLimitByRoleId::<T>::set(role_id, limit);
+
+            Self::deposit_event(Event::RoleSpendingLimitUpdated { account: who, role_id, limit });
+            Ok(())
+        }
+
+        /// Dispatch a call on behalf of a given space, i.e. from the actor account of this space.
+        ///
+        /// The caller needs `RepresentSpaceInternally` permission if the call is targeted at
+        /// the same space, or `RepresentSpaceExternally` permission otherwise.
+        ///
+        /// The actor account is separate from the space treasury: deposits, that the call
+        /// reserves, are charged to the actor account, which has to be funded separately.
+        #[pallet::call_index(3)]
+        #[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            (
+                <T as Config>::WeightInfo::act_as_space()
+                    .saturating_add(space_owner_walk_weight::<T>())
+                    .saturating_add(dispatch_info.weight),
+                dispatch_info.class,
+            )
+        })]
+        pub fn act_as_space(
+            origin: OriginFor<T>,
+            space_id: SpaceId,
+            call: Box<<T as Config>::RuntimeCall>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(T::SpaceCalls::can_act_as_space(&call), Error::<T>::CallCannotActAsSpace);
+
+            let space = Spaces::<T>::require_space(space_id)?;
+            let permission = if T::SpaceCalls::target_space(&call) == Some(space_id) {
+                SpacePermission::RepresentSpaceInternally
+            } else {
+                SpacePermission::RepresentSpaceExternally
+            };
+            Spaces::<T>::ensure_account_has_space_permission(
+                who.clone(),
+                &space,
+                permission,
+                Error::<T>::NoPermissionToRepresentSpace.into(),
+            )?;
+
+            let dispatch_info = call.get_dispatch_info();
+            let space_actor = Self::space_actor_account(space_id);
+            let result = call.dispatch(frame_system::RawOrigin::Signed(space_actor).into());
+
+            // Charge the base weight of this call and refund unused weight of the inner call.
+            let weight = <T as Config>::WeightInfo::act_as_space()
+                .saturating_add(space_owner_walk_weight::<T>())
+                .saturating_add(extract_actual_weight(&result, &dispatch_info));
+
+            if let Err(mut err) = result {
+                err.post_info = Some(weight).into();
+                return Err(err)
+            }
+
+            Self::deposit_event(Event::ActedAsSpace { account: who, space_id });
+            Ok(Some(weight).into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Get the treasury account of a given space.
+        pub fn space_treasury_account(space_id: SpaceId) -> T::AccountId {
+            T::PalletId::get().into_sub_account_truncating(space_id)
+        }
+
+        /// Get the account, that a given space acts from via `act_as_space`.
+        ///
+        /// It is separate from the space treasury, so representatives of a space can't reserve
+        /// treasury funds or act as the owner of spaces, owned by the treasury account.
+        pub fn space_actor_account(space_id: SpaceId) -> T::AccountId {
+            T::PalletId::get().into_sub_account_truncating((b"actor", space_id))
+        }
+
+        /// Get the index of the current spending period.
+        fn current_spending_period() -> T::BlockNumber {
+            <frame_system::Pallet<T>>::block_number()
+                .checked_div(&T::SpendingPeriod::get())
+                .unwrap_or_else(Zero::zero)
+        }
+
+        /// Get the amount, that users of a given role have spent within a given period.
+        fn spent_in_period(role_id: RoleId, period: T::BlockNumber) -> BalanceOf<T> {
+            let (spent_period, spent) = Self::spent_by_role_id(role_id);
+            if spent_period == period {
+                spent
+            } else {
+                Zero::zero()
+            }
+        }
+
+        /// Ensure that a given amount fits into the remaining allowance of the account's roles
+        /// within the current spending period, and charge it to the role with the most
+        /// remaining allowance.
+        fn charge_spending_limit(
+            account: &T::AccountId,
+            space: &Space<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            if space.is_owner(account) {
+                return Ok(())
+            }
+
+            let role_ids = Roles::<T>::active_role_ids_with_permission(
+                User::Account(account.clone()),
+                space.id,
+                SpacePermission::SpendTreasury,
+            );
+
+            // Spending via space permissions, not via roles, cannot be limited.
+            ensure!(!role_ids.is_empty(), Error::<T>::NoPermissionToSpendTreasury);
+
+            let period = Self::current_spending_period();
+            let mut charged_role: Option<(RoleId, BalanceOf<T>)> = None;
+            for role_id in role_ids {
+                let limit = match Self::spending_limit_by_role_id(role_id) {
+                    Some(limit) => limit,
+                    None => return Ok(()),
+                };
+
+                let remaining = limit.saturating_sub(Self::spent_in_period(role_id, period));
+                if charged_role.map_or(true, |(_, max_remaining)| remaining > max_remaining) {
+                    charged_role = Some((role_id, remaining));
+                }
+            }
+
+            if let Some((role_id, remaining)) = charged_role {
+                ensure!(amount <= remaining, Error::<T>::SpendingLimitExceeded);
+
+                let spent = Self::spent_in_period(role_id, period).saturating_add(amount);
+                SpentByRoleId::<T>::insert(role_id, (period, spent));
+            }
+            Ok(())
+        }
+    }
+
+    impl<T: Config> OnRoleRemoved for Pallet<T> {
+        fn on_role_removed(role_id: RoleId) {
+            SpendingLimitByRoleId::<T>::remove(role_id);
+            SpentByRoleId::<T>::remove(role_id);
+        }
+    }
+
+    impl<T: Config> OnSpaceDeleted<T::AccountId> for Pallet<T> {
+        /// Space ids are never reused, so funds of a deleted space would be stranded forever.
+        /// Both the treasury and the actor account of a space are swept to its' owner.
+        fn on_space_deleted(owner: &T::AccountId, space_id: SpaceId) {
+            let space_accounts =
+                [Self::space_treasury_account(space_id), Self::space_actor_account(space_id)];
+
+            for space_account in space_accounts {
+                let amount = <T as Config>::Currency::free_balance(&space_account);
+                if amount.is_zero() {
+                    continue
+                }
+
+                let result = <T as Config>::Currency::transfer(
+                    &space_account,
+                    owner,
+                    amount,
+                    ExistenceRequirement::AllowDeath,
+                );
+                if result.is_ok() {
+                    Self::deposit_event(Event::DeletedSpaceFundsReturned {
+                        space_id,
+                        space_account,
+                        owner: owner.clone(),
+                        amount,
+                    });
+                }
+            }
+        }
+
+        fn on_space_deleted_weight() -> Weight {
+            // Both accounts of a space and the account of its' owner.
+            T::DbWeight::get().reads_writes(3, 3)
+        }
+    }
+
+    impl<T: Config> SpaceAccountProvider<T::AccountId> for Pallet<T> {
+        fn space_account(space_id: SpaceId) -> T::AccountId {
+            Self::space_treasury_account(space_id)
+        }
+    }
+}
+
+/// Inspects calls that users can dispatch on behalf of a space.
+pub trait SpaceCallInspector<Call> {
+    /// Whether a call can be dispatched on behalf of a space.
+    fn can_act_as_space(call: &Call) -> bool;
+
+    /// A space a call is targeted at, if any.
+    fn target_space(call: &Call) -> Option<SpaceId>;
+}
+
+impl<Call> SpaceCallInspector<Call> for () {
+    fn can_act_as_space(_call: &Call) -> bool {
+        false
+    }
+
+    fn target_space(_call: &Call) -> Option<SpaceId> {
+        None
+    }
+}
+
+/// A helper to build calls on behalf of a space in benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<Call> {
+    /// A call targeted at a given space, that can be dispatched on behalf of a space.
+    fn space_call(space_id: SpaceId) -> Call;
+}
@@ -0,0 +1,139 @@
+
+//! Weights for pallet_space_treasury
+//!
+//! THESE WEIGHTS ARE NOT BENCHMARKED YET. They were estimated by hand from similar calls
+//! of other pallets, and must be replaced by running the benchmarks of this pallet:
+
+// Command to generate this file:
+    // ./scripts/../target/release/subsocial-collator
+    // benchmark
+    // pallet
+    // --chain
+    // dev
+    // --execution
+    // wasm
+    // --wasm-execution
+    // Compiled
+    // --pallet
+    // pallet_space_treasury
+    // --extrinsic
+    // *
+    // --steps
+    // 50
+    // --repeat
+    // 20
+    // --heap-pages
+    // 4096
+    // --output
+    // pallets/space-treasury/src/weights.rs
+    // --template
+    // ./.maintain/weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(non_snake_case)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_space_treasury.
+pub trait WeightInfo {
+    fn deposit() -> Weight;
+    fn spend() -> Weight;
+    fn set_role_spending_limit() -> Weight;
+    fn act_as_space() -> Weight;
+}
+
+/// Weights for pallet_space_treasury using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+        impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: System Account (r:2 w:2)
+        fn deposit() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(49_877_000)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: SpaceTreasury SpendingLimitByRoleId (r:1 w:0)
+            // Storage: SpaceTreasury SpentByRoleId (r:1 w:1)
+            // Storage: System Account (r:2 w:2)
+        fn spend() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(68_412_000)
+            .saturating_add(T::DbWeight::get().reads(8))
+            .saturating_add(T::DbWeight::get().writes(3))
+        }
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleRankById (r:1 w:0)
+            // Storage: SpaceTreasury SpendingLimitByRoleId (r:0 w:1)
+        fn set_role_spending_limit() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(31_650_000)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(1))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleById (r:1 w:0)
+        fn act_as_space() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(24_500_000)
+            .saturating_add(T::DbWeight::get().reads(4))
+        }
+    }
+
+    // For backwards compatibility and tests
+    impl WeightInfo for () {
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: System Account (r:2 w:2)
+        fn deposit() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(49_877_000)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: SpaceTreasury SpendingLimitByRoleId (r:1 w:0)
+            // Storage: SpaceTreasury SpentByRoleId (r:1 w:1)
+            // Storage: System Account (r:2 w:2)
+        fn spend() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(68_412_000)
+            .saturating_add(RocksDbWeight::get().reads(8))
+            .saturating_add(RocksDbWeight::get().writes(3))
+        }
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleRankById (r:1 w:0)
+            // Storage: SpaceTreasury SpendingLimitByRoleId (r:0 w:1)
+        fn set_role_spending_limit() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(31_650_000)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(1))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleById (r:1 w:0)
+        fn act_as_space() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(24_500_000)
+            .saturating_add(RocksDbWeight::get().reads(4))
+        }
+    }
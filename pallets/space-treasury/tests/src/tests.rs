@@ -0,0 +1,243 @@
+use frame_support::{assert_noop, assert_ok};
+
+use pallet_roles::Error as RolesError;
+use pallet_space_follows::Error as SpaceFollowsError;
+use pallet_space_treasury::Error as SpaceTreasuryError;
+use pallet_spaces::Error as SpacesError;
+use subsocial_support::Content;
+
+use crate::{mock::*, tests_utils::*};
+
+#[test]
+fn deposit_should_work() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_deposit(None, None, Some(50)));
+
+        assert_eq!(treasury_balance(), 50);
+        assert_eq!(Balances::free_balance(ACCOUNT2), 50);
+    });
+}
+
+#[test]
+fn deposit_should_fail_when_space_not_found() {
+    ExtBuilder::build().execute_with(|| {
+        assert_noop!(_deposit(None, None, None), SpacesError::<Test>::SpaceNotFound);
+    });
+}
+
+#[test]
+fn deposit_should_fail_with_zero_amount() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(_deposit(None, None, Some(0)), SpaceTreasuryError::<Test>::ZeroAmount);
+    });
+}
+
+#[test]
+fn delete_space_should_return_treasury_funds_to_owner() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_deposit(None, None, Some(50)));
+        assert_ok!(Balances::transfer(
+            RuntimeOrigin::signed(ACCOUNT3),
+            space_actor_account(SPACE1),
+            20
+        ));
+
+        assert_ok!(_delete_default_space());
+
+        assert_eq!(treasury_balance(), 0);
+        assert_eq!(Balances::free_balance(space_actor_account(SPACE1)), 0);
+        assert_eq!(Balances::free_balance(ACCOUNT1), 170);
+    });
+}
+
+#[test]
+fn spend_should_work_when_space_owner() {
+    ExtBuilder::build_with_funded_treasury_and_role().execute_with(|| {
+        assert_ok!(_spend(None, Some(30)));
+
+        assert_eq!(treasury_balance(), 20);
+        assert_eq!(Balances::free_balance(ACCOUNT3), 130);
+    });
+}
+
+#[test]
+fn spend_should_fail_when_account_has_no_permission() {
+    ExtBuilder::build_with_funded_treasury_and_role().execute_with(|| {
+        assert_noop!(
+            _spend(Some(RuntimeOrigin::signed(ACCOUNT3)), None),
+            SpaceTreasuryError::<Test>::NoPermissionToSpendTreasury
+        );
+    });
+}
+
+#[test]
+fn spend_should_work_via_role_without_limit() {
+    ExtBuilder::build_with_funded_treasury_and_role().execute_with(|| {
+        assert_ok!(_spend(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(40)));
+        assert_eq!(treasury_balance(), 10);
+    });
+}
+
+#[test]
+fn spend_should_fail_when_role_limit_exceeded() {
+    ExtBuilder::build_with_funded_treasury_and_role().execute_with(|| {
+        assert_ok!(_set_role_spending_limit(None, Some(20)));
+        assert_eq!(SpaceTreasury::spending_limit_by_role_id(ROLE1), Some(20));
+
+        assert_noop!(
+            _spend(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(21)),
+            SpaceTreasuryError::<Test>::SpendingLimitExceeded
+        );
+        assert_ok!(_spend(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(20)));
+
+        // Limits do not apply to the space owner
+        assert_ok!(_spend(None, Some(30)));
+    });
+}
+
+#[test]
+fn spend_should_fail_when_role_limit_exceeded_by_several_payments() {
+    ExtBuilder::build_with_funded_treasury_and_role().execute_with(|| {
+        assert_ok!(_set_role_spending_limit(None, Some(20)));
+
+        assert_ok!(_spend(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(15)));
+        assert_eq!(SpaceTreasury::spent_by_role_id(ROLE1), (0, 15));
+
+        assert_noop!(
+            _spend(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(6)),
+            SpaceTreasuryError::<Test>::SpendingLimitExceeded
+        );
+        assert_ok!(_spend(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(5)));
+        assert_eq!(treasury_balance(), 30);
+    });
+}
+
+#[test]
+fn spend_should_reset_role_limit_in_next_spending_period() {
+    ExtBuilder::build_with_funded_treasury_and_role().execute_with(|| {
+        assert_ok!(_set_role_spending_limit(None, Some(20)));
+        assert_ok!(_spend(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(20)));
+
+        // Spending period is 10 blocks in tests
+        System::set_block_number(10);
+
+        assert_ok!(_spend(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(20)));
+        assert_eq!(SpaceTreasury::spent_by_role_id(ROLE1), (1, 20));
+        assert_eq!(treasury_balance(), 10);
+    });
+}
+
+#[test]
+fn spend_should_fail_when_permission_is_not_granted_via_role() {
+    ExtBuilder::build_with_funded_treasury_and_role().execute_with(|| {
+        assert_ok!(_allow_everyone_to_spend_treasury());
+
+        assert_noop!(
+            _spend(Some(RuntimeOrigin::signed(ACCOUNT3)), None),
+            SpaceTreasuryError::<Test>::NoPermissionToSpendTreasury
+        );
+    });
+}
+
+#[test]
+fn spend_should_fail_when_treasury_has_insufficient_balance() {
+    ExtBuilder::build_with_funded_treasury_and_role().execute_with(|| {
+        assert_noop!(_spend(None, Some(51)), pallet_balances::Error::<Test>::InsufficientBalance);
+    });
+}
+
+#[test]
+fn set_role_spending_limit_should_fail_when_account_has_no_permission() {
+    ExtBuilder::build_with_funded_treasury_and_role().execute_with(|| {
+        assert_noop!(
+            _set_role_spending_limit(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(20)),
+            RolesError::<Test>::NoPermissionToManageRoles
+        );
+    });
+}
+
+#[test]
+fn set_role_spending_limit_should_fail_when_role_rank_is_too_high() {
+    ExtBuilder::build_with_funded_treasury_and_role().execute_with(|| {
+        assert_ok!(_create_role_manager(ACCOUNT3)); // RoleId 2
+        assert_ok!(Roles::set_role_rank(RuntimeOrigin::signed(ACCOUNT1), ROLE1, 1));
+
+        assert_noop!(
+            _set_role_spending_limit(Some(RuntimeOrigin::signed(ACCOUNT3)), Some(20)),
+            RolesError::<Test>::RoleRankTooHigh
+        );
+    });
+}
+
+#[test]
+fn spending_limit_should_be_removed_with_role() {
+    ExtBuilder::build_with_funded_treasury_and_role().execute_with(|| {
+        assert_ok!(_set_role_spending_limit(None, Some(20)));
+        assert_ok!(_spend(Some(RuntimeOrigin::signed(ACCOUNT2)), Some(5)));
+
+        assert_ok!(Roles::delete_role(RuntimeOrigin::signed(ACCOUNT1), ROLE1, 1));
+
+        assert!(SpaceTreasury::spending_limit_by_role_id(ROLE1).is_none());
+        assert_eq!(SpaceTreasury::spent_by_role_id(ROLE1), (0, 0));
+    });
+}
+
+#[test]
+fn act_as_space_should_work_when_space_owner() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_act_as_space(None, follow_space_call(SPACE1)));
+
+        assert!(SpaceFollows::space_followed_by_account((space_actor_account(SPACE1), SPACE1)));
+    });
+}
+
+#[test]
+fn act_as_space_should_fail_when_account_has_no_permission() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _act_as_space(Some(RuntimeOrigin::signed(ACCOUNT2)), follow_space_call(SPACE1)),
+            SpaceTreasuryError::<Test>::NoPermissionToRepresentSpace
+        );
+    });
+}
+
+#[test]
+fn act_as_space_should_check_permission_by_target_space() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(Spaces::create_space(RuntimeOrigin::signed(ACCOUNT3), Content::None, None));
+        assert_ok!(_create_role_to_represent_space_internally()); // RoleId 1
+
+        assert_ok!(_act_as_space(Some(RuntimeOrigin::signed(ACCOUNT2)), follow_space_call(SPACE1)));
+        assert_noop!(
+            _act_as_space(Some(RuntimeOrigin::signed(ACCOUNT2)), follow_space_call(SPACE2)),
+            SpaceTreasuryError::<Test>::NoPermissionToRepresentSpace
+        );
+
+        // The space owner can represent the space externally
+        assert_ok!(_act_as_space(None, follow_space_call(SPACE2)));
+        assert!(SpaceFollows::space_followed_by_account((space_actor_account(SPACE1), SPACE2)));
+    });
+}
+
+#[test]
+fn act_as_space_should_return_actual_weight_when_call_fails() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_act_as_space(None, follow_space_call(SPACE1)));
+
+        let err = _act_as_space(None, follow_space_call(SPACE1)).unwrap_err();
+        assert_eq!(err.error, SpaceFollowsError::<Test>::AlreadySpaceFollower.into());
+        assert!(err.post_info.actual_weight.is_some());
+    });
+}
+
+#[test]
+fn act_as_space_should_fail_when_call_is_not_allowed() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        let call = Box::new(RuntimeCall::Balances(pallet_balances::Call::transfer {
+            dest: ACCOUNT1,
+            value: 10,
+        }));
+
+        assert_noop!(_act_as_space(None, call), SpaceTreasuryError::<Test>::CallCannotActAsSpace);
+    });
+}
@@ -0,0 +1,187 @@
+use frame_support::{assert_ok, pallet_prelude::*};
+use sp_core::storage::Storage;
+use sp_io::TestExternalities;
+
+use pallet_permissions::{SpacePermission as SP, SpacePermissions};
+use pallet_roles::RoleId;
+use pallet_spaces::types::SpaceUpdate;
+use subsocial_support::{Content, SpaceId, User};
+
+use crate::mock::*;
+
+////// Ext Builder
+
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+    fn configure_storages(storage: &mut Storage) {
+        let mut accounts = Vec::new();
+        for account in ACCOUNT1..=ACCOUNT3 {
+            accounts.push(account);
+        }
+
+        let _ = pallet_balances::GenesisConfig::<Test> {
+            balances: accounts.iter().cloned().map(|k| (k, 100)).collect(),
+        }
+        .assimilate_storage(storage);
+    }
+
+    /// Default ext configuration with BlockNumber 1
+    pub fn build() -> TestExternalities {
+        let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+        Self::configure_storages(&mut storage);
+
+        let mut ext = TestExternalities::from(storage);
+        ext.execute_with(|| System::set_block_number(1));
+
+        ext
+    }
+
+    /// Custom ext configuration with SpaceId 1 and BlockNumber 1
+    pub fn build_with_space() -> TestExternalities {
+        let mut ext = Self::build();
+        ext.execute_with(|| assert_ok!(_create_default_space()));
+        ext
+    }
+
+    /// Custom ext configuration with a space, that has a treasury funded by ACCOUNT2,
+    /// and a role with `SpendTreasury` permission granted to ACCOUNT2
+    pub fn build_with_funded_treasury_and_role() -> TestExternalities {
+        let mut ext = Self::build_with_space();
+        ext.execute_with(|| {
+            assert_ok!(_deposit(None, None, Some(50)));
+            assert_ok!(_create_role_with_spend_permission()); // RoleId 1
+        });
+        ext
+    }
+}
+
+////// Consts
+
+pub(crate) const ACCOUNT1: AccountId = 1;
+pub(crate) const ACCOUNT2: AccountId = 2;
+pub(crate) const ACCOUNT3: AccountId = 3;
+
+pub(crate) const SPACE1: SpaceId = 1001;
+pub(crate) const SPACE2: SpaceId = 1002;
+
+pub(crate) const ROLE1: RoleId = 1;
+
+///////////// Space Utils
+
+pub(crate) fn _create_default_space() -> DispatchResult {
+    Spaces::create_space(RuntimeOrigin::signed(ACCOUNT1), Content::None, None)
+}
+
+pub(crate) fn _delete_default_space() -> DispatchResult {
+    Spaces::delete_space(RuntimeOrigin::signed(ACCOUNT1), SPACE1)
+}
+
+pub(crate) fn _allow_everyone_to_spend_treasury() -> DispatchResult {
+    let permissions = SpacePermissions {
+        everyone: Some(vec![SP::SpendTreasury].into_iter().collect()),
+        ..Default::default()
+    };
+    Spaces::update_space(
+        RuntimeOrigin::signed(ACCOUNT1),
+        SPACE1,
+        SpaceUpdate { content: None, hidden: None, permissions: Some(Some(permissions)) },
+    )
+}
+
+///////////// Roles Utils
+
+pub(crate) fn _create_role_with_spend_permission() -> DispatchResult {
+    Roles::create_role(
+        RuntimeOrigin::signed(ACCOUNT1),
+        SPACE1,
+        None,
+        Content::None,
+        vec![SP::SpendTreasury],
+        0,
+    )?;
+    Roles::grant_role(RuntimeOrigin::signed(ACCOUNT1), ROLE1, vec![User::Account(ACCOUNT2)], None)
+}
+
+pub(crate) fn _create_role_manager(account: AccountId) -> DispatchResult {
+    let role_id = Roles::next_role_id();
+    Roles::create_role(
+        RuntimeOrigin::signed(ACCOUNT1),
+        SPACE1,
+        None,
+        Content::None,
+        vec![SP::ManageRoles],
+        1,
+    )?;
+    Roles::grant_role(RuntimeOrigin::signed(ACCOUNT1), role_id, vec![User::Account(account)], None)
+}
+
+pub(crate) fn _create_role_to_represent_space_internally() -> DispatchResult {
+    Roles::create_role(
+        RuntimeOrigin::signed(ACCOUNT1),
+        SPACE1,
+        None,
+        Content::None,
+        vec![SP::RepresentSpaceInternally],
+        0,
+    )?;
+    Roles::grant_role(RuntimeOrigin::signed(ACCOUNT1), ROLE1, vec![User::Account(ACCOUNT2)], None)
+}
+
+//////// Space treasury utils
+
+pub(crate) fn treasury_balance() -> Balance {
+    Balances::free_balance(SpaceTreasury::space_treasury_account(SPACE1))
+}
+
+pub(crate) fn _deposit(
+    origin: Option<RuntimeOrigin>,
+    space_id: Option<SpaceId>,
+    amount: Option<Balance>,
+) -> DispatchResult {
+    SpaceTreasury::deposit(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT2)),
+        space_id.unwrap_or(SPACE1),
+        amount.unwrap_or(10),
+    )
+}
+
+pub(crate) fn _spend(origin: Option<RuntimeOrigin>, amount: Option<Balance>) -> DispatchResult {
+    SpaceTreasury::spend(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        SPACE1,
+        ACCOUNT3,
+        amount.unwrap_or(10),
+    )
+}
+
+pub(crate) fn _set_role_spending_limit(
+    origin: Option<RuntimeOrigin>,
+    limit: Option<Balance>,
+) -> DispatchResult {
+    SpaceTreasury::set_role_spending_limit(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        ROLE1,
+        limit,
+    )
+}
+
+pub(crate) fn space_actor_account(space_id: SpaceId) -> AccountId {
+    SpaceTreasury::space_actor_account(space_id)
+}
+
+pub(crate) fn follow_space_call(space_id: SpaceId) -> Box<RuntimeCall> {
+    Box::new(RuntimeCall::SpaceFollows(pallet_space_follows::Call::follow_space { space_id }))
+}
+
+pub(crate) fn _act_as_space(
+    origin: Option<RuntimeOrigin>,
+    call: Box<RuntimeCall>,
+) -> DispatchResultWithPostInfo {
+    SpaceTreasury::act_as_space(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        SPACE1,
+        call,
+    )
+}
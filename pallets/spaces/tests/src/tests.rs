@@ -1,8 +1,27 @@
-use frame_support::{assert_noop, assert_ok};
-
-use pallet_permissions::SpacePermission as SP;
-use pallet_spaces::Error as SpacesError;
-use subsocial_support::{mock_functions::*, ContentError, ModerationError};
+use frame_support::{
+    assert_noop, assert_ok,
+    pallet_prelude::{Encode, StorageVersion},
+    storage::unhashed,
+    traits::{GetStorageVersion, OnIdle, OnRuntimeUpgrade},
+    weights::Weight,
+};
+
+use pallet_permissions::{
+    default_permissions::DefaultSpacePermissions,
+    migration::{v0 as permissions_v0, v1 as permissions_v1},
+    NamespacedPermission, SpacePermission as SP,
+};
+use pallet_posts::{Error as PostsError, MaxCommentDepthSetting};
+use pallet_spaces::{
+    migration::{deposits::ExemptLegacySpaces, v1::MigrateToV1, v2::MigrateToV2, v3::MigrateToV3},
+    types::SpacePrivacy,
+    Error as SpacesError, SpaceById,
+};
+use sp_runtime::traits::BadOrigin;
+use subsocial_support::{
+    mock_functions::*, traits::SpaceSettingsProvider, Content, ContentError, ModerationError,
+    SpaceSettingsError, User,
+};
 
 use crate::{mock::*, tests_utils::*};
 
@@ -58,6 +77,36 @@ fn create_space_should_work_with_permissions_override() {
     });
 }
 
+#[test]
+fn create_space_should_work_with_root_origin_mapped_to_account() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(_create_space(Some(RuntimeOrigin::root()), None, None));
+
+        let space = Spaces::space_by_id(SPACE1).unwrap();
+        assert_eq!(space.owner, RootSpaceOwner::get());
+
+        // The mapped account is the space owner, so the same origin can manage the space,
+        // while the account itself can still sign for it.
+        assert_ok!(_update_space(
+            Some(RuntimeOrigin::root()),
+            None,
+            Some(update_for_space_content(updated_space_content()))
+        ));
+        assert_ok!(_update_space(
+            Some(RuntimeOrigin::signed(RootSpaceOwner::get())),
+            None,
+            Some(space_update(None, Some(true)))
+        ));
+    });
+}
+
+#[test]
+fn create_space_should_fail_with_unsigned_origin() {
+    ExtBuilder::build().execute_with(|| {
+        assert_noop!(_create_space(Some(RuntimeOrigin::none()), None, None), BadOrigin);
+    });
+}
+
 #[test]
 fn create_post_should_work_overridden_space_permission_for_everyone() {
     ExtBuilder::build_with_space_and_custom_permissions(
@@ -91,6 +140,20 @@ fn create_space_should_fail_when_ipfs_cid_is_invalid() {
     });
 }
 
+#[test]
+fn create_space_should_fail_with_undeclared_namespaced_permission() {
+    ExtBuilder::build().execute_with(|| {
+        let mut permissions = DefaultSpacePermissions::get();
+        permissions.everyone =
+            Some(vec![NamespacedPermission::new(*b"unknown_", 0).into()].into_iter().collect());
+
+        assert_noop!(
+            _create_space(None, None, Some(Some(permissions))),
+            pallet_permissions::Error::<Test>::UnknownNamespacedPermission
+        );
+    });
+}
+
 #[test]
 fn update_space_should_work() {
     ExtBuilder::build_with_space().execute_with(|| {
@@ -190,6 +253,571 @@ fn update_space_should_fail_when_no_right_permission_in_account_roles() {
     );
 }
 
+#[test]
+fn set_space_handle_should_work() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_set_space_handle(None, None, None));
+
+        // Handle should be stored lowercased and resolvable in both directions
+        assert_eq!(Spaces::handle_by_space_id(SPACE1).unwrap().to_vec(), b"subsocial".to_vec());
+        assert_eq!(Spaces::space_id_by_handle(Spaces::handle_by_space_id(SPACE1).unwrap()), Some(SPACE1));
+        assert_eq!(Spaces::space_id_by_any_case_handle(b"SubSocial"), Some(SPACE1));
+    });
+}
+
+#[test]
+fn set_space_handle_should_replace_and_remove_previous_handle() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_set_space_handle(None, None, None));
+        assert_ok!(_set_space_handle(None, None, Some(Some(b"new_handle".to_vec()))));
+
+        assert_eq!(Spaces::space_id_by_any_case_handle(b"subsocial"), None);
+        assert_eq!(Spaces::space_id_by_any_case_handle(b"new_handle"), Some(SPACE1));
+
+        assert_ok!(_set_space_handle(None, None, Some(None)));
+
+        assert!(Spaces::handle_by_space_id(SPACE1).is_none());
+        assert_eq!(Spaces::space_id_by_any_case_handle(b"new_handle"), None);
+    });
+}
+
+#[test]
+fn set_space_handle_should_fail_when_handle_is_not_unique() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_create_default_space()); // SpaceId 2
+        assert_ok!(_set_space_handle(None, None, None));
+
+        // Handles are case-insensitive, so a handle in different case is a duplicate
+        assert_noop!(
+            _set_space_handle(None, Some(SPACE2), Some(Some(b"SUBSOCIAL".to_vec()))),
+            SpacesError::<Test>::SpaceHandleIsNotUnique
+        );
+    });
+}
+
+#[test]
+fn set_space_handle_should_fail_when_handle_not_changed() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        // Space has no handle yet, so there is nothing to remove
+        assert_noop!(
+            _set_space_handle(None, None, Some(None)),
+            SpacesError::<Test>::SpaceHandleNotChanged
+        );
+
+        assert_ok!(_set_space_handle(None, None, None));
+
+        assert_noop!(
+            _set_space_handle(None, None, Some(Some(b"subSocial".to_vec()))),
+            SpacesError::<Test>::SpaceHandleNotChanged
+        );
+    });
+}
+
+#[test]
+fn set_space_handle_should_fail_when_handle_is_invalid() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _set_space_handle(None, None, Some(Some(b"abc".to_vec()))),
+            ModerationError::HandleIsTooShort
+        );
+        assert_noop!(
+            _set_space_handle(None, None, Some(Some(vec![b'a'; 51]))),
+            ModerationError::HandleIsTooLong
+        );
+        assert_noop!(
+            _set_space_handle(None, None, Some(Some(b"sub-social".to_vec()))),
+            ModerationError::HandleContainsInvalidChars
+        );
+    });
+}
+
+#[test]
+fn set_space_handle_should_fail_when_account_has_no_permission() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _set_space_handle(Some(RuntimeOrigin::signed(ACCOUNT2)), None, None),
+            SpacesError::<Test>::NoPermissionToUpdateSpace
+        );
+    });
+}
+
+#[test]
+fn archive_space_should_work() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_archive_space(None, None));
+        assert!(Spaces::is_space_archived(SPACE1));
+
+        assert_ok!(_unarchive_space(None, None));
+        assert!(!Spaces::is_space_archived(SPACE1));
+    });
+}
+
+#[test]
+fn archive_space_should_fail_when_not_a_space_owner() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _archive_space(Some(RuntimeOrigin::signed(ACCOUNT2)), None),
+            SpacesError::<Test>::NotASpaceOwner
+        );
+    });
+}
+
+#[test]
+fn archive_space_should_fail_when_space_is_already_archived() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_archive_space(None, None));
+        assert_noop!(_archive_space(None, None), SpacesError::<Test>::SpaceIsArchived);
+    });
+}
+
+#[test]
+fn unarchive_space_should_fail_when_space_is_not_archived() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(_unarchive_space(None, None), SpacesError::<Test>::SpaceIsNotArchived);
+    });
+}
+
+#[test]
+fn archived_space_should_be_read_only() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_archive_space(None, None));
+
+        assert_noop!(_create_default_post(), SpacesError::<Test>::SpaceIsArchived);
+        assert_noop!(_default_follow_space(), SpacesError::<Test>::SpaceIsArchived);
+    });
+}
+
+#[test]
+fn delete_space_should_work() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateSpace]).execute_with(
+        || {
+            assert_ok!(_default_follow_space());
+            assert_ok!(_set_space_handle(None, None, None));
+            assert_ok!(Profiles::set_profile(RuntimeOrigin::signed(ACCOUNT1), SPACE1));
+
+            assert_ok!(_delete_space(None, None));
+
+            // Check that space storages are cleaned
+            assert!(Spaces::space_by_id(SPACE1).is_none());
+            assert!(Spaces::space_ids_by_owner(ACCOUNT1).is_empty());
+            assert_eq!(Spaces::space_id_by_any_case_handle(b"subsocial"), None);
+
+            assert!(Profiles::profile_space_id_by_account(ACCOUNT1).is_none());
+
+            // Check that follows and roles are cleaned in the following blocks
+            assert!(SpaceFollows::space_followed_by_account((ACCOUNT2, SPACE1)));
+            assert!(Roles::role_by_id(ROLE1).is_some());
+
+            <AllPalletsWithSystem as OnIdle<BlockNumber>>::on_idle(2, Weight::MAX);
+
+            assert!(SpaceFollows::space_followers(SPACE1).is_empty());
+            assert!(SpaceFollows::spaces_followed_by_account(ACCOUNT2).is_empty());
+            assert!(Roles::role_ids_by_space_id(SPACE1).is_empty());
+            assert!(Roles::role_by_id(ROLE1).is_none());
+            assert!(Roles::role_ids_by_user_in_space(User::Account(ACCOUNT2), SPACE1).is_empty());
+
+            // Unfollowing a deleted space should not be possible
+            assert_noop!(
+                SpaceFollows::unfollow_space(RuntimeOrigin::signed(ACCOUNT2), SPACE1),
+                SpacesError::<Test>::SpaceNotFound
+            );
+        },
+    );
+}
+
+#[test]
+fn delete_space_should_fail_when_space_has_posts() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_create_default_post());
+        assert_noop!(_delete_space(None, None), SpacesError::<Test>::SpaceIsNotEmpty);
+    });
+}
+
+#[test]
+fn delete_space_should_fail_when_not_a_space_owner() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _delete_space(Some(RuntimeOrigin::signed(ACCOUNT2)), None),
+            SpacesError::<Test>::NotASpaceOwner
+        );
+    });
+}
+
+#[test]
+fn update_space_settings_should_work() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_eq!(
+            Spaces::space_setting::<MaxCommentDepthSetting<Test>>(SPACE1),
+            MaxCommentDepth::get()
+        );
+
+        assert_ok!(_update_space_settings(None, None, vec![max_comment_depth_setting(Some(3))]));
+        assert_eq!(Spaces::space_setting::<MaxCommentDepthSetting<Test>>(SPACE1), 3);
+
+        // Reset a setting to its' default value
+        assert_ok!(_update_space_settings(None, None, vec![max_comment_depth_setting(None)]));
+        assert_eq!(
+            Spaces::space_setting::<MaxCommentDepthSetting<Test>>(SPACE1),
+            MaxCommentDepth::get()
+        );
+    });
+}
+
+#[test]
+fn update_space_settings_should_fail_when_no_updates_provided() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _update_space_settings(None, None, vec![]),
+            SpacesError::<Test>::NoUpdatesForSpacesSettings
+        );
+        assert_noop!(
+            _update_space_settings(None, None, vec![max_comment_depth_setting(None)]),
+            SpacesError::<Test>::NoUpdatesForSpacesSettings
+        );
+    });
+}
+
+#[test]
+fn update_space_settings_should_fail_when_setting_is_unknown() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _update_space_settings(None, None, vec![(*b"unknown/setting_", Some(vec![1]))]),
+            SpaceSettingsError::UnknownSpaceSetting
+        );
+    });
+}
+
+#[test]
+fn update_space_settings_should_fail_when_value_is_invalid() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        let (key, _) = max_comment_depth_setting(None);
+        assert_noop!(
+            _update_space_settings(None, None, vec![(key, Some(vec![1, 2]))]),
+            SpaceSettingsError::InvalidSpaceSettingValue
+        );
+        assert_noop!(
+            _update_space_settings(None, None, vec![(key, Some(vec![0; 257]))]),
+            SpaceSettingsError::SpaceSettingValueTooLong
+        );
+    });
+}
+
+#[test]
+fn update_space_settings_should_fail_when_account_has_no_permission() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _update_space_settings(
+                Some(RuntimeOrigin::signed(ACCOUNT2)),
+                None,
+                vec![max_comment_depth_setting(Some(3))]
+            ),
+            SpacesError::<Test>::NoPermissionToUpdateSpaceSettings
+        );
+    });
+}
+
+#[test]
+fn update_space_privacy_should_work() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_eq!(Spaces::space_privacy(SPACE1), SpacePrivacy::Public);
+
+        assert_ok!(_update_space_privacy(None, None, None));
+        assert_eq!(Spaces::space_privacy(SPACE1), SpacePrivacy::MembersOnly);
+
+        assert_ok!(_update_space_privacy(None, None, Some(SpacePrivacy::Public)));
+        assert_eq!(Spaces::space_privacy(SPACE1), SpacePrivacy::Public);
+    });
+}
+
+#[test]
+fn update_space_privacy_should_fail_when_not_a_space_owner() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _update_space_privacy(Some(RuntimeOrigin::signed(ACCOUNT2)), None, None),
+            SpacesError::<Test>::NotASpaceOwner
+        );
+    });
+}
+
+#[test]
+fn update_space_privacy_should_fail_when_privacy_not_changed() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_noop!(
+            _update_space_privacy(None, None, Some(SpacePrivacy::Public)),
+            SpacesError::<Test>::SpacePrivacyNotChanged
+        );
+    });
+}
+
+#[test]
+fn members_only_space_should_not_be_accessible_by_non_member() {
+    let permissions = permissions_where_everyone_can_create_post();
+    ExtBuilder::build_with_space_and_custom_permissions(permissions).execute_with(|| {
+        assert_ok!(_update_space_privacy(None, None, None));
+
+        assert_noop!(_default_follow_space(), SpacesError::<Test>::NotASpaceMember);
+        assert_noop!(
+            _create_post(Some(RuntimeOrigin::signed(ACCOUNT2)), None, None, None),
+            PostsError::<Test>::NoPermissionToCreatePosts
+        );
+
+        // Space owner still has access to the space
+        assert_ok!(_create_default_post());
+    });
+}
+
+#[test]
+fn members_only_space_should_be_accessible_by_member() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::CreatePosts]).execute_with(
+        || {
+            assert_ok!(_update_space_privacy(None, None, None));
+
+            assert_ok!(_default_follow_space());
+            assert_ok!(_create_post(Some(RuntimeOrigin::signed(ACCOUNT2)), None, None, None));
+        },
+    );
+}
+
+#[test]
+fn create_space_should_reserve_deposit() {
+    ExtBuilder::build().execute_with(|| {
+        BaseSpaceDeposit::set(10);
+
+        assert_ok!(_create_default_space());
+
+        assert_eq!(Spaces::space_deposit(SPACE1), 10);
+        assert_eq!(Balances::reserved_balance(ACCOUNT1), 10);
+        assert_eq!(Balances::free_balance(ACCOUNT1), 90);
+    });
+}
+
+#[test]
+fn create_space_should_fail_when_deposit_cannot_be_reserved() {
+    ExtBuilder::build().execute_with(|| {
+        BaseSpaceDeposit::set(1000);
+
+        assert_noop!(
+            _create_default_space(),
+            SpacesError::<Test>::InsufficientBalanceToReserveDeposit
+        );
+    });
+}
+
+#[test]
+fn update_space_should_adjust_deposit_to_content_size() {
+    ExtBuilder::build().execute_with(|| {
+        StorageByteDeposit::set(1);
+        assert_ok!(_create_space_with_content(Content::None));
+
+        let initial_deposit = Spaces::space_deposit(SPACE1);
+        assert!(initial_deposit > 0);
+
+        assert_ok!(_update_space(None, None, Some(update_for_space_content(space_content_ipfs()))));
+
+        let updated_deposit = Spaces::space_deposit(SPACE1);
+        assert!(updated_deposit > initial_deposit);
+        assert_eq!(Balances::reserved_balance(ACCOUNT1), updated_deposit);
+    });
+}
+
+#[test]
+fn update_space_should_not_change_deposit_of_owner_when_updated_by_another_account() {
+    ExtBuilder::build_with_a_few_roles_granted_to_account2(vec![SP::UpdateSpace]).execute_with(
+        || {
+            StorageByteDeposit::set(1);
+            let reserved_by_owner = Balances::reserved_balance(ACCOUNT1);
+
+            assert_ok!(_update_space(
+                Some(RuntimeOrigin::signed(ACCOUNT2)),
+                Some(SPACE1),
+                Some(update_for_space_content(updated_space_content()))
+            ));
+
+            assert_eq!(Spaces::space_deposit(SPACE1), 0);
+            assert_eq!(Balances::reserved_balance(ACCOUNT1), reserved_by_owner);
+        },
+    );
+}
+
+#[test]
+fn delete_space_should_unreserve_deposit() {
+    ExtBuilder::build().execute_with(|| {
+        BaseSpaceDeposit::set(10);
+        assert_ok!(_create_default_space());
+
+        assert_ok!(_delete_space(None, None));
+
+        assert_eq!(Spaces::space_deposit(SPACE1), 0);
+        assert_eq!(Balances::reserved_balance(ACCOUNT1), 0);
+        assert_eq!(Balances::free_balance(ACCOUNT1), 100);
+    });
+}
+
+#[test]
+fn update_space_should_not_reserve_deposit_of_legacy_space() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        StorageByteDeposit::set(1);
+
+        ExemptLegacySpaces::<Test>::on_runtime_upgrade();
+        assert_eq!(Spaces::first_space_id_with_deposit(), SPACE2);
+
+        assert_ok!(_update_space(None, None, Some(update_for_space_content(space_content_ipfs()))));
+
+        assert_eq!(Spaces::space_deposit(SPACE1), 0);
+        assert_eq!(Balances::reserved_balance(ACCOUNT1), 0);
+
+        // Spaces created after the upgrade are charged as usual.
+        assert_ok!(_create_default_space());
+        assert!(Spaces::space_deposit(SPACE2) > 0);
+    });
+}
+
+#[test]
+fn space_posts_counters_should_be_updated() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_create_default_post()); // PostId 1
+
+        let space = Spaces::space_by_id(SPACE1).unwrap();
+        assert_eq!(space.posts_count, 1);
+        assert_eq!(space.hidden_posts_count, 0);
+
+        assert_ok!(_set_post_hidden(true));
+        assert_eq!(Spaces::space_by_id(SPACE1).unwrap().hidden_posts_count, 1);
+
+        assert_ok!(_move_post_out_of_space());
+
+        let space = Spaces::space_by_id(SPACE1).unwrap();
+        assert_eq!(space.posts_count, 0);
+        assert_eq!(space.hidden_posts_count, 0);
+    });
+}
+
+#[test]
+fn space_followers_count_should_be_updated() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_default_follow_space());
+        assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 1);
+
+        assert_ok!(SpaceFollows::unfollow_space(RuntimeOrigin::signed(ACCOUNT2), SPACE1));
+        assert_eq!(Spaces::space_by_id(SPACE1).unwrap().followers_count, 0);
+    });
+}
+
+#[test]
+fn migration_to_v1_should_backfill_space_counters() {
+    ExtBuilder::build_with_space().execute_with(|| {
+        assert_ok!(_create_default_post()); // PostId 1
+        assert_ok!(_set_post_hidden(true));
+        assert_ok!(_default_follow_space());
+
+        // Store the space as it was before v1, i.e. without the trailing counters.
+        let space = Spaces::space_by_id(SPACE1).unwrap();
+        let mut old_space = space.encode();
+        old_space.truncate(old_space.len() - 3 * 4);
+        unhashed::put_raw(&SpaceById::<Test>::hashed_key_for(SPACE1), &old_space);
+        StorageVersion::new(0).put::<Spaces>();
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        // The counters are zeroed by the migration and recounted in `on_idle`.
+        let migrated_space = Spaces::space_by_id(SPACE1).unwrap();
+        assert_eq!(migrated_space.posts_count, 0);
+        assert_eq!(migrated_space.followers_count, 0);
+        assert_eq!(Spaces::next_space_id_to_recount(), Some(SPACE1));
+        assert_eq!(Spaces::on_chain_storage_version(), Spaces::current_storage_version());
+
+        <AllPalletsWithSystem as OnIdle<BlockNumber>>::on_idle(2, Weight::MAX);
+
+        assert_eq!(Spaces::space_by_id(SPACE1), Some(space));
+        assert_eq!(Spaces::next_space_id_to_recount(), None);
+    });
+}
+
+#[test]
+fn migration_to_v2_should_convert_space_permissions_to_bitsets() {
+    let permissions = permissions_where_follower_can_create_post();
+    ExtBuilder::build_with_space_and_custom_permissions(permissions.clone()).execute_with(|| {
+        let space = Spaces::space_by_id(SPACE1).unwrap();
+        assert_eq!(space.permissions, Some(permissions.clone()));
+
+        // Store the space as it was before v2, i.e. with permissions as ordered sets.
+        let to_old_set = |set: Option<pallet_permissions::SpacePermissionSet>| {
+            set.map(|set| set.iter().collect::<permissions_v0::SpacePermissionSet>())
+        };
+        let old_permissions = permissions_v0::SpacePermissions {
+            none: to_old_set(permissions.none),
+            everyone: to_old_set(permissions.everyone),
+            follower: to_old_set(permissions.follower),
+            space_owner: to_old_set(permissions.space_owner),
+        };
+        let old_space = (
+            space.id,
+            space.created.clone(),
+            space.edited,
+            space.owner.clone(),
+            space.content.clone(),
+            space.hidden,
+            Some(old_permissions),
+            space.posts_count,
+            space.hidden_posts_count,
+            space.followers_count,
+        );
+        unhashed::put_raw(&SpaceById::<Test>::hashed_key_for(SPACE1), &old_space.encode());
+        StorageVersion::new(1).put::<Spaces>();
+
+        MigrateToV2::<Test>::on_runtime_upgrade();
+
+        assert_eq!(Spaces::space_by_id(SPACE1), Some(space));
+        assert_eq!(Spaces::on_chain_storage_version(), Spaces::current_storage_version());
+
+        assert_ok!(_default_follow_space());
+        assert_ok!(_create_post(Some(RuntimeOrigin::signed(ACCOUNT2)), None, None, None));
+    });
+}
+
+#[test]
+fn migration_to_v3_should_convert_space_permission_bitsets() {
+    let permissions = permissions_where_follower_can_create_post();
+    ExtBuilder::build_with_space_and_custom_permissions(permissions.clone()).execute_with(|| {
+        let space = Spaces::space_by_id(SPACE1).unwrap();
+
+        // Store the space as it was before v3, i.e. with permissions as bitsets only.
+        let to_old_set = |set: Option<pallet_permissions::SpacePermissionSet>| {
+            set.map(|set| {
+                set.iter().fold(0u64, |bits, permission| bits | 1 << permission.encode()[0])
+            })
+        };
+        let old_permissions = permissions_v1::SpacePermissions {
+            none: to_old_set(permissions.none),
+            everyone: to_old_set(permissions.everyone),
+            follower: to_old_set(permissions.follower),
+            space_owner: to_old_set(permissions.space_owner),
+        };
+        let old_space = (
+            space.id,
+            space.created.clone(),
+            space.edited,
+            space.owner.clone(),
+            space.content.clone(),
+            space.hidden,
+            Some(old_permissions),
+            space.posts_count,
+            space.hidden_posts_count,
+            space.followers_count,
+        );
+        unhashed::put_raw(&SpaceById::<Test>::hashed_key_for(SPACE1), &old_space.encode());
+        StorageVersion::new(2).put::<Spaces>();
+
+        MigrateToV3::<Test>::on_runtime_upgrade();
+
+        assert_eq!(Spaces::space_by_id(SPACE1), Some(space));
+        assert_eq!(Spaces::on_chain_storage_version(), 3);
+
+        assert_ok!(_default_follow_space());
+        assert_ok!(_create_post(Some(RuntimeOrigin::signed(ACCOUNT2)), None, None, None));
+    });
+}
+
 // TODO: refactor or remove. Deprecated tests
 // Find public space ids tests
 // --------------------------------------------------------------------------------------------
@@ -12,12 +12,12 @@ use pallet_permissions::{
     default_permissions::DefaultSpacePermissions, SpacePermission as SP, SpacePermission,
     SpacePermissions,
 };
-use pallet_posts::PostExtension;
-use pallet_spaces::types::SpaceUpdate;
+use pallet_posts::{MaxCommentDepthSetting, PostExtension, PostUpdate};
+use pallet_spaces::types::{SpacePrivacy, SpaceUpdate};
 use subsocial_support::{
     mock_functions::valid_content_ipfs,
-    traits::{IsAccountBlocked, IsContentBlocked, IsPostBlocked, IsSpaceBlocked},
-    Content, PostId, SpaceId, User,
+    traits::{IsAccountBlocked, IsContentBlocked, IsPostBlocked, IsSpaceBlocked, SpaceSetting},
+    Content, PostId, SpaceId, SpaceSettingKey, User,
 };
 
 use crate::mock::*;
@@ -101,6 +101,8 @@ pub(crate) const ACCOUNT3: AccountId = 3;
 pub(crate) const SPACE1: SpaceId = 1001;
 pub(crate) const SPACE2: SpaceId = 1002;
 
+pub(crate) const POST1: PostId = 1;
+
 type RoleId = u64;
 
 pub(crate) const ROLE1: RoleId = 1;
@@ -274,6 +276,67 @@ pub(crate) fn _update_space(
     )
 }
 
+pub(crate) fn _set_space_handle(
+    origin: Option<RuntimeOrigin>,
+    space_id: Option<SpaceId>,
+    handle_opt: Option<Option<Vec<u8>>>,
+) -> DispatchResult {
+    Spaces::set_space_handle(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        space_id.unwrap_or(SPACE1),
+        handle_opt.unwrap_or_else(|| Some(b"Subsocial".to_vec())),
+    )
+}
+
+pub(crate) fn _archive_space(origin: Option<RuntimeOrigin>, space_id: Option<SpaceId>) -> DispatchResult {
+    Spaces::archive_space(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        space_id.unwrap_or(SPACE1),
+    )
+}
+
+pub(crate) fn _unarchive_space(origin: Option<RuntimeOrigin>, space_id: Option<SpaceId>) -> DispatchResult {
+    Spaces::unarchive_space(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        space_id.unwrap_or(SPACE1),
+    )
+}
+
+pub(crate) fn _delete_space(origin: Option<RuntimeOrigin>, space_id: Option<SpaceId>) -> DispatchResult {
+    Spaces::delete_space(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        space_id.unwrap_or(SPACE1),
+    )
+}
+
+pub(crate) fn _update_space_settings(
+    origin: Option<RuntimeOrigin>,
+    space_id: Option<SpaceId>,
+    settings: Vec<(SpaceSettingKey, Option<Vec<u8>>)>,
+) -> DispatchResult {
+    Spaces::update_space_settings(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        space_id.unwrap_or(SPACE1),
+        settings,
+    )
+}
+
+pub(crate) fn max_comment_depth_setting(depth: Option<u32>) -> (SpaceSettingKey, Option<Vec<u8>>) {
+    (MaxCommentDepthSetting::<Test>::KEY, depth.map(|d| d.encode()))
+}
+
+pub(crate) fn _update_space_privacy(
+    origin: Option<RuntimeOrigin>,
+    space_id: Option<SpaceId>,
+    privacy: Option<SpacePrivacy>,
+) -> DispatchResult {
+    Spaces::update_space_privacy(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        space_id.unwrap_or(SPACE1),
+        privacy.unwrap_or(SpacePrivacy::MembersOnly),
+    )
+}
+
 ///////////// Post Utils
 
 pub(crate) fn post_content_ipfs() -> Content {
@@ -302,6 +365,18 @@ pub(crate) fn _create_post(
     )
 }
 
+pub(crate) fn _set_post_hidden(hidden: bool) -> DispatchResult {
+    Posts::update_post(
+        RuntimeOrigin::signed(ACCOUNT1),
+        POST1,
+        PostUpdate { space_id: None, content: None, hidden: Some(hidden) },
+    )
+}
+
+pub(crate) fn _move_post_out_of_space() -> DispatchResult {
+    Posts::move_post(RuntimeOrigin::signed(ACCOUNT1), POST1, None)
+}
+
 //// Space follows utils
 
 pub(crate) fn _default_follow_space() -> DispatchResult {
@@ -339,6 +414,7 @@ pub(crate) fn _create_role(
         time_to_live.unwrap_or_default(), // Should return 'None'
         content.unwrap_or_else(default_role_content_ipfs),
         permissions.unwrap_or_else(permission_set_default),
+        0,
     )
 }
 
@@ -355,6 +431,7 @@ pub(crate) fn _grant_role(
         origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
         role_id.unwrap_or(ROLE1),
         users.unwrap_or_else(|| vec![User::Account(ACCOUNT2)]),
+        None,
     )
 }
 
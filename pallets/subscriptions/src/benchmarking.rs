@@ -0,0 +1,157 @@
+//! Subscriptions pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::{dispatch::DispatchError, ensure, traits::Currency, weights::Weight};
+use frame_system::RawOrigin;
+use pallet_permissions::SpacePermission;
+use sp_runtime::traits::Bounded;
+use sp_std::vec;
+use subsocial_support::Content;
+
+const PERIOD: u32 = 10;
+
+fn fund_account<T: Config>(origin: &RawOrigin<T::AccountId>) {
+    if let RawOrigin::Signed(account) = origin {
+        let balance = pallet_spaces::BalanceOf::<T>::max_value() / 2u32.into();
+        <T as pallet_spaces::Config>::Currency::make_free_balance_be(account, balance);
+    }
+}
+
+fn create_dummy_space<T: Config>(
+    origin: RawOrigin<T::AccountId>,
+) -> Result<SpaceId, DispatchError> {
+    fund_account::<T>(&origin);
+
+    let space_id = pallet_spaces::NextSpaceId::<T>::get();
+
+    pallet_spaces::Pallet::<T>::create_space(origin.into(), Content::None, None)?;
+
+    Ok(space_id)
+}
+
+fn create_dummy_role<T: Config>(
+    origin: RawOrigin<T::AccountId>,
+    space_id: SpaceId,
+) -> Result<RoleId, DispatchError> {
+    let role_id = pallet_roles::NextRoleId::<T>::get();
+
+    Roles::<T>::create_role(
+        origin.into(),
+        space_id,
+        None,
+        Content::None,
+        vec![SpacePermission::CreatePosts],
+        0,
+    )?;
+
+    Ok(role_id)
+}
+
+fn create_dummy_tier<T: Config>(owner: T::AccountId) -> Result<TierId, DispatchError> {
+    let space_id = create_dummy_space::<T>(RawOrigin::Signed(owner.clone()))?;
+    let role_id = create_dummy_role::<T>(RawOrigin::Signed(owner.clone()), space_id)?;
+    let tier_id = NextTierId::<T>::get();
+
+    Pallet::<T>::create_tier(
+        RawOrigin::Signed(owner).into(),
+        space_id,
+        price::<T>(),
+        PERIOD.into(),
+        role_id,
+        SubscriptionPayee::SpaceOwner,
+    )?;
+
+    Ok(tier_id)
+}
+
+fn price<T: Config>() -> BalanceOf<T> {
+    <T as Config>::Currency::minimum_balance() * 10u32.into()
+}
+
+fn fund_subscriber<T: Config>(subscriber: &T::AccountId) {
+    <T as Config>::Currency::make_free_balance_be(subscriber, price::<T>() * 100u32.into());
+}
+
+benchmarks! {
+
+    create_tier {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+
+        let space_id = create_dummy_space::<T>(RawOrigin::Signed(acc1.clone()))?;
+        let role_id = create_dummy_role::<T>(RawOrigin::Signed(acc1.clone()), space_id)?;
+        let period: T::BlockNumber = PERIOD.into();
+        let tier_id = NextTierId::<T>::get();
+        let payee = SubscriptionPayee::SpaceOwner;
+    }: _(RawOrigin::Signed(acc1), space_id, price::<T>(), period, role_id, payee)
+    verify {
+        ensure!(TierById::<T>::contains_key(tier_id), "Tier is not created");
+    }
+
+    disable_tier {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+
+        let tier_id = create_dummy_tier::<T>(acc1.clone())?;
+    }: _(RawOrigin::Signed(acc1), tier_id)
+    verify {
+        let tier = Pallet::<T>::require_tier(tier_id)?;
+        ensure!(tier.disabled, "Tier is not disabled");
+    }
+
+    subscribe {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+        let acc2 = account::<T::AccountId>("Acc2", 2, 0);
+
+        let tier_id = create_dummy_tier::<T>(acc1)?;
+        fund_subscriber::<T>(&acc2);
+    }: _(RawOrigin::Signed(acc2.clone()), tier_id)
+    verify {
+        ensure!(Subscriptions::<T>::contains_key(tier_id, &acc2), "Subscription is not created");
+    }
+
+    cancel_subscription {
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+        let acc2 = account::<T::AccountId>("Acc2", 2, 0);
+
+        let tier_id = create_dummy_tier::<T>(acc1)?;
+        fund_subscriber::<T>(&acc2);
+        Pallet::<T>::subscribe(RawOrigin::Signed(acc2.clone()).into(), tier_id)?;
+    }: _(RawOrigin::Signed(acc2.clone()), tier_id)
+    verify {
+        let subscription = Subscriptions::<T>::get(tier_id, &acc2)
+            .ok_or(DispatchError::Other("Subscription not found"))?;
+        ensure!(!subscription.auto_renew, "Subscription is not cancelled");
+    }
+
+    process_renewals {
+        let n in 1 .. 100;
+
+        let acc1 = account::<T::AccountId>("Acc1", 1, 0);
+        let tier_id = create_dummy_tier::<T>(acc1)?;
+
+        let start = T::BlockNumber::one();
+        frame_system::Pallet::<T>::set_block_number(start);
+        NextRenewalBlock::<T>::put(start);
+
+        for i in 0 .. n {
+            let subscriber = account::<T::AccountId>("Subscriber", i, 0);
+            fund_subscriber::<T>(&subscriber);
+            Pallet::<T>::subscribe(RawOrigin::Signed(subscriber).into(), tier_id)?;
+        }
+
+        let renew_at = start + PERIOD.into();
+    }: {
+        // Start from the block with due renewals, so that only renewals are counted.
+        NextRenewalBlock::<T>::put(renew_at);
+        Pallet::<T>::process_renewals(renew_at, &mut WeightMeter::new(Weight::MAX));
+    }
+    verify {
+        ensure!(
+            RenewalsAt::<T>::iter_prefix(renew_at).next().is_none(),
+            "Not all renewals are processed"
+        );
+    }
+
+}
@@ -0,0 +1,473 @@
+//! # Subscriptions Module
+//!
+//! This module allows space owners to define paid subscription tiers. Each tier has a price,
+//! a billing period, and a role that is granted to subscribers of this tier.
+//!
+//! The tier role is granted until the end of the paid period, unless a subscriber already has
+//! this role granted independently, in which case the subscription leaves it as is.
+//!
+//! Subscriptions are renewed automatically at the end of each period: a subscriber is charged
+//! once again, or the tier role is revoked from them if the payment fails or the subscription
+//! was cancelled. Only `MaxRenewalsPerBlock` renewals are processed per block, the rest of
+//! them are processed in the following blocks.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+    dispatch::{DispatchError, DispatchResult},
+    ensure,
+    traits::{Currency, ExistenceRequirement},
+};
+use frame_system::ensure_signed;
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{One, Saturating, Zero},
+    RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+use pallet_roles::{types::Role, Pallet as Roles, RoleId};
+use pallet_spaces::Pallet as Spaces;
+use subsocial_support::{
+    cleanup::{process_block_queue, WeightMeter},
+    space_owner_walk_weight,
+    traits::{OnSpaceDeleted, SpaceAccountProvider},
+    SpaceId, User,
+};
+
+pub use pallet::*;
+
+pub mod types;
+pub use types::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+
+pub(crate) type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use crate::weights::WeightInfo;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_spaces::Config + pallet_roles::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// The currency mechanism.
+        type Currency: Currency<Self::AccountId>;
+
+        /// Accounts of spaces that can receive subscription payments.
+        type SpaceAccounts: SpaceAccountProvider<Self::AccountId>;
+
+        /// The maximum number of subscriptions that can be renewed or expired in a single block.
+        #[pallet::constant]
+        type MaxRenewalsPerBlock: Get<u32>;
+
+        /// The maximum number of subscription tiers, that can be created in a single space.
+        #[pallet::constant]
+        type MaxTiersPerSpace: Get<u32>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        SubscriptionTierCreated { account: T::AccountId, space_id: SpaceId, tier_id: TierId },
+        SubscriptionTierDisabled { account: T::AccountId, tier_id: TierId },
+        Subscribed { account: T::AccountId, tier_id: TierId, expires_at: T::BlockNumber },
+        SubscriptionCancelled { account: T::AccountId, tier_id: TierId },
+        SubscriptionRenewed { account: T::AccountId, tier_id: TierId, expires_at: T::BlockNumber },
+        SubscriptionExpired { account: T::AccountId, tier_id: TierId },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Subscription tier was not found by id.
+        TierNotFound,
+        /// `NextTierId` exceeds its maximum value.
+        TierIdOverflow,
+        /// Subscription tier is disabled.
+        TierIsDisabled,
+        /// A billing period must be greater than zero.
+        ZeroPeriod,
+        /// A role of a subscription tier must belong to the same space.
+        RoleNotInSpace,
+        /// Account is already subscribed to this tier.
+        AlreadySubscribed,
+        /// Account is not subscribed to this tier.
+        NotSubscribed,
+        /// Subscription is already cancelled.
+        SubscriptionAlreadyCancelled,
+        /// Cannot create more than `MaxTiersPerSpace` tiers in a space.
+        TooManyTiers,
+    }
+
+    #[pallet::type_value]
+    pub fn DefaultForNextTierId() -> TierId {
+        FIRST_TIER_ID
+    }
+
+    /// The next subscription tier id.
+    #[pallet::storage]
+    #[pallet::getter(fn next_tier_id)]
+    pub type NextTierId<T: Config> = StorageValue<_, TierId, ValueQuery, DefaultForNextTierId>;
+
+    /// Get the details of a subscription tier by its' id.
+    #[pallet::storage]
+    #[pallet::getter(fn tier_by_id)]
+    pub type TierById<T: Config> = StorageMap<_, Twox64Concat, TierId, SubscriptionTierOf<T>>;
+
+    /// Ids of subscription tiers, that were created in a given space.
+    #[pallet::storage]
+    #[pallet::getter(fn tier_ids_by_space_id)]
+    pub type TierIdsBySpaceId<T: Config> =
+        StorageMap<_, Twox64Concat, SpaceId, BoundedVec<TierId, T::MaxTiersPerSpace>, ValueQuery>;
+
+    /// Get a subscription of a given account to a given tier.
+    #[pallet::storage]
+    #[pallet::getter(fn subscription)]
+    pub type Subscriptions<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        TierId,
+        Blake2_128Concat,
+        T::AccountId,
+        SubscriptionOf<T>,
+    >;
+
+    /// Subscriptions that should be renewed or expired at a given block.
+    #[pallet::storage]
+    pub type RenewalsAt<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        Blake2_128Concat,
+        (TierId, T::AccountId),
+        (),
+    >;
+
+    /// The first block that may still have unprocessed renewals.
+    #[pallet::storage]
+    #[pallet::getter(fn next_renewal_block)]
+    pub type NextRenewalBlock<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let mut meter = WeightMeter::new(<T as Config>::WeightInfo::process_renewals(
+                T::MaxRenewalsPerBlock::get(),
+            ));
+            Self::process_renewals(now, &mut meter);
+            meter.consumed()
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create a new subscription tier in a given space.
+        /// Only the space owner can call this dispatch.
+        #[pallet::call_index(0)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::create_tier()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn create_tier(
+            origin: OriginFor<T>,
+            space_id: SpaceId,
+            price: BalanceOf<T>,
+            period: T::BlockNumber,
+            role_id: RoleId,
+            payee: SubscriptionPayee,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!period.is_zero(), Error::<T>::ZeroPeriod);
+
+            let space = Spaces::<T>::require_space(space_id)?;
+            space.ensure_space_owner(who.clone())?;
+
+            let role = Roles::<T>::require_role(role_id)?;
+            ensure!(role.space_id == space_id, Error::<T>::RoleNotInSpace);
+
+            let tier_id = Self::next_tier_id();
+            let next_tier_id = tier_id.checked_add(1).ok_or(Error::<T>::TierIdOverflow)?;
+
+            TierIdsBySpaceId::<T>::try_mutate(space_id, |tier_ids| {
+                tier_ids.try_push(tier_id).map_err(|_| Error::<T>::TooManyTiers)
+            })?;
+
+            TierById::<T>::insert(
+                tier_id,
+                SubscriptionTier {
+                    id: tier_id,
+                    space_id,
+                    price,
+                    period,
+                    role_id,
+                    payee,
+                    disabled: false,
+                },
+            );
+            NextTierId::<T>::put(next_tier_id);
+
+            Self::deposit_event(Event::SubscriptionTierCreated { account: who, space_id, tier_id });
+            Ok(())
+        }
+
+        /// Disable a subscription tier: it will not accept new subscribers,
+        /// and existing subscriptions will expire at the end of their current period.
+        /// Only the space owner can call this dispatch.
+        #[pallet::call_index(1)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::disable_tier()
+                .saturating_add(space_owner_walk_weight::<T>())
+        )]
+        pub fn disable_tier(origin: OriginFor<T>, tier_id: TierId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut tier = Self::require_tier(tier_id)?;
+            Spaces::<T>::require_space(tier.space_id)?.ensure_space_owner(who.clone())?;
+
+            ensure!(!tier.disabled, Error::<T>::TierIsDisabled);
+
+            tier.disabled = true;
+            TierById::<T>::insert(tier_id, tier);
+
+            Self::deposit_event(Event::SubscriptionTierDisabled { account: who, tier_id });
+            Ok(())
+        }
+
+        /// Pay for the first period of a given tier and receive its role.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::subscribe())]
+        pub fn subscribe(origin: OriginFor<T>, tier_id: TierId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let tier = Self::require_tier(tier_id)?;
+            ensure!(!tier.disabled, Error::<T>::TierIsDisabled);
+            ensure!(
+                !Subscriptions::<T>::contains_key(tier_id, &who),
+                Error::<T>::AlreadySubscribed
+            );
+
+            let role = Roles::<T>::require_role(tier.role_id)?;
+
+            Self::charge_subscriber(&who, &tier)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let expires_at = now.saturating_add(tier.period);
+            let grants_role = Self::grant_tier_role(&role, &who, expires_at, false);
+
+            Subscriptions::<T>::insert(
+                tier_id,
+                &who,
+                Subscription { expires_at, auto_renew: true, grants_role },
+            );
+            Self::schedule_renewal(tier_id, &who, expires_at, now);
+
+            Self::deposit_event(Event::Subscribed { account: who, tier_id, expires_at });
+            Ok(())
+        }
+
+        /// Stop renewing a subscription. The tier role stays granted
+        /// until the end of the current period.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_subscription())]
+        pub fn cancel_subscription(origin: OriginFor<T>, tier_id: TierId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Subscriptions::<T>::try_mutate(tier_id, &who, |maybe_subscription| {
+                let subscription = maybe_subscription.as_mut().ok_or(Error::<T>::NotSubscribed)?;
+                ensure!(subscription.auto_renew, Error::<T>::SubscriptionAlreadyCancelled);
+
+                subscription.auto_renew = false;
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::SubscriptionCancelled { account: who, tier_id });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Get `SubscriptionTier` by id from the storage or return `TierNotFound` error.
+        pub fn require_tier(tier_id: TierId) -> Result<SubscriptionTierOf<T>, DispatchError> {
+            Ok(Self::tier_by_id(tier_id).ok_or(Error::<T>::TierNotFound)?)
+        }
+
+        /// Renew or expire subscriptions that are due at or before `now`,
+        /// while there is enough weight left. Visiting a block without due renewals
+        /// is weighed the same as processing a renewal.
+        pub(crate) fn process_renewals(now: T::BlockNumber, meter: &mut WeightMeter) {
+            let base_weight = <T as Config>::WeightInfo::process_renewals(0);
+            if !meter.try_consume(base_weight) {
+                return
+            }
+
+            let step_weight =
+                <T as Config>::WeightInfo::process_renewals(1).saturating_sub(base_weight);
+
+            let next_block = process_block_queue(
+                Self::next_renewal_block(),
+                now,
+                meter,
+                step_weight,
+                |block| RenewalsAt::<T>::iter_key_prefix(block).next(),
+                |_| step_weight,
+                |block, (tier_id, account)| {
+                    RenewalsAt::<T>::remove(block, (tier_id, account.clone()));
+                    Self::renew_or_expire(tier_id, account, now);
+                },
+            );
+
+            NextRenewalBlock::<T>::put(next_block);
+        }
+
+        fn renew_or_expire(tier_id: TierId, account: T::AccountId, now: T::BlockNumber) {
+            let (subscription, tier) =
+                match (Self::subscription(tier_id, &account), Self::tier_by_id(tier_id)) {
+                    (Some(subscription), Some(tier)) => (subscription, tier),
+                    _ => return,
+                };
+
+            let role = Roles::<T>::role_by_id(tier.role_id);
+
+            let can_renew = subscription.auto_renew && !tier.disabled && role.is_some();
+            if can_renew && Self::charge_subscriber(&account, &tier).is_ok() {
+                let expires_at = subscription.expires_at.saturating_add(tier.period);
+                let grants_role = role.map_or(false, |role| {
+                    Self::grant_tier_role(&role, &account, expires_at, subscription.grants_role)
+                });
+
+                Subscriptions::<T>::insert(
+                    tier_id,
+                    &account,
+                    Subscription { expires_at, auto_renew: true, grants_role },
+                );
+                Self::schedule_renewal(tier_id, &account, expires_at, now);
+
+                Self::deposit_event(Event::SubscriptionRenewed { account, tier_id, expires_at });
+                return
+            }
+
+            Subscriptions::<T>::remove(tier_id, &account);
+            if let Some(role) = role.filter(|_| subscription.grants_role) {
+                role.revoke_from_users(vec![User::Account(account.clone())]);
+            }
+
+            // A tier of a deleted space is kept only until its' last subscription expires.
+            let is_space_deleted = Spaces::<T>::space_by_id(tier.space_id).is_none();
+            if tier.disabled &&
+                is_space_deleted &&
+                Subscriptions::<T>::iter_key_prefix(tier_id).next().is_none()
+            {
+                TierById::<T>::remove(tier_id);
+            }
+
+            Self::deposit_event(Event::SubscriptionExpired { account, tier_id });
+        }
+
+        /// Grant a tier role to a subscriber until a given block, unless the subscriber has
+        /// an active grant of this role, that was not created by the subscription.
+        /// Returns `true`, if the role is granted by the subscription.
+        fn grant_tier_role(
+            role: &Role<T>,
+            account: &T::AccountId,
+            expires_at: T::BlockNumber,
+            granted_by_subscription: bool,
+        ) -> bool {
+            let user = User::Account(account.clone());
+
+            let has_role = Roles::<T>::role_ids_by_user_in_space(&user, role.space_id)
+                .contains(&role.id) &&
+                Roles::<T>::is_role_grant_active(role.id, &user);
+            if has_role && !granted_by_subscription {
+                return false
+            }
+
+            role.grant_to_user(user.clone());
+            role.set_grant_expiration(user, Some(expires_at));
+            true
+        }
+
+        fn schedule_renewal(
+            tier_id: TierId,
+            account: &T::AccountId,
+            expires_at: T::BlockNumber,
+            now: T::BlockNumber,
+        ) {
+            // Renewals must never be scheduled in the past, otherwise they would be skipped.
+            let renew_at = expires_at.max(now.saturating_add(One::one()));
+            RenewalsAt::<T>::insert(renew_at, (tier_id, account.clone()), ());
+        }
+
+        /// Disable tiers of a deleted space, so that nobody is charged for them anymore,
+        /// and remove tiers, that have no subscriptions. Existing subscriptions
+        /// expire at the end of their current period, as with any disabled tier.
+        fn disable_space_tiers(owner: &T::AccountId, space_id: SpaceId) {
+            for tier_id in TierIdsBySpaceId::<T>::take(space_id) {
+                let mut tier = match Self::tier_by_id(tier_id) {
+                    Some(tier) => tier,
+                    None => continue,
+                };
+
+                if Subscriptions::<T>::iter_key_prefix(tier_id).next().is_none() {
+                    TierById::<T>::remove(tier_id);
+                    continue
+                }
+
+                if !tier.disabled {
+                    tier.disabled = true;
+                    TierById::<T>::insert(tier_id, tier);
+                    Self::deposit_event(Event::SubscriptionTierDisabled {
+                        account: owner.clone(),
+                        tier_id,
+                    });
+                }
+            }
+        }
+
+        fn charge_subscriber(
+            subscriber: &T::AccountId,
+            tier: &SubscriptionTierOf<T>,
+        ) -> DispatchResult {
+            let payee = match tier.payee {
+                SubscriptionPayee::SpaceOwner => Spaces::<T>::require_space(tier.space_id)?.owner,
+                SubscriptionPayee::SpaceAccount => T::SpaceAccounts::space_account(tier.space_id),
+            };
+
+            <T as Config>::Currency::transfer(
+                subscriber,
+                &payee,
+                tier.price,
+                ExistenceRequirement::KeepAlive,
+            )
+        }
+    }
+
+    impl<T: Config> OnSpaceDeleted<T::AccountId> for Pallet<T> {
+        fn on_space_deleted(owner: &T::AccountId, space_id: SpaceId) {
+            Self::disable_space_tiers(owner, space_id);
+        }
+
+        fn on_space_deleted_weight() -> Weight {
+            let db_weight = T::DbWeight::get();
+            db_weight.reads_writes(1, 1).saturating_add(
+                db_weight.reads_writes(2, 1).saturating_mul(T::MaxTiersPerSpace::get().into()),
+            )
+        }
+    }
+}
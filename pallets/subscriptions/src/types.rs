@@ -0,0 +1,56 @@
+use super::*;
+
+pub type TierId = u64;
+
+pub const FIRST_TIER_ID: TierId = 1;
+
+/// Who receives subscription payments.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum SubscriptionPayee {
+    /// The current owner of a space.
+    SpaceOwner,
+    /// The account of a space, see `SpaceAccountProvider`.
+    SpaceAccount,
+}
+
+/// A paid subscription tier of a space. Subscribers of a tier receive its role
+/// for as long as they keep paying for it.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SubscriptionTier<Balance, BlockNumber> {
+    pub id: TierId,
+
+    /// An id of a space that this tier belongs to.
+    pub space_id: SpaceId,
+
+    /// An amount that a subscriber pays once per `period`.
+    pub price: Balance,
+
+    /// A number of blocks that a single payment covers.
+    pub period: BlockNumber,
+
+    /// A role of the space that is granted to subscribers of this tier.
+    pub role_id: RoleId,
+
+    pub payee: SubscriptionPayee,
+
+    /// A disabled tier accepts no new subscribers, and existing subscriptions are not renewed.
+    pub disabled: bool,
+}
+
+pub type SubscriptionTierOf<T> =
+    SubscriptionTier<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Subscription<BlockNumber> {
+    /// A block number at which this subscription is either renewed or expired.
+    pub expires_at: BlockNumber,
+
+    /// If `false`, a subscription expires at `expires_at` block instead of being renewed.
+    pub auto_renew: bool,
+
+    /// Whether the tier role is granted by this subscription, and should be revoked
+    /// when it expires. It is `false`, if a subscriber had this role granted independently.
+    pub grants_role: bool,
+}
+
+pub type SubscriptionOf<T> = Subscription<<T as frame_system::Config>::BlockNumber>;
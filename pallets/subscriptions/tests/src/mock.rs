@@ -0,0 +1,180 @@
+use frame_support::{
+    pallet_prelude::{ConstU32, ConstU64},
+    parameter_types,
+    traits::Everything,
+    PalletId,
+};
+use sp_core::H256;
+use sp_runtime::{
+    testing::{Header, TestSignature, UintAuthorityId},
+    traits::{BlakeTwo256, IdentityLookup},
+};
+use sp_std::convert::{TryFrom, TryInto};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        Timestamp: pallet_timestamp,
+        Balances: pallet_balances,
+        Permissions: pallet_permissions,
+        Roles: pallet_roles,
+        SpaceFollows: pallet_space_follows,
+        Spaces: pallet_spaces,
+        SpaceTreasury: pallet_space_treasury,
+        Subscriptions: pallet_subscriptions,
+    }
+);
+
+pub(super) type AccountId = u64;
+pub(super) type Balance = u64;
+pub(super) type BlockNumber = u64;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+    pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+    type Balance = Balance;
+    type DustRemoval = ();
+    type RuntimeEvent = RuntimeEvent;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = ();
+}
+
+impl pallet_permissions::Config for Test {
+    type DefaultSpacePermissions = pallet_permissions::default_permissions::DefaultSpacePermissions;
+    type NamespacedPermissions = ();
+}
+
+parameter_types! {
+    pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+}
+
+impl pallet_roles::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
+    type SpacePermissionsProvider = Spaces;
+    type SpaceFollows = SpaceFollows;
+    type Currency = Balances;
+    type JoinRequestDeposit = ConstU64<0>;
+    type DomainsProvider = ();
+    type IsAccountBlocked = ();
+    type IsContentBlocked = ();
+    type SpaceSettings = Spaces;
+    type OnRoleRemoved = SpaceTreasury;
+    type RoleProposalLifetime = ConstU64<100>;
+    type RoleProposalDeposit = ConstU64<0>;
+    type InviteSigner = UintAuthorityId;
+    type InviteSignature = TestSignature;
+    type WeightInfo = ();
+}
+
+impl pallet_spaces::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Roles = Roles;
+    type SpaceFollows = SpaceFollows;
+    type IsAccountBlocked = ();
+    type IsContentBlocked = ();
+    type SpacePosts = ();
+    type ProfileManager = ();
+    type OnSpaceDeleted = (SpaceFollows, Roles, SpaceTreasury, Subscriptions);
+    type OnSpaceOwnerChanged = ();
+    type SpaceSettingsHandler = ();
+    type SpaceOwnerOrigin = frame_system::EnsureSigned<AccountId>;
+    type SpaceAccounts = SpaceTreasury;
+    type Currency = Balances;
+    type BaseSpaceDeposit = ConstU64<0>;
+    type StorageByteDeposit = ConstU64<0>;
+    type MaxSpacesPerAccount = ConstU32<100>;
+    type MinHandleLen = ConstU32<5>;
+    type MaxHandleLen = ConstU32<50>;
+    type WeightInfo = ();
+}
+
+impl pallet_space_follows::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type SpaceStats = Spaces;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const SpaceTreasuryPalletId: PalletId = PalletId(*b"df/sptrs");
+}
+
+impl pallet_space_treasury::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
+    type Currency = Balances;
+    type PalletId = SpaceTreasuryPalletId;
+    type SpendingPeriod = ConstU64<10>;
+    type SpaceCalls = ();
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MaxRenewalsPerBlock: u32 = 2;
+    pub const MaxTiersPerSpace: u32 = 2;
+}
+
+impl pallet_subscriptions::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type SpaceAccounts = SpaceTreasury;
+    type MaxRenewalsPerBlock = MaxRenewalsPerBlock;
+    type MaxTiersPerSpace = MaxTiersPerSpace;
+    type WeightInfo = ();
+}
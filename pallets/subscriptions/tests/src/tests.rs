@@ -0,0 +1,232 @@
+use frame_support::{assert_noop, assert_ok};
+
+use pallet_spaces::Error as SpacesError;
+use pallet_subscriptions::{Error as SubscriptionsError, SubscriptionPayee};
+
+use crate::{mock::*, tests_utils::*};
+
+#[test]
+fn create_tier_should_work() {
+    ExtBuilder::build_with_space_and_role().execute_with(|| {
+        assert_ok!(_create_default_tier());
+
+        let tier = Subscriptions::tier_by_id(TIER1).unwrap();
+        assert_eq!(tier.space_id, SPACE1);
+        assert_eq!(tier.price, PRICE);
+        assert_eq!(tier.period, PERIOD);
+        assert_eq!(tier.role_id, ROLE1);
+        assert!(!tier.disabled);
+        assert_eq!(Subscriptions::next_tier_id(), TIER1 + 1);
+    });
+}
+
+#[test]
+fn create_tier_should_fail_when_not_space_owner() {
+    ExtBuilder::build_with_space_and_role().execute_with(|| {
+        assert_noop!(
+            _create_tier(Some(RuntimeOrigin::signed(ACCOUNT2)), None, None, None),
+            SpacesError::<Test>::NotASpaceOwner
+        );
+    });
+}
+
+#[test]
+fn create_tier_should_fail_with_zero_period() {
+    ExtBuilder::build_with_space_and_role().execute_with(|| {
+        assert_noop!(
+            _create_tier(None, Some(0), None, None),
+            SubscriptionsError::<Test>::ZeroPeriod
+        );
+    });
+}
+
+#[test]
+fn create_tier_should_fail_when_role_is_in_another_space() {
+    ExtBuilder::build_with_space_and_role().execute_with(|| {
+        assert_ok!(_create_default_space()); // SpaceId 1002
+        assert_ok!(_create_role_in_space(SPACE2)); // RoleId 2
+
+        assert_noop!(
+            _create_tier(None, None, Some(ROLE2), None),
+            SubscriptionsError::<Test>::RoleNotInSpace
+        );
+    });
+}
+
+#[test]
+fn subscribe_should_work() {
+    ExtBuilder::build_with_tier().execute_with(|| {
+        assert_ok!(_subscribe(None, None));
+
+        assert_eq!(Balances::free_balance(ACCOUNT2), 100 - PRICE);
+        assert_eq!(Balances::free_balance(ACCOUNT1), 100 + PRICE);
+        assert_eq!(subscription_expires_at(ACCOUNT2), Some(1 + PERIOD));
+        assert!(has_role(ACCOUNT2, ROLE1));
+        assert_eq!(role_grant_expires_at(ACCOUNT2), Some(1 + PERIOD));
+    });
+}
+
+#[test]
+fn subscribe_should_pay_to_space_account() {
+    ExtBuilder::build_with_space_and_role().execute_with(|| {
+        assert_ok!(_create_tier(None, None, None, Some(SubscriptionPayee::SpaceAccount)));
+        assert_ok!(_subscribe(None, None));
+
+        let space_account = SpaceTreasury::space_treasury_account(SPACE1);
+        assert_eq!(Balances::free_balance(space_account), PRICE);
+        assert_eq!(Balances::free_balance(ACCOUNT1), 100);
+    });
+}
+
+#[test]
+fn subscribe_should_fail_when_already_subscribed() {
+    ExtBuilder::build_with_subscription().execute_with(|| {
+        assert_noop!(_subscribe(None, None), SubscriptionsError::<Test>::AlreadySubscribed);
+    });
+}
+
+#[test]
+fn create_tier_should_fail_when_too_many_tiers_in_space() {
+    ExtBuilder::build_with_space_and_role().execute_with(|| {
+        // `MaxTiersPerSpace` is 2 in the mock.
+        assert_ok!(_create_default_tier());
+        assert_ok!(_create_default_tier());
+
+        assert_noop!(_create_default_tier(), SubscriptionsError::<Test>::TooManyTiers);
+    });
+}
+
+#[test]
+fn subscribe_should_fail_when_tier_is_disabled() {
+    ExtBuilder::build_with_tier().execute_with(|| {
+        assert_ok!(_disable_tier(None));
+
+        assert_noop!(_subscribe(None, None), SubscriptionsError::<Test>::TierIsDisabled);
+    });
+}
+
+#[test]
+fn disable_tier_should_fail_when_not_space_owner() {
+    ExtBuilder::build_with_tier().execute_with(|| {
+        assert_noop!(
+            _disable_tier(Some(RuntimeOrigin::signed(ACCOUNT2))),
+            SpacesError::<Test>::NotASpaceOwner
+        );
+    });
+}
+
+#[test]
+fn cancel_subscription_should_fail_when_not_subscribed() {
+    ExtBuilder::build_with_tier().execute_with(|| {
+        assert_noop!(_cancel_subscription(None), SubscriptionsError::<Test>::NotSubscribed);
+    });
+}
+
+#[test]
+fn subscription_should_be_renewed_at_the_end_of_period() {
+    ExtBuilder::build_with_subscription().execute_with(|| {
+        run_to_block(PERIOD);
+        assert_eq!(Balances::free_balance(ACCOUNT2), 100 - PRICE);
+
+        run_to_block(1 + PERIOD);
+        assert_eq!(Balances::free_balance(ACCOUNT2), 100 - 2 * PRICE);
+        assert_eq!(subscription_expires_at(ACCOUNT2), Some(1 + 2 * PERIOD));
+        assert!(has_role(ACCOUNT2, ROLE1));
+        assert_eq!(role_grant_expires_at(ACCOUNT2), Some(1 + 2 * PERIOD));
+    });
+}
+
+#[test]
+fn subscription_should_expire_when_cancelled() {
+    ExtBuilder::build_with_subscription().execute_with(|| {
+        assert_ok!(_cancel_subscription(None));
+        assert!(has_role(ACCOUNT2, ROLE1));
+
+        run_to_block(1 + PERIOD);
+        assert_eq!(Balances::free_balance(ACCOUNT2), 100 - PRICE);
+        assert_eq!(subscription_expires_at(ACCOUNT2), None);
+        assert!(!has_role(ACCOUNT2, ROLE1));
+    });
+}
+
+#[test]
+fn subscription_should_expire_when_subscriber_cannot_pay() {
+    ExtBuilder::build_with_subscription().execute_with(|| {
+        assert_ok!(Balances::transfer(RuntimeOrigin::signed(ACCOUNT2), ACCOUNT3, 85));
+
+        run_to_block(1 + PERIOD);
+        assert_eq!(Balances::free_balance(ACCOUNT2), 5);
+        assert_eq!(subscription_expires_at(ACCOUNT2), None);
+        assert!(!has_role(ACCOUNT2, ROLE1));
+    });
+}
+
+#[test]
+fn subscription_should_not_revoke_independently_granted_role() {
+    ExtBuilder::build_with_tier().execute_with(|| {
+        assert_ok!(_grant_default_role(ACCOUNT2));
+        assert_ok!(_subscribe(None, None));
+        assert_eq!(role_grant_expires_at(ACCOUNT2), None);
+
+        assert_ok!(_cancel_subscription(None));
+        run_to_block(1 + PERIOD);
+
+        assert_eq!(subscription_expires_at(ACCOUNT2), None);
+        assert!(has_role(ACCOUNT2, ROLE1));
+        assert_eq!(role_grant_expires_at(ACCOUNT2), None);
+    });
+}
+
+#[test]
+fn renewals_should_be_limited_per_block() {
+    ExtBuilder::build_with_tier().execute_with(|| {
+        for account in [ACCOUNT2, ACCOUNT3, ACCOUNT4] {
+            assert_ok!(_subscribe(Some(RuntimeOrigin::signed(account)), None));
+        }
+
+        // Only `MaxRenewalsPerBlock` (2) subscriptions are renewed in the first block.
+        run_to_block(1 + PERIOD);
+        let renewed = [ACCOUNT2, ACCOUNT3, ACCOUNT4]
+            .into_iter()
+            .filter(|account| subscription_expires_at(*account) == Some(1 + 2 * PERIOD))
+            .count();
+        assert_eq!(renewed, 2);
+
+        // The rest of them is renewed in the next block, keeping the same billing cycle.
+        run_to_block(2 + PERIOD);
+        for account in [ACCOUNT2, ACCOUNT3, ACCOUNT4] {
+            assert_eq!(subscription_expires_at(account), Some(1 + 2 * PERIOD));
+            assert_eq!(Balances::free_balance(account), 100 - 2 * PRICE);
+        }
+    });
+}
+
+#[test]
+fn delete_space_should_remove_tiers_without_subscriptions() {
+    ExtBuilder::build_with_tier().execute_with(|| {
+        assert_ok!(_delete_default_space());
+
+        assert!(Subscriptions::tier_by_id(TIER1).is_none());
+        assert!(Subscriptions::tier_ids_by_space_id(SPACE1).is_empty());
+    });
+}
+
+#[test]
+fn delete_space_should_disable_tiers_and_stop_renewals() {
+    ExtBuilder::build_with_subscription().execute_with(|| {
+        assert_ok!(_delete_default_space());
+
+        assert!(Subscriptions::tier_by_id(TIER1).unwrap().disabled);
+        assert_noop!(
+            _subscribe(Some(RuntimeOrigin::signed(ACCOUNT3)), None),
+            SubscriptionsError::<Test>::TierIsDisabled
+        );
+
+        // The subscription expires instead of being charged into the deleted space,
+        // and the tier is removed together with its' last subscription.
+        run_to_block(1 + PERIOD);
+        assert_eq!(Balances::free_balance(ACCOUNT2), 100 - PRICE);
+        assert_eq!(subscription_expires_at(ACCOUNT2), None);
+        assert!(Subscriptions::tier_by_id(TIER1).is_none());
+    });
+}
@@ -0,0 +1,177 @@
+use frame_support::{assert_ok, pallet_prelude::*, traits::Hooks};
+use sp_core::storage::Storage;
+use sp_io::TestExternalities;
+
+use pallet_permissions::SpacePermission as SP;
+use pallet_roles::RoleId;
+use pallet_subscriptions::{SubscriptionPayee, TierId};
+use subsocial_support::{Content, SpaceId, User};
+
+use crate::mock::*;
+
+////// Ext Builder
+
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+    fn configure_storages(storage: &mut Storage) {
+        let mut accounts = Vec::new();
+        for account in ACCOUNT1..=ACCOUNT4 {
+            accounts.push(account);
+        }
+
+        let _ = pallet_balances::GenesisConfig::<Test> {
+            balances: accounts.iter().cloned().map(|k| (k, 100)).collect(),
+        }
+        .assimilate_storage(storage);
+    }
+
+    /// Default ext configuration with BlockNumber 1
+    pub fn build() -> TestExternalities {
+        let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+        Self::configure_storages(&mut storage);
+
+        let mut ext = TestExternalities::from(storage);
+        ext.execute_with(|| System::set_block_number(1));
+
+        ext
+    }
+
+    /// Custom ext configuration with SpaceId 1001, RoleId 1 and BlockNumber 1
+    pub fn build_with_space_and_role() -> TestExternalities {
+        let mut ext = Self::build();
+        ext.execute_with(|| {
+            assert_ok!(_create_default_space());
+            assert_ok!(_create_default_role());
+        });
+        ext
+    }
+
+    /// Custom ext configuration with a subscription tier (TierId 1),
+    /// that costs `PRICE` per `PERIOD` blocks and is paid to the space owner
+    pub fn build_with_tier() -> TestExternalities {
+        let mut ext = Self::build_with_space_and_role();
+        ext.execute_with(|| assert_ok!(_create_default_tier()));
+        ext
+    }
+
+    /// Custom ext configuration with a subscription tier, that ACCOUNT2 is subscribed to
+    pub fn build_with_subscription() -> TestExternalities {
+        let mut ext = Self::build_with_tier();
+        ext.execute_with(|| assert_ok!(_subscribe(None, None)));
+        ext
+    }
+}
+
+////// Consts
+
+pub(crate) const ACCOUNT1: AccountId = 1;
+pub(crate) const ACCOUNT2: AccountId = 2;
+pub(crate) const ACCOUNT3: AccountId = 3;
+pub(crate) const ACCOUNT4: AccountId = 4;
+
+pub(crate) const SPACE1: SpaceId = 1001;
+pub(crate) const SPACE2: SpaceId = 1002;
+
+pub(crate) const ROLE1: RoleId = 1;
+pub(crate) const ROLE2: RoleId = 2;
+
+pub(crate) const TIER1: TierId = 1;
+
+pub(crate) const PRICE: Balance = 10;
+pub(crate) const PERIOD: BlockNumber = 10;
+
+////// Block Utils
+
+/// Run `on_initialize` of the subscriptions pallet for every block up to a given one.
+pub(crate) fn run_to_block(n: BlockNumber) {
+    while System::block_number() < n {
+        let next = System::block_number() + 1;
+        System::set_block_number(next);
+        Subscriptions::on_initialize(next);
+    }
+}
+
+///////////// Space Utils
+
+pub(crate) fn _create_default_space() -> DispatchResult {
+    Spaces::create_space(RuntimeOrigin::signed(ACCOUNT1), Content::None, None)
+}
+
+pub(crate) fn _delete_default_space() -> DispatchResult {
+    Spaces::delete_space(RuntimeOrigin::signed(ACCOUNT1), SPACE1)
+}
+
+///////////// Roles Utils
+
+pub(crate) fn _create_default_role() -> DispatchResult {
+    _create_role_in_space(SPACE1)
+}
+
+pub(crate) fn _create_role_in_space(space_id: SpaceId) -> DispatchResult {
+    Roles::create_role(
+        RuntimeOrigin::signed(ACCOUNT1),
+        space_id,
+        None,
+        Content::None,
+        vec![SP::CreatePosts],
+        0,
+    )
+}
+
+pub(crate) fn _grant_default_role(account: AccountId) -> DispatchResult {
+    Roles::grant_role(RuntimeOrigin::signed(ACCOUNT1), ROLE1, vec![User::Account(account)], None)
+}
+
+pub(crate) fn has_role(account: AccountId, role_id: RoleId) -> bool {
+    Roles::users_by_role_id(role_id).contains(&User::Account(account))
+}
+
+pub(crate) fn role_grant_expires_at(account: AccountId) -> Option<BlockNumber> {
+    Roles::role_grant_expires_at(ROLE1, User::Account(account))
+}
+
+//////// Subscriptions utils
+
+pub(crate) fn _create_default_tier() -> DispatchResult {
+    _create_tier(None, None, None, None)
+}
+
+pub(crate) fn _create_tier(
+    origin: Option<RuntimeOrigin>,
+    period: Option<BlockNumber>,
+    role_id: Option<RoleId>,
+    payee: Option<SubscriptionPayee>,
+) -> DispatchResult {
+    Subscriptions::create_tier(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)),
+        SPACE1,
+        PRICE,
+        period.unwrap_or(PERIOD),
+        role_id.unwrap_or(ROLE1),
+        payee.unwrap_or(SubscriptionPayee::SpaceOwner),
+    )
+}
+
+pub(crate) fn _disable_tier(origin: Option<RuntimeOrigin>) -> DispatchResult {
+    Subscriptions::disable_tier(origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT1)), TIER1)
+}
+
+pub(crate) fn _subscribe(origin: Option<RuntimeOrigin>, tier_id: Option<TierId>) -> DispatchResult {
+    Subscriptions::subscribe(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT2)),
+        tier_id.unwrap_or(TIER1),
+    )
+}
+
+pub(crate) fn _cancel_subscription(origin: Option<RuntimeOrigin>) -> DispatchResult {
+    Subscriptions::cancel_subscription(
+        origin.unwrap_or_else(|| RuntimeOrigin::signed(ACCOUNT2)),
+        TIER1,
+    )
+}
+
+pub(crate) fn subscription_expires_at(account: AccountId) -> Option<BlockNumber> {
+    Subscriptions::subscription(TIER1, account).map(|subscription| subscription.expires_at)
+}
@@ -0,0 +1,137 @@
+//! Helpers for cleanups of storage, that are spread across several blocks,
+//! e.g. in `on_idle` or `on_initialize` hooks.
+
+use frame_support::{
+    sp_runtime::traits::{AtLeast32BitUnsigned, One, Zero},
+    weights::Weight,
+    RuntimeDebug,
+};
+
+/// Tracks how much weight a cleanup has consumed out of a given limit.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub struct WeightMeter {
+    limit: Weight,
+    consumed: Weight,
+}
+
+impl WeightMeter {
+    pub fn new(limit: Weight) -> Self {
+        Self { limit, consumed: Weight::zero() }
+    }
+
+    pub fn consumed(&self) -> Weight {
+        self.consumed
+    }
+
+    pub fn remaining(&self) -> Weight {
+        self.limit.saturating_sub(self.consumed)
+    }
+
+    /// Consume a given weight, if it fits into the limit. Returns `false` otherwise.
+    pub fn try_consume(&mut self, weight: Weight) -> bool {
+        let consumed = self.consumed.saturating_add(weight);
+        if consumed.any_gt(self.limit) {
+            return false
+        }
+
+        self.consumed = consumed;
+        true
+    }
+}
+
+/// Process entries of a queue indexed by block number, block by block from `cursor` up to `now`
+/// inclusive, while there is enough weight left. A zero `cursor` means that the queue
+/// has never been processed yet, so the processing starts from `now`.
+///
+/// `next_entry` returns any entry that is still queued at a given block. `process_entry` must
+/// either remove this entry from the queue, or make progress on it, so that it is removed
+/// after a few steps. Visiting a block without entries costs `empty_block_weight`.
+///
+/// Returns the block to continue from, that should be stored as a new cursor.
+pub fn process_block_queue<BlockNumber, Entry>(
+    cursor: BlockNumber,
+    now: BlockNumber,
+    meter: &mut WeightMeter,
+    empty_block_weight: Weight,
+    mut next_entry: impl FnMut(BlockNumber) -> Option<Entry>,
+    mut entry_weight: impl FnMut(&Entry) -> Weight,
+    mut process_entry: impl FnMut(BlockNumber, Entry),
+) -> BlockNumber
+where
+    BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+    let mut block = if cursor.is_zero() { now } else { cursor };
+
+    while block <= now {
+        match next_entry(block) {
+            Some(entry) => {
+                if !meter.try_consume(entry_weight(&entry)) {
+                    break
+                }
+                process_entry(block, entry);
+            },
+            None => {
+                if !meter.try_consume(empty_block_weight) {
+                    break
+                }
+                block = block.saturating_add(One::one());
+            },
+        }
+    }
+
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn process_block_queue_should_resume_from_returned_block() {
+        let queue = RefCell::new(vec![(1u32, 'a'), (1, 'b'), (3, 'c')]);
+        let processed = RefCell::new(vec![]);
+
+        let process = |cursor: u32, limit: u64| {
+            let mut meter = WeightMeter::new(Weight::from_ref_time(limit));
+            process_block_queue(
+                cursor,
+                3,
+                &mut meter,
+                Weight::from_ref_time(1),
+                |block| queue.borrow().iter().find(|(b, _)| *b == block).map(|(_, entry)| *entry),
+                |_| Weight::from_ref_time(10),
+                |block, entry| {
+                    queue.borrow_mut().retain(|queued| *queued != (block, entry));
+                    processed.borrow_mut().push(entry);
+                },
+            )
+        };
+
+        // Enough weight for two entries at block 1, and to leave blocks 1 and 2.
+        let cursor = process(1, 22);
+        assert_eq!(cursor, 3);
+        assert_eq!(*processed.borrow(), vec!['a', 'b']);
+
+        let cursor = process(cursor, 100);
+        assert_eq!(cursor, 4);
+        assert_eq!(*processed.borrow(), vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn process_block_queue_should_start_from_now_when_cursor_is_zero() {
+        let mut meter = WeightMeter::new(Weight::from_ref_time(100));
+        let cursor = process_block_queue(
+            0u32,
+            5,
+            &mut meter,
+            Weight::from_ref_time(1),
+            |_| None::<()>,
+            |_| Weight::zero(),
+            |_, _| {},
+        );
+
+        assert_eq!(cursor, 6);
+        assert_eq!(meter.consumed(), Weight::from_ref_time(1));
+    }
+}
@@ -9,12 +9,32 @@ use scale_info::TypeInfo;
 use frame_support::pallet_prelude::*;
 use sp_std::{collections::btree_set::BTreeSet, vec, vec::Vec};
 
+pub mod cleanup;
 pub mod traits;
 
 pub type SpaceId = u64;
 pub type PostId = u64;
 
-#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+/// A unique key of a space setting, e.g. `*b"posts/maxCmtDpth"`.
+pub type SpaceSettingKey = [u8; 16];
+
+/// The maximum length of an encoded value of a space setting.
+pub const MAX_SPACE_SETTING_VALUE_LEN: u32 = 256;
+
+/// An encoded value of a space setting.
+pub type SpaceSettingValue = BoundedVec<u8, ConstU32<MAX_SPACE_SETTING_VALUE_LEN>>;
+
+/// How many owner spaces can be walked through, when resolving an owner of a space
+/// that is owned by another space.
+pub const MAX_SPACE_OWNERSHIP_DEPTH: u32 = 5;
+
+/// The worst-case weight of resolving an owner of a space, that is owned by another space:
+/// an owner space id and an owner space are read at every step of the walk.
+pub fn space_owner_walk_weight<T: frame_system::Config>() -> Weight {
+    T::DbWeight::get().reads(2 * MAX_SPACE_OWNERSHIP_DEPTH as u64 + 1)
+}
+
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct WhoAndWhen<AccountId, BlockNumber, Moment> {
     pub account: AccountId,
     pub block: BlockNumber,
@@ -154,6 +174,22 @@ impl From<ContentError> for DispatchError {
     }
 }
 
+#[derive(Encode, Decode, RuntimeDebug, strum::IntoStaticStr)]
+pub enum SpaceSettingsError {
+    /// There is no space setting registered by this key.
+    UnknownSpaceSetting,
+    /// A space setting value cannot be decoded.
+    InvalidSpaceSettingValue,
+    /// A space setting value is too long.
+    SpaceSettingValueTooLong,
+}
+
+impl From<SpaceSettingsError> for DispatchError {
+    fn from(err: SpaceSettingsError) -> DispatchError {
+        Self::Other(err.into())
+    }
+}
+
 /// Minimal set of fields from Space struct that are required by roles pallet.
 pub struct SpacePermissionsInfo<AccountId, SpacePermissions> {
     pub owner: AccountId,
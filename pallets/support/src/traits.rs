@@ -1,7 +1,11 @@
 pub use common::{
-    ProfileManager, SpaceFollowsProvider, SpacePermissionsProvider, SpacesInterface, PostFollowsProvider,
+    DomainsProvider, OnSpaceDeleted, OnSpaceOwnerChanged, PostFollowsProvider, ProfileManager,
+    SpaceAccountProvider, SpaceFollowsProvider, SpaceMembersProvider, SpacePermissionsProvider,
+    SpacePostsProvider, SpaceStatsManager, SpacesInterface,
 };
 pub use moderation::{IsAccountBlocked, IsContentBlocked, IsPostBlocked, IsSpaceBlocked};
+pub use settings::{SpaceSetting, SpaceSettingsHandler, SpaceSettingsProvider};
 
 mod common;
 mod moderation;
+mod settings;
@@ -1,6 +1,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::dispatch::{DispatchError, DispatchResult};
+use codec::{Decode, Encode};
+use frame_support::{
+    dispatch::{DispatchError, DispatchResult},
+    sp_runtime::traits::TrailingZeroInput,
+    weights::Weight,
+};
 
 use crate::{Content, PostId, SpaceId};
 
@@ -8,12 +13,26 @@ pub trait SpacePermissionsProvider<AccountId, SpacePermissionsInfo> {
     fn space_permissions_info(id: SpaceId) -> Result<SpacePermissionsInfo, DispatchError>;
 
     fn ensure_space_owner(id: SpaceId, account: &AccountId) -> DispatchResult;
+
+    /// Whether an account can interact with a space, taking its' privacy into account.
+    fn is_space_accessible_by(id: SpaceId, account: &AccountId) -> bool;
 }
 
 pub trait SpaceFollowsProvider {
     type AccountId;
+    type BlockNumber;
 
     fn is_space_follower(account: Self::AccountId, space_id: SpaceId) -> bool;
+
+    fn space_followers_count(space_id: SpaceId) -> u32;
+
+    /// A block at which an account has started following a space, if it follows it.
+    fn space_followed_at(account: Self::AccountId, space_id: SpaceId) -> Option<Self::BlockNumber>;
+}
+
+pub trait SpaceMembersProvider<AccountId> {
+    /// Whether an account is a member of a space, i.e. it has at least one role in this space.
+    fn is_space_member(account: &AccountId, space_id: SpaceId) -> bool;
 }
 
 pub trait PostFollowsProvider {
@@ -26,6 +45,110 @@ pub trait ProfileManager<AccountId> {
     fn unlink_space_from_profile(account: &AccountId, space_id: SpaceId);
 }
 
+impl<AccountId> ProfileManager<AccountId> for () {
+    fn unlink_space_from_profile(_account: &AccountId, _space_id: SpaceId) {}
+}
+
+pub trait DomainsProvider<AccountId> {
+    /// Whether an account owns at least one registered domain, that has not expired yet.
+    fn owns_any_domain(account: &AccountId) -> bool;
+}
+
+impl<AccountId> DomainsProvider<AccountId> for () {
+    fn owns_any_domain(_account: &AccountId) -> bool {
+        false
+    }
+}
+
+pub trait SpacePostsProvider {
+    fn space_posts_count(space_id: SpaceId) -> u32;
+
+    fn space_hidden_posts_count(space_id: SpaceId) -> u32;
+}
+
+impl SpacePostsProvider for () {
+    fn space_posts_count(_space_id: SpaceId) -> u32 {
+        0
+    }
+
+    fn space_hidden_posts_count(_space_id: SpaceId) -> u32 {
+        0
+    }
+}
+
+/// A handler of changes in the activity of spaces, that keeps the space counters up to date.
+pub trait SpaceStatsManager {
+    /// A root post was added to a space.
+    fn on_post_added(space_id: SpaceId, hidden: bool);
+
+    /// A root post was removed from a space.
+    fn on_post_removed(space_id: SpaceId, hidden: bool);
+
+    /// A root post of a space was hidden or made visible again.
+    fn on_post_visibility_changed(space_id: SpaceId, hidden: bool);
+
+    fn on_follower_added(space_id: SpaceId);
+
+    fn on_follower_removed(space_id: SpaceId);
+}
+
+impl SpaceStatsManager for () {
+    fn on_post_added(_space_id: SpaceId, _hidden: bool) {}
+
+    fn on_post_removed(_space_id: SpaceId, _hidden: bool) {}
+
+    fn on_post_visibility_changed(_space_id: SpaceId, _hidden: bool) {}
+
+    fn on_follower_added(_space_id: SpaceId) {}
+
+    fn on_follower_removed(_space_id: SpaceId) {}
+}
+
+/// A handler that is called when a space is deleted,
+/// so that other pallets can clean up their storage related to this space.
+pub trait OnSpaceDeleted<AccountId> {
+    fn on_space_deleted(owner: &AccountId, space_id: SpaceId);
+
+    /// The maximum weight of `on_space_deleted`, that is charged on top of a space deletion.
+    /// Handlers, whose storage accesses are already included into the benchmarked weight
+    /// of a space deletion, don't need to override it.
+    fn on_space_deleted_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+impl<AccountId> OnSpaceDeleted<AccountId> for Tuple {
+    fn on_space_deleted(owner: &AccountId, space_id: SpaceId) {
+        for_tuples!( #( Tuple::on_space_deleted(owner, space_id); )* );
+    }
+
+    fn on_space_deleted_weight() -> Weight {
+        let mut weight = Weight::zero();
+        for_tuples!( #( weight = weight.saturating_add(Tuple::on_space_deleted_weight()); )* );
+        weight
+    }
+}
+
+/// A handler that is called when a space is handed over to a new owner.
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait OnSpaceOwnerChanged<AccountId> {
+    fn on_space_owner_changed(space_id: SpaceId, old_owner: &AccountId, new_owner: &AccountId);
+}
+
+pub trait SpaceAccountProvider<AccountId> {
+    /// An account that holds funds of a given space.
+    fn space_account(space_id: SpaceId) -> AccountId;
+}
+
+impl<AccountId: Decode> SpaceAccountProvider<AccountId> for () {
+    fn space_account(space_id: SpaceId) -> AccountId {
+        (b"space", space_id)
+            .using_encoded(|b| AccountId::decode(&mut TrailingZeroInput::new(b)))
+            .expect("infinite length input; no invalid inputs for type; qed")
+    }
+}
+
 pub trait SpacesInterface<AccountId, SpaceId> {
     fn get_space_owner(space_id: SpaceId) -> Result<AccountId, DispatchError>;
 
@@ -0,0 +1,52 @@
+use codec::{Decode, DecodeAll, Encode};
+use frame_support::dispatch::DispatchResult;
+
+use crate::{SpaceId, SpaceSettingKey, SpaceSettingsError};
+
+/// A typed space setting that can be registered by any pallet.
+pub trait SpaceSetting {
+    /// A unique key of this setting across all pallets, e.g. `*b"posts/maxCmtDpth"`.
+    const KEY: SpaceSettingKey;
+
+    type Value: Encode + Decode;
+
+    /// A value used when a space has no value set for this setting.
+    fn default_value() -> Self::Value;
+
+    /// Check that a new value of this setting is valid.
+    fn validate(_value: &Self::Value) -> DispatchResult {
+        Ok(())
+    }
+
+    /// Decode a raw value of this setting and check that it is valid.
+    fn decode_and_validate(raw_value: &[u8]) -> DispatchResult {
+        let value = Self::Value::decode_all(&mut &raw_value[..])
+            .map_err(|_| SpaceSettingsError::InvalidSpaceSettingValue)?;
+        Self::validate(&value)
+    }
+}
+
+/// A registry of space settings. Each pallet that has its' own space settings
+/// should implement this trait and be added to a runtime's handlers tuple.
+pub trait SpaceSettingsHandler {
+    /// Validate a raw value of a setting by its' key.
+    /// Returns `None` if this handler doesn't know a setting with such key.
+    fn validate_space_setting(key: &SpaceSettingKey, raw_value: &[u8]) -> Option<DispatchResult>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+impl SpaceSettingsHandler for Tuple {
+    fn validate_space_setting(key: &SpaceSettingKey, raw_value: &[u8]) -> Option<DispatchResult> {
+        for_tuples!( #(
+            if let Some(result) = Tuple::validate_space_setting(key, raw_value) {
+                return Some(result)
+            }
+        )* );
+        None
+    }
+}
+
+pub trait SpaceSettingsProvider {
+    /// Get a value of a given setting in a space, or its' default value if it is not set.
+    fn space_setting<S: SpaceSetting>(space_id: SpaceId) -> S::Value;
+}
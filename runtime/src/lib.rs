@@ -23,7 +23,7 @@ use sp_version::RuntimeVersion;
 use frame_support::{
 	construct_runtime, parameter_types,
 	dispatch::DispatchClass,
-	traits::{ConstU32, ConstU64, ConstU8, Contains, WithdrawReasons},
+	traits::{ConstU32, ConstU64, ConstU8, Contains, EitherOf, WithdrawReasons},
 	weights::{
 		constants::WEIGHT_REF_TIME_PER_SECOND, ConstantMultiplier, Weight,
 		WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
@@ -33,13 +33,14 @@ use frame_support::{
 use frame_support::traits::InstanceFilter;
 use frame_system::{
 	limits::{BlockLength, BlockWeights},
-	EnsureRoot,
+	EnsureRoot, EnsureRootWithSuccess, EnsureSigned,
 };
 pub use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 pub use sp_runtime::{MultiAddress, Perbill, Permill, FixedI64, FixedPointNumber};
 use xcm_config::{XcmConfig, XcmOriginToTransactDispatchOrigin};
 
 use pallet_domains::types::PricesConfigVec;
+use pallet_space_ownership_rpc_runtime_api::{SpaceId, SpaceOwnershipRecord};
 
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
@@ -72,6 +73,9 @@ pub type Hash = sp_core::H256;
 /// An index to a block.
 pub type BlockNumber = u32;
 
+/// A timestamp: milliseconds since the unix epoch.
+pub type Moment = u64;
+
 /// The address format for describing accounts.
 pub type Address = MultiAddress<AccountId, ()>;
 
@@ -112,7 +116,18 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
-	pallet_domains::migration::v1::MigrateToV1<Runtime>,
+	(
+		pallet_domains::migration::v1::MigrateToV1<Runtime>,
+		pallet_spaces::migration::v1::MigrateToV1<Runtime>,
+		pallet_spaces::migration::v2::MigrateToV2<Runtime>,
+		pallet_spaces::migration::v3::MigrateToV3<Runtime>,
+		pallet_spaces::migration::deposits::ExemptLegacySpaces<Runtime>,
+		pallet_posts::migration::deposits::ExemptLegacyPosts<Runtime>,
+		pallet_roles::migration::v1::MigrateToV1<Runtime>,
+		pallet_roles::migration::v2::MigrateToV2<Runtime>,
+		pallet_roles::migration::v3::MigrateToV3<Runtime>,
+		pallet_space_follows::migration::followed_at::TrackLegacyFollows<Runtime>,
+	),
 >;
 
 /// Handles converting a weight scalar to a fee value, based on the scale and granularity of the
@@ -329,7 +344,7 @@ impl frame_system::Config for Runtime {
 
 impl pallet_timestamp::Config for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
+	type Moment = Moment;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = ConstU64<{ SLOT_DURATION / 2 }>;
 	type WeightInfo = ();
@@ -645,21 +660,32 @@ use pallet_permissions::default_permissions::DefaultSpacePermissions;
 
 impl pallet_permissions::Config for Runtime {
 	type DefaultSpacePermissions = DefaultSpacePermissions;
+	type NamespacedPermissions = (Roles,);
 }
 
 parameter_types! {
   pub const MaxCommentDepth: u32 = 10;
+  pub const BasePostDeposit: Balance = 10 * MILLIUNIT;
+  pub const BaseCommentDeposit: Balance = 5 * MILLIUNIT;
 }
 
 impl pallet_posts::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type MaxCommentDepth = MaxCommentDepth;
 	type IsPostBlocked = ()/*Moderation*/;
+	type SpaceStats = Spaces;
+	type BasePostDeposit = BasePostDeposit;
+	type BaseCommentDeposit = BaseCommentDeposit;
 	type WeightInfo = pallet_posts::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const BaseReactionDeposit: Balance = MILLIUNIT;
+}
+
 impl pallet_reactions::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type BaseReactionDeposit = BaseReactionDeposit;
 	type WeightInfo = pallet_reactions::weights::SubstrateWeight<Runtime>;
 }
 
@@ -667,11 +693,27 @@ impl pallet_profiles::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type SpacePermissionsProvider = Spaces;
 	type SpacesInterface = Spaces;
+	type MaxLinkedAddresses = ConstU32<10>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ProfilesBenchmarkHelper;
 	type WeightInfo = pallet_profiles::weights::SubstrateWeight<Runtime>;
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ProfilesBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_profiles::BenchmarkHelper<AccountId> for ProfilesBenchmarkHelper {
+	fn fund_account(account: &AccountId) {
+		use frame_support::traits::Currency;
+		let _ = Balances::deposit_creating(account, 1_000 * UNIT);
+	}
+}
+
 parameter_types! {
   pub const MaxUsersToProcessPerDeleteRole: u16 = 40;
+  pub const JoinRequestDeposit: Balance = 10 * MILLIUNIT;
+  pub const RoleProposalDeposit: Balance = 10 * MILLIUNIT;
 }
 
 impl pallet_roles::Config for Runtime {
@@ -679,18 +721,53 @@ impl pallet_roles::Config for Runtime {
 	type MaxUsersToProcessPerDeleteRole = MaxUsersToProcessPerDeleteRole;
 	type SpacePermissionsProvider = Spaces;
 	type SpaceFollows = SpaceFollows;
+	type Currency = Balances;
+	type JoinRequestDeposit = JoinRequestDeposit;
+	type DomainsProvider = Domains;
 	type IsAccountBlocked = ()/*Moderation*/;
 	type IsContentBlocked = ()/*Moderation*/;
+	type SpaceSettings = Spaces;
+	type OnRoleRemoved = SpaceTreasury;
+	type RoleProposalLifetime = ConstU32<{ 7 * DAYS }>;
+	type RoleProposalDeposit = RoleProposalDeposit;
+	type InviteSigner = <Signature as Verify>::Signer;
+	type InviteSignature = Signature;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = RolesBenchmarkHelper;
 	type WeightInfo = pallet_roles::weights::SubstrateWeight<Runtime>;
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+pub struct RolesBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_roles::BenchmarkHelper<AccountId, Signature> for RolesBenchmarkHelper {
+	fn sign(payload: &[u8]) -> (AccountId, Signature) {
+		let key_type = sp_core::crypto::KeyTypeId(*b"role");
+		let public = sp_io::crypto::sr25519_generate(key_type, None);
+		let signature = sp_io::crypto::sr25519_sign(key_type, &public, payload)
+			.expect("qed; a key has just been generated");
+
+		(<Signature as Verify>::Signer::from(public).into_account(), signature.into())
+	}
+}
+
 impl pallet_space_follows::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type SpaceStats = Spaces;
 	type WeightInfo = pallet_space_follows::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
 	pub const MaxSpacesPerAccount: u32 = 4096;
+	pub const MinHandleLen: u32 = 5;
+	pub const MaxHandleLen: u32 = 50;
+	pub const BaseSpaceDeposit: Balance = 100 * MILLIUNIT;
+	pub const StorageByteDeposit: Balance = 10 * MICROUNIT;
+	// Spaces created with the root origin are owned by this account.
+	// There is no collective in this runtime, so organisations own spaces
+	// through pure proxy accounts, that are signed accounts too.
+	pub RootSpaceOwner: AccountId = PalletId(*b"df/sprot").into_account_truncating();
 }
 
 impl pallet_spaces::Config for Runtime {
@@ -699,16 +776,125 @@ impl pallet_spaces::Config for Runtime {
 	type SpaceFollows = SpaceFollows;
 	type IsAccountBlocked = ()/*Moderation*/;
 	type IsContentBlocked = ()/*Moderation*/;
+	type SpacePosts = Posts;
+	type ProfileManager = Profiles;
+	type OnSpaceDeleted = (SpaceFollows, Roles, SpaceOwnership, SpaceTreasury, Subscriptions);
+	type OnSpaceOwnerChanged = SpaceOwnership;
+	type SpaceSettingsHandler = (Posts, Roles);
+	type SpaceOwnerOrigin = EitherOf<EnsureSigned<AccountId>, EnsureRootWithSuccess<AccountId, RootSpaceOwner>>;
+	type SpaceAccounts = SpaceTreasury;
+	type Currency = Balances;
+	type BaseSpaceDeposit = BaseSpaceDeposit;
+	type StorageByteDeposit = StorageByteDeposit;
 	type MaxSpacesPerAccount = MaxSpacesPerAccount;
+	type MinHandleLen = MinHandleLen;
+	type MaxHandleLen = MaxHandleLen;
 	type WeightInfo = pallet_spaces::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const MaxExpiredTransfersPerBlock: u32 = 50;
+	pub const MaxOwnershipHistoryLength: u32 = 20;
+}
+
 impl pallet_space_ownership::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type ProfileManager = Profiles;
+	type MaxExpiredTransfersPerBlock = MaxExpiredTransfersPerBlock;
+	type MaxOwnershipHistoryLength = MaxOwnershipHistoryLength;
 	type WeightInfo = pallet_space_ownership::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const SpaceTreasuryPalletId: PalletId = PalletId(*b"df/sptrs");
+	pub const SpaceTreasurySpendingPeriod: BlockNumber = 30 * DAYS;
+}
+
+impl pallet_space_treasury::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type PalletId = SpaceTreasuryPalletId;
+	type SpendingPeriod = SpaceTreasurySpendingPeriod;
+	type SpaceCalls = SpaceCalls;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = SpaceTreasuryBenchmarkHelper;
+	type WeightInfo = pallet_space_treasury::weights::SubstrateWeight<Runtime>;
+}
+
+/// Only social actions can be dispatched on behalf of a space.
+/// Spending funds of a space should go through `SpaceTreasury::spend`.
+pub struct SpaceCalls;
+
+impl pallet_space_treasury::SpaceCallInspector<RuntimeCall> for SpaceCalls {
+	fn can_act_as_space(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::Posts(
+				pallet_posts::Call::create_post { .. }
+					| pallet_posts::Call::update_post { .. }
+					| pallet_posts::Call::move_post { .. }
+			) | RuntimeCall::Reactions(
+				pallet_reactions::Call::create_post_reaction { .. }
+					| pallet_reactions::Call::update_post_reaction { .. }
+					| pallet_reactions::Call::delete_post_reaction { .. }
+			) | RuntimeCall::SpaceFollows(
+				pallet_space_follows::Call::follow_space { .. }
+					| pallet_space_follows::Call::unfollow_space { .. }
+			) | RuntimeCall::AccountFollows(
+				pallet_account_follows::Call::follow_account { .. }
+					| pallet_account_follows::Call::unfollow_account { .. }
+			)
+		)
+	}
+
+	fn target_space(call: &RuntimeCall) -> Option<SpaceId> {
+		let post_id = match call {
+			RuntimeCall::Posts(pallet_posts::Call::create_post {
+				extension: pallet_posts::PostExtension::Comment(comment),
+				..
+			}) => comment.root_post_id,
+			RuntimeCall::Posts(pallet_posts::Call::create_post { space_id_opt, .. }) =>
+				return *space_id_opt,
+			RuntimeCall::Posts(pallet_posts::Call::update_post { post_id, .. }) |
+			RuntimeCall::Posts(pallet_posts::Call::move_post { post_id, .. }) |
+			RuntimeCall::Reactions(pallet_reactions::Call::create_post_reaction { post_id, .. }) |
+			RuntimeCall::Reactions(pallet_reactions::Call::update_post_reaction { post_id, .. }) |
+			RuntimeCall::Reactions(pallet_reactions::Call::delete_post_reaction { post_id, .. }) =>
+				*post_id,
+			RuntimeCall::SpaceFollows(pallet_space_follows::Call::follow_space { space_id }) |
+			RuntimeCall::SpaceFollows(pallet_space_follows::Call::unfollow_space { space_id }) =>
+				return Some(*space_id),
+			_ => return None,
+		};
+
+		Posts::post_by_id(post_id).and_then(|post| post.try_get_space_id())
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct SpaceTreasuryBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_space_treasury::BenchmarkHelper<RuntimeCall> for SpaceTreasuryBenchmarkHelper {
+	fn space_call(space_id: SpaceId) -> RuntimeCall {
+		RuntimeCall::SpaceFollows(pallet_space_follows::Call::follow_space { space_id })
+	}
+}
+
+parameter_types! {
+	pub const MaxSubscriptionRenewalsPerBlock: u32 = 50;
+	pub const MaxSubscriptionTiersPerSpace: u32 = 10;
+}
+
+impl pallet_subscriptions::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type SpaceAccounts = SpaceTreasury;
+	type MaxRenewalsPerBlock = MaxSubscriptionRenewalsPerBlock;
+	type MaxTiersPerSpace = MaxSubscriptionTiersPerSpace;
+	type WeightInfo = pallet_subscriptions::weights::SubstrateWeight<Runtime>;
+}
+
 impl pallet_account_follows::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 }
@@ -780,6 +966,8 @@ construct_runtime!(
 		Spaces: pallet_spaces = 76,
 		Posts: pallet_posts = 77,
 		Reactions: pallet_reactions = 78,
+		SpaceTreasury: pallet_space_treasury = 79,
+		Subscriptions: pallet_subscriptions = 80,
 
 		// Temporary
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>} = 255,
@@ -809,6 +997,8 @@ mod benches {
 		[pallet_roles, Roles]
 		[pallet_space_follows, SpaceFollows]
 		[pallet_space_ownership, SpaceOwnership]
+		[pallet_space_treasury, SpaceTreasury]
+		[pallet_subscriptions, Subscriptions]
 		[pallet_spaces, Spaces]
 		[pallet_posts, Posts]
 		[pallet_free_proxy, FreeProxy]
@@ -939,10 +1129,46 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl pallet_domains_rpc_runtime_api::DomainsApi<Block, Balance> for Runtime {
+	impl pallet_domains_rpc_runtime_api::DomainsApi<Block, AccountId, Balance> for Runtime {
 		fn calculate_price(subdomain: Vec<u8>) -> Option<Balance> {
 			Domains::calculate_price(&subdomain)
 		}
+
+		fn primary_domain(account: AccountId) -> Option<Vec<u8>> {
+			Domains::primary_domain(&account).map(|domain| domain.into_inner())
+		}
+	}
+
+	impl pallet_space_ownership_rpc_runtime_api::SpaceOwnershipApi<Block, AccountId, BlockNumber, Moment>
+		for Runtime
+	{
+		fn get_space_ownership_history(
+			space_id: SpaceId,
+		) -> Vec<SpaceOwnershipRecord<AccountId, BlockNumber, Moment>> {
+			SpaceOwnership::space_ownership_history(space_id).into_inner()
+		}
+	}
+
+	impl pallet_permissions_rpc_runtime_api::PermissionsApi<Block, AccountId, pallet_roles::RoleId>
+		for Runtime
+	{
+		fn explain_space_permission(
+			account: AccountId,
+			space_id: SpaceId,
+			permission: pallet_permissions::SpacePermission,
+		) -> Option<pallet_permissions::PermissionTrace<pallet_roles::RoleId>> {
+			Roles::trace_account_space_permission(account, space_id, permission).ok()
+		}
+	}
+
+	impl pallet_spaces_rpc_runtime_api::SpacesApi<Block> for Runtime {
+		fn space_id_by_handle(handle: Vec<u8>) -> Option<SpaceId> {
+			Spaces::space_id_by_any_case_handle(&handle)
+		}
+
+		fn handle_by_space_id(space_id: SpaceId) -> Option<Vec<u8>> {
+			Spaces::handle_by_space_id(space_id).map(|handle| handle.into_inner())
+		}
 	}
 
 	#[cfg(feature = "try-runtime")]